@@ -46,6 +46,70 @@
         }
     });
     
+    // Clicking the status of a TODO item toggles it between done and undone in the source file,
+    // only files of the current workspace can be edited
+    const workspacePrefix = '/view/current/';
+    document.addEventListener('click', function(event) {
+        const indicator = event.target.closest('.status-indicator');
+        const item = indicator && indicator.closest('[data-todo]');
+        const article = document.querySelector('article[data-source-hash]');
+        if (!item || !article || !location.pathname.startsWith(workspacePrefix)) {
+            return;
+        }
+        const params = new URLSearchParams({
+            item: item.dataset.todo,
+            status: item.dataset.status === 'done' ? 'undone' : 'done',
+            hash: article.dataset.sourceHash,
+        });
+        const file = location.pathname.slice(workspacePrefix.length);
+        fetch('/api/todo/' + file + '?' + params, { method: 'POST' })
+            .then(function(response) {
+                if (response.status === 409) {
+                    // the file changed since the page was rendered, show the current version
+                    location.reload();
+                    return;
+                }
+                if (!response.ok) {
+                    throw new Error(response.statusText);
+                }
+                return response.text().then(function(page) {
+                    const rendered = new DOMParser().parseFromString(page, 'text/html');
+                    document.querySelector('main').replaceWith(rendered.querySelector('main'));
+                });
+            })
+            .catch(function(error) {
+                console.error('Couldn\'t change the TODO status', error);
+            });
+    });
+
+    // Follow the cursor and the unsaved changes of an editor, the server sends them for the file
+    document.addEventListener('DOMContentLoaded', function() {
+        if (!location.pathname.startsWith(workspacePrefix) || !document.querySelector('article[data-source-hash]')) {
+            return;
+        }
+        const events = new EventSource('/api/events/' + location.pathname.slice(workspacePrefix.length));
+        // Pages don't know the lines of their elements, so the same part of the article is shown
+        events.addEventListener('scroll', function(event) {
+            const position = JSON.parse(event.data);
+            const article = document.querySelector('article[data-source-hash]');
+            const fraction = position.lines > 1 ? (position.line - 1) / (position.lines - 1) : 0;
+            const top = article.getBoundingClientRect().top + window.scrollY;
+            window.scrollTo({
+                top: top + fraction * article.scrollHeight - window.innerHeight / 2,
+                behavior: 'smooth',
+            });
+        });
+        // Unsaved buffers of the editor, TODO changes are refused until the buffer is saved
+        events.addEventListener('content', function(event) {
+            const preview = JSON.parse(event.data);
+            const article = document.querySelector('article[data-source-hash]');
+            article.innerHTML = preview.content;
+            article.dataset.sourceHash = preview.source_hash;
+            document.title = preview.title;
+            document.querySelector('.site-title').textContent = preview.title;
+        });
+    });
+
     // Listen for system theme changes
     window.matchMedia('(prefers-color-scheme: light)').addEventListener('change', function(e) {
         if (!localStorage.getItem('theme')) {
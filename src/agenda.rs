@@ -0,0 +1,150 @@
+//! agenda of the open tasks of the workspace by date, grouped into overdue, today, this week and upcoming
+//!
+//! due dates and timestamps which have passed are overdue, start dates are only shown until the task starts.
+//! recurring tasks are expanded into their occurrences for the next [`RECURRING_DAYS`] days
+
+use std::fmt::Write;
+
+use chrono::{Datelike, Days, NaiveDate, NaiveTime};
+
+use crate::{
+    html::sanitize_html,
+    tasks::{Task, TaskDate},
+};
+
+/// number of days after today for which occurrences of recurring tasks are shown
+pub const RECURRING_DAYS: u64 = 28;
+
+/// why the task is on the agenda
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    Due,
+    Start,
+    Scheduled,
+    Recurring,
+}
+
+impl ItemKind {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Due => "due",
+            Self::Start => "starts",
+            Self::Scheduled => "scheduled",
+            Self::Recurring => "recurs",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AgendaItem<'a> {
+    pub date: NaiveDate,
+    pub time: Option<NaiveTime>,
+    pub kind: ItemKind,
+    pub task: &'a Task,
+}
+
+/// items of every group in date order
+#[derive(Debug, Default)]
+pub struct Agenda<'a> {
+    pub overdue: Vec<AgendaItem<'a>>,
+    pub today: Vec<AgendaItem<'a>>,
+    /// rest of the week after today, weeks end on sunday
+    pub this_week: Vec<AgendaItem<'a>>,
+    pub upcoming: Vec<AgendaItem<'a>>,
+}
+
+/// agenda of the open tasks as seen on `today`
+pub fn agenda(tasks: &[Task], today: NaiveDate) -> Agenda<'_> {
+    let mut items = Vec::new();
+    for task in tasks.iter().filter(|task| task.is_open()) {
+        let mut add = |date: Option<TaskDate>, kind| {
+            if let Some(TaskDate { date, time }) = date {
+                items.push(AgendaItem {
+                    date,
+                    time,
+                    kind,
+                    task,
+                });
+            }
+        };
+        add(task.due, ItemKind::Due);
+        add(task.timestamp, ItemKind::Scheduled);
+        add(
+            task.start.filter(|start| start.date >= today),
+            ItemKind::Start,
+        );
+        if let Some(recurrence) = task.recurrence() {
+            for date in recurrence.occurrences(today, today + Days::new(RECURRING_DAYS)) {
+                add(Some(TaskDate { date, time: None }), ItemKind::Recurring);
+            }
+        }
+    }
+    items.sort_by(|a, b| (a.date, a.time).cmp(&(b.date, b.time)));
+
+    let end_of_week = today + Days::new((6 - today.weekday().num_days_from_monday()).into());
+    let mut agenda = Agenda::default();
+    for item in items {
+        let group = if item.date < today {
+            &mut agenda.overdue
+        } else if item.date == today {
+            &mut agenda.today
+        } else if item.date <= end_of_week {
+            &mut agenda.this_week
+        } else {
+            &mut agenda.upcoming
+        };
+        group.push(item);
+    }
+    agenda
+}
+
+/// html of the agenda, `task_link` gives the url of the heading of a task
+pub fn agenda_page(
+    agenda: &Agenda,
+    task_link: impl Fn(&Task) -> String,
+) -> Result<String, std::fmt::Error> {
+    let mut content = String::from("<h1>Agenda</h1>\n");
+    let groups = [
+        ("Overdue", "overdue", &agenda.overdue),
+        ("Today", "today", &agenda.today),
+        ("This Week", "this_week", &agenda.this_week),
+        ("Upcoming", "upcoming", &agenda.upcoming),
+    ];
+    if groups.iter().all(|(_, _, items)| items.is_empty()) {
+        content.push_str("<p>No dated tasks.</p>");
+        return Ok(content);
+    }
+    for (title, class, items) in groups {
+        if items.is_empty() {
+            continue;
+        }
+        writeln!(content, "<h2>{title}</h2>")?;
+        writeln!(content, r#"<ul class="agenda {class}">"#)?;
+        for item in items {
+            let time = item
+                .time
+                .map(|time| time.format(" %H:%M").to_string())
+                .unwrap_or_default();
+            write!(
+                content,
+                r#"<li><time datetime="{}">{}{time}</time> <span class="agenda_kind {}">{}</span> <a href="{}">{}</a>"#,
+                item.date,
+                item.date.format("%a, %-d %b %Y"),
+                item.kind.label(),
+                item.kind.label(),
+                sanitize_html(&task_link(item.task)),
+                sanitize_html(&item.task.text),
+            )?;
+            if let Some(priority) = &item.task.priority {
+                write!(
+                    content,
+                    r#" <span class="priority">{}</span>"#,
+                    sanitize_html(priority)
+                )?;
+            }
+            content.push_str("</li>\n");
+        }
+        content.push_str("</ul>\n");
+    }
+    Ok(content)
+}
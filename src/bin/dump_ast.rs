@@ -7,6 +7,9 @@ struct Args {
     // dump flat ast instead of tree
     #[arg(short, long)]
     flat: bool,
+    /// print AST as JSON, see `norgmill::json` for the schema
+    #[arg(short, long)]
+    json: bool,
     path: std::path::PathBuf,
 }
 
@@ -20,7 +23,11 @@ async fn main() -> miette::Result<()> {
         .into_diagnostic()
         .wrap_err_with(|| format!("Couldn't read {:?}", args.path))?;
     debug!(path = %args.path.display(), bytes = input.len(), "Successfully read file content");
-    if args.flat {
+    if args.json {
+        info!(flat = args.flat, "Dumping AST as JSON");
+        let ast = norgmill::json::parse_to_json(&input, args.flat)?;
+        println!("{ast:#}");
+    } else if args.flat {
         info!("Dumping flat AST");
         let tokens = norg::parse(&input).map_err(|e| miette::miette!("failed to parse: {e:?}"))?;
         println!("{tokens:#?}");
@@ -1,8 +1,11 @@
 #![recursion_limit = "512"]
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use miette::{Context, IntoDiagnostic};
-use norgmill::renderer;
+use norgmill::renderer::{
+    self,
+    backend::{LatexBackend, MarkdownBackend},
+};
 use std::fs;
 use std::io::{self, Read, Write};
 use std::path::PathBuf;
@@ -15,9 +18,24 @@ struct Args {
     #[clap(value_parser)]
     input_file: Option<PathBuf>,
 
-    /// Output .html file (writes to stdout if not provided).
+    /// Output file (writes to stdout if not provided).
     #[clap(value_parser)]
     output_file: Option<PathBuf>,
+
+    /// Output format, markdown output is github flavored markdown and latex output is a complete `.tex` document.
+    #[clap(long, value_enum, default_value_t = Format::Html)]
+    format: Format,
+
+    /// Variable in `name=value` form which can be used in the document as `&name&`.
+    #[clap(long = "var", value_parser = renderer::parse_variable)]
+    variables: Vec<(String, String)>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Html,
+    Markdown,
+    Latex,
 }
 
 #[instrument]
@@ -47,14 +65,58 @@ fn main() -> miette::Result<()> {
 
     debug!(bytes = norg_content.len(), "Read Norg content successfully");
 
-    let content = renderer::parse_and_render_norg(&norg_content)
+    let mut ctx = renderer::RenderContext::with_variables(args.variables.iter().cloned().collect());
+    if let Some(path) = &args.input_file {
+        ctx = ctx.with_document_path(path);
+    }
+    let output = match args.format {
+        Format::Html => render_html_page(&args, &norg_content, &mut ctx)?,
+        Format::Markdown => {
+            renderer::render_norg(&norg_content, &mut ctx, &mut MarkdownBackend::default())
+                .wrap_err("Failed to parse and render Norg content")?
+        }
+        Format::Latex => renderer::render_norg(&norg_content, &mut ctx, &mut LatexBackend)
+            .wrap_err("Failed to parse and render Norg content")?,
+    };
+
+    match &args.output_file {
+        Some(path) => {
+            info!(output = %path.display(), "Writing to output file");
+            fs::write(path, output)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Failed to write output to file: {}", path.display()))?;
+        }
+        None => {
+            info!("Writing to stdout");
+            io::stdout()
+                .write_all(output.as_bytes())
+                .into_diagnostic()
+                .wrap_err("Failed to write to stdout")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// renders norg content into a full html page
+fn render_html_page(
+    args: &Args,
+    norg_content: &str,
+    ctx: &mut renderer::RenderContext,
+) -> miette::Result<String> {
+    let content = renderer::parse_and_render_norg_with(norg_content, ctx)
         .wrap_err("Failed to parse and render Norg content")?;
 
-    let title = args
-        .input_file
-        .as_ref()
-        .and_then(|p| p.file_stem())
-        .map(|s| s.to_string_lossy().into_owned())
+    let title = ctx
+        .document_meta()
+        .title
+        .clone()
+        .or_else(|| {
+            args.input_file
+                .as_ref()
+                .and_then(|p| p.file_stem())
+                .map(|s| s.to_string_lossy().into_owned())
+        })
         .unwrap_or_else(|| "Norg Document".to_string());
 
     let escaped_title = norgmill::html::sanitize_html(&title);
@@ -77,22 +139,5 @@ fn main() -> miette::Result<()> {
         bytes = full_html_output.len(),
         "Generated HTML successfully"
     );
-
-    match &args.output_file {
-        Some(path) => {
-            info!(output = %path.display(), "Writing to output file");
-            fs::write(path, full_html_output)
-                .into_diagnostic()
-                .wrap_err_with(|| format!("Failed to write HTML to file: {}", path.display()))?;
-        }
-        None => {
-            info!("Writing to stdout");
-            io::stdout()
-                .write_all(full_html_output.as_bytes())
-                .into_diagnostic()
-                .wrap_err("Failed to write to stdout")?;
-        }
-    }
-
-    Ok(())
+    Ok(full_html_output)
 }
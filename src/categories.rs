@@ -0,0 +1,167 @@
+//! pages listing the documents of every category given in `@document.meta`
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use miette::{Context, IntoDiagnostic};
+use tracing::{debug, instrument, trace, warn};
+
+use crate::{
+    export::{is_norg_file, workspace_files},
+    html::sanitize_html,
+    renderer,
+};
+
+#[derive(Debug, Clone)]
+struct IndexedDocument {
+    title: String,
+    categories: Vec<String>,
+    /// modification time when the meta was read, `None` if it was added without reading the file
+    modified: Option<SystemTime>,
+}
+
+/// categories of the workspace documents, kept up to date by reading only the changed files
+#[derive(Debug, Clone, Default)]
+pub struct CategoryIndex {
+    /// documents by path relative to the workspace root
+    documents: BTreeMap<PathBuf, IndexedDocument>,
+}
+
+/// title of a document without a meta title
+fn file_title(path: &Path) -> String {
+    path.file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+impl CategoryIndex {
+    /// adds an already parsed document, `title` is the meta title of the document
+    pub fn insert(&mut self, path: PathBuf, title: Option<String>, categories: Vec<String>) {
+        let title = title.unwrap_or_else(|| file_title(&path));
+        self.documents.insert(
+            path,
+            IndexedDocument {
+                title,
+                categories,
+                modified: None,
+            },
+        );
+    }
+
+    /// reads meta of the files modified since the last update and removes the deleted files
+    #[instrument(skip(self))]
+    pub fn update(&mut self, root_dir: &Path) -> miette::Result<()> {
+        let files: Vec<PathBuf> = workspace_files(root_dir)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Couldn't read workspace {}", root_dir.display()))?
+            .into_iter()
+            .filter(|file| is_norg_file(file))
+            .collect();
+        self.documents.retain(|path, _| files.contains(path));
+        for file in files {
+            let source = root_dir.join(&file);
+            let modified = std::fs::metadata(&source)
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            let document = self.documents.get(&file);
+            if modified.is_some() && document.is_some_and(|document| document.modified == modified)
+            {
+                trace!(?file, "document is unchanged");
+                continue;
+            }
+            debug!(?file, "reading document meta");
+            let meta = match std::fs::read_to_string(&source)
+                .into_diagnostic()
+                .and_then(|input| renderer::document_meta(&input))
+            {
+                Ok(meta) => meta,
+                Err(e) => {
+                    warn!(?file, "Couldn't read document meta: {e}");
+                    Default::default()
+                }
+            };
+            let title = meta.title.unwrap_or_else(|| file_title(&file));
+            self.documents.insert(
+                file,
+                IndexedDocument {
+                    title,
+                    categories: meta.categories,
+                    modified,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// every category with the path and title of its documents, sorted by name
+    pub fn categories(&self) -> BTreeMap<&str, Vec<(&Path, &str)>> {
+        let mut categories: BTreeMap<&str, Vec<(&Path, &str)>> = BTreeMap::new();
+        for (path, document) in &self.documents {
+            for category in &document.categories {
+                categories
+                    .entry(category)
+                    .or_default()
+                    .push((path, &document.title));
+            }
+        }
+        categories
+    }
+
+    /// path and title of the documents in the category
+    pub fn documents(&self, category: &str) -> Vec<(&Path, &str)> {
+        self.documents
+            .iter()
+            .filter(|(_, document)| document.categories.iter().any(|c| c == category))
+            .map(|(path, document)| (path.as_path(), document.title.as_str()))
+            .collect()
+    }
+}
+
+/// list of the documents in a category, `document_link` gives the url of a document
+pub fn category_page(
+    name: &str,
+    documents: &[(&Path, &str)],
+    document_link: impl Fn(&Path) -> String,
+) -> Result<String, std::fmt::Error> {
+    let mut content = String::new();
+    writeln!(content, "<h1>📂 {}</h1>", sanitize_html(name))?;
+    if documents.is_empty() {
+        content.push_str("<p>No documents in this category.</p>");
+        return Ok(content);
+    }
+    content.push_str("<ul class=\"category\">\n");
+    for (path, title) in documents {
+        writeln!(
+            content,
+            "<li><a href=\"{}\">{}</a></li>",
+            sanitize_html(&document_link(path)),
+            sanitize_html(title)
+        )?;
+    }
+    content.push_str("</ul>");
+    Ok(content)
+}
+
+/// every category with the number of its documents, `category_link` gives the url of a category page
+pub fn categories_overview(
+    index: &CategoryIndex,
+    category_link: impl Fn(&str) -> String,
+) -> Result<String, std::fmt::Error> {
+    let mut content = String::from("<h1>Categories</h1>\n<ul class=\"categories\">\n");
+    for (name, documents) in index.categories() {
+        writeln!(
+            content,
+            "<li><a href=\"{}\">{}</a> ({})</li>",
+            sanitize_html(&category_link(name)),
+            sanitize_html(name),
+            documents.len()
+        )?;
+    }
+    content.push_str("</ul>");
+    Ok(content)
+}
@@ -10,10 +10,41 @@ pub const SYSTEM_PATH: &str = "/view/root";
 /// this path is used to access files from home directory
 pub const HOME_PATH: &str = "/view/home";
 
+/// documents by their `@document.meta` categories
+pub const CATEGORIES_PATH: &str = "/categories";
+
+/// atom feeds of workspace directories
+pub const FEED_PATH: &str = "/feeds";
+
+/// due and scheduled tasks of the workspace by date
+pub const AGENDA_PATH: &str = "/agenda";
+
+/// calendar of the workspace journal
+pub const JOURNAL_PATH: &str = "/journal";
+
 pub const ARG_RAW: &str = "raw";
 
 pub const ARG_RAW_POSSIBLE_VALS: [&str; 3] = ["1", "true", "yes"];
 
+/// query argument for getting flat AST instead of tree from the AST api
+pub const ARG_FLAT: &str = "flat";
+
+/// query argument for the month shown in the journal calendar, e.g. `2024-01`
+pub const ARG_MONTH: &str = "month";
+
+/// query arguments for changing a TODO status, the number of the item in the rendered page,
+/// the new status and the hash of the source the page was rendered from
+pub const ARG_ITEM: &str = "item";
+pub const ARG_STATUS: &str = "status";
+pub const ARG_HASH: &str = "hash";
+
+/// fields of the JSON sent by editors, the file being edited, the line of the cursor,
+/// the number of lines of the buffer and the source of the buffer
+pub const ARG_FILE: &str = "file";
+pub const ARG_LINE: &str = "line";
+pub const ARG_LINES: &str = "lines";
+pub const ARG_SOURCE: &str = "source";
+
 pub mod paths {
     use super::*;
     use const_format::concatcp;
@@ -28,4 +59,35 @@ pub mod paths {
     pub const HOME_FILES: &str = concatcp!(HOME_PATH, "/*file_path");
 
     pub const DIRECTORY_SERVE: &str = "/view/fs";
+
+    /// documents of a single category
+    pub const CATEGORY: &str = concatcp!(CATEGORIES_PATH, "/:name");
+
+    /// feed of a workspace directory, e.g. `/feeds/blog.xml`
+    pub const FEEDS: &str = concatcp!(FEED_PATH, "/*feed");
+
+    /// journal entries of a day, e.g. `/journal/day/2024-01-31`
+    pub const JOURNAL_DAY: &str = concatcp!(JOURNAL_PATH, "/day/:date");
+    /// journal entries of the week containing the day
+    pub const JOURNAL_WEEK: &str = concatcp!(JOURNAL_PATH, "/week/:date");
+    /// journal entries of a month, e.g. `/journal/month/2024-01`
+    pub const JOURNAL_MONTH: &str = concatcp!(JOURNAL_PATH, "/month/:month");
+
+    /// iCalendar of the dated tasks of current workspace
+    pub const TASKS_ICS: &str = "/export/tasks.ics";
+
+    /// JSON AST of any file from current workspace
+    pub const AST_API: &str = "/api/ast/*file_path";
+
+    /// changes a TODO status in a file from current workspace
+    pub const TODO_API: &str = "/api/todo/*file_path";
+
+    /// editors report the position of the cursor here as JSON like
+    /// `{"file": "notes.norg", "line": 12, "lines": 80}`, so that the preview follows it
+    pub const SCROLL_API: &str = "/api/scroll";
+    /// editors send the source of unsaved buffers here as JSON like `{"file": "notes.norg", "source": "..."}`,
+    /// so that the preview updates while typing
+    pub const PREVIEW_API: &str = "/api/preview";
+    /// server sent events of a file from current workspace for the browsers previewing it
+    pub const EVENTS_API: &str = "/api/events/*file_path";
 }
@@ -0,0 +1,106 @@
+//! converting every norg file of a workspace into other formats
+
+use std::path::{Path, PathBuf};
+
+use miette::{Context, IntoDiagnostic};
+use tracing::{debug, info, instrument, trace};
+
+use crate::renderer::{self, backend::Backend, RenderContext};
+
+/// every file in the workspace relative to the root, hidden files and directories are skipped
+pub fn workspace_files(root: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut directories = vec![PathBuf::new()];
+    while let Some(directory) = directories.pop() {
+        for entry in std::fs::read_dir(root.join(&directory))? {
+            let entry = entry?;
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                trace!(path = ?entry.path(), "skipping hidden file");
+                continue;
+            }
+            let relative = directory.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                directories.push(relative);
+            } else {
+                files.push(relative);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// `output_dir` relative to `root_dir` when the output is written into a directory inside the workspace
+pub fn relative_output_dir(root_dir: &Path, output_dir: &Path) -> Option<PathBuf> {
+    let relative = match output_dir.strip_prefix(root_dir) {
+        Ok(relative) => relative.to_path_buf(),
+        Err(_) => output_dir
+            .canonicalize()
+            .ok()?
+            .strip_prefix(root_dir.canonicalize().ok()?)
+            .ok()?
+            .to_path_buf(),
+    };
+    (!relative.as_os_str().is_empty()).then_some(relative)
+}
+
+pub fn is_norg_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "norg")
+}
+
+/// renders every norg file with the backend given by `new_backend` and writes it with `extension` into `output_dir`,
+/// other files are copied as it is so that images and attachments keep working
+///
+/// returns number of rendered norg files
+#[instrument(skip(variables, new_backend))]
+pub fn export_workspace(
+    root_dir: &Path,
+    output_dir: &Path,
+    extension: &str,
+    variables: &std::collections::HashMap<String, String>,
+    new_backend: impl Fn(&Path) -> Box<dyn Backend>,
+) -> miette::Result<usize> {
+    let mut files = workspace_files(root_dir)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Couldn't read workspace {}", root_dir.display()))?;
+    if let Some(output) = relative_output_dir(root_dir, output_dir) {
+        debug!(?output, "skipping output directory inside the workspace");
+        files.retain(|file| !file.starts_with(&output));
+    }
+    let includes = std::sync::Arc::new(renderer::IncludeCache::default());
+    let mut rendered = 0;
+    for file in files {
+        let source = root_dir.join(&file);
+        let mut target = output_dir.join(&file);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Couldn't create directory {}", parent.display()))?;
+        }
+        if !is_norg_file(&file) {
+            debug!(?file, "copying file");
+            std::fs::copy(&source, &target)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Couldn't copy {}", source.display()))?;
+            continue;
+        }
+
+        debug!(?file, "rendering file");
+        let content = std::fs::read_to_string(&source)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Couldn't read {}", source.display()))?;
+        let mut ctx = RenderContext::with_variables(variables.clone())
+            .with_document_path(&source)
+            .with_workspace_root(root_dir)
+            .with_include_cache(includes.clone());
+        let output = renderer::render_norg(&content, &mut ctx, new_backend(&file).as_mut())
+            .wrap_err_with(|| format!("Couldn't render {}", source.display()))?;
+        target.set_extension(extension);
+        std::fs::write(&target, output)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Couldn't write {}", target.display()))?;
+        rendered += 1;
+    }
+    info!(rendered, "exported workspace");
+    Ok(rendered)
+}
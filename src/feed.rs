@@ -0,0 +1,219 @@
+//! atom feeds of workspace directories, every norg document in the directory is an entry
+//!
+//! entries use `title`, `description`, `authors`, `created` and `updated` of `@document.meta`,
+//! the feed itself is authored by the `author` workspace variable or the workspace
+
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use miette::{Context, IntoDiagnostic};
+use tracing::{debug, instrument, warn};
+
+use crate::{
+    export::{is_norg_file, workspace_files},
+    html::{percent_encode, sanitize_html},
+    renderer::{self, backend::HtmlBackend, RenderContext},
+};
+
+#[derive(Debug, Clone)]
+pub struct FeedEntry {
+    /// path of the document relative to the workspace root
+    pub path: PathBuf,
+    pub title: String,
+    pub description: Option<String>,
+    pub authors: Vec<String>,
+    pub published: Option<DateTime<FixedOffset>>,
+    pub updated: DateTime<FixedOffset>,
+    /// rendered html of the document
+    pub content: String,
+}
+
+/// dates of document meta, neorg writes `2024-01-31T20:15:00+0530` but plain dates and rfc3339 are also accepted
+///
+/// dates without time are taken as midnight in utc
+pub fn parse_date(value: &str) -> Option<DateTime<FixedOffset>> {
+    let value = value.trim();
+    DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%z")
+        .or_else(|_| DateTime::parse_from_rfc3339(value))
+        .ok()
+        .or_else(|| {
+            let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+            Some(date.and_hms_opt(0, 0, 0)?.and_utc().fixed_offset())
+        })
+}
+
+/// percent encodes characters which can't be part of an IRI
+fn encode_iri(path: &str) -> String {
+    path.split('/')
+        .map(percent_encode)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// renders every document in `directory` of the workspace, newest entries are first
+#[instrument(skip(variables))]
+pub fn feed_entries(
+    root_dir: &Path,
+    directory: &Path,
+    variables: &HashMap<String, String>,
+) -> miette::Result<Vec<FeedEntry>> {
+    let files = workspace_files(&root_dir.join(directory))
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Couldn't read feed directory {}", directory.display()))?;
+    let mut entries = Vec::new();
+    for file in files.into_iter().filter(|file| is_norg_file(file)) {
+        let path = directory.join(&file);
+        let source = root_dir.join(&path);
+        debug!(?path, "adding feed entry");
+        let input = std::fs::read_to_string(&source)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Couldn't read {}", source.display()))?;
+        let mut ctx = RenderContext::with_variables(variables.clone())
+            .with_document_path(&source)
+            .with_workspace_root(root_dir);
+        let content = renderer::render_norg(&input, &mut ctx, &mut HtmlBackend)
+            .wrap_err_with(|| format!("Couldn't render {}", source.display()))?;
+
+        let meta = ctx.document_meta().clone();
+        let published = meta.created.as_deref().and_then(parse_date);
+        let updated = meta
+            .updated
+            .as_deref()
+            .and_then(parse_date)
+            .or(published)
+            .or_else(|| {
+                let modified = std::fs::metadata(&source).ok()?.modified().ok()?;
+                Some(DateTime::<Utc>::from(modified).fixed_offset())
+            })
+            .unwrap_or_default();
+        if published.is_none() {
+            warn!(?path, "feed entry doesn't have a creation date");
+        }
+        entries.push(FeedEntry {
+            title: meta.title.unwrap_or_else(|| {
+                file.file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .into_owned()
+            }),
+            description: meta.description,
+            authors: meta.authors,
+            published,
+            updated,
+            content,
+            path,
+        });
+    }
+    entries.sort_by(|a, b| b.updated.cmp(&a.updated).then_with(|| a.path.cmp(&b.path)));
+    Ok(entries)
+}
+
+/// author of the feed, the `author` workspace variable or the name of the workspace directory
+pub fn feed_author(root_dir: &Path, variables: &HashMap<String, String>) -> String {
+    if let Some(author) = variables.get("author").filter(|a| !a.trim().is_empty()) {
+        return author.trim().to_string();
+    }
+    root_dir
+        .canonicalize()
+        .ok()
+        .as_deref()
+        .unwrap_or(root_dir)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| env!("CARGO_PKG_NAME").to_string())
+}
+
+/// atom feed of the directory, `self_link` is the url of the feed and `entry_link` gives the url of a document
+///
+/// atom requires an author for every entry, so `author` is written for the feed and is used by
+/// the entries without authors
+pub fn atom_feed(
+    directory: &Path,
+    self_link: &str,
+    author: &str,
+    entries: &[FeedEntry],
+    entry_link: impl Fn(&Path) -> String,
+) -> Result<String, std::fmt::Error> {
+    let mut feed = String::new();
+    let name = directory.to_string_lossy();
+    let updated = entries
+        .iter()
+        .map(|entry| entry.updated)
+        .max()
+        .unwrap_or_default();
+    writeln!(feed, r#"<?xml version="1.0" encoding="utf-8"?>"#)?;
+    writeln!(feed, r#"<feed xmlns="http://www.w3.org/2005/Atom">"#)?;
+    writeln!(feed, "  <title>{}</title>", sanitize_html(&name))?;
+    writeln!(feed, "  <id>urn:norgmill:feed:{}</id>", encode_iri(&name))?;
+    writeln!(feed, "  <updated>{}</updated>", updated.to_rfc3339())?;
+    writeln!(
+        feed,
+        "  <author><name>{}</name></author>",
+        sanitize_html(author)
+    )?;
+    writeln!(
+        feed,
+        r#"  <link rel="self" type="application/atom+xml" href="{}"/>"#,
+        sanitize_html(self_link)
+    )?;
+    writeln!(
+        feed,
+        r#"  <generator>{}</generator>"#,
+        env!("CARGO_PKG_NAME")
+    )?;
+    for entry in entries {
+        let link = entry_link(&entry.path);
+        writeln!(feed, "  <entry>")?;
+        writeln!(feed, "    <title>{}</title>", sanitize_html(&entry.title))?;
+        writeln!(
+            feed,
+            "    <id>urn:norgmill:{}</id>",
+            encode_iri(&entry.path.to_string_lossy())
+        )?;
+        writeln!(
+            feed,
+            r#"    <link rel="alternate" type="text/html" href="{}"/>"#,
+            sanitize_html(&link)
+        )?;
+        if let Some(published) = entry.published {
+            writeln!(
+                feed,
+                "    <published>{}</published>",
+                published.to_rfc3339()
+            )?;
+        }
+        writeln!(
+            feed,
+            "    <updated>{}</updated>",
+            entry.updated.to_rfc3339()
+        )?;
+        for author in &entry.authors {
+            writeln!(
+                feed,
+                "    <author><name>{}</name></author>",
+                sanitize_html(author)
+            )?;
+        }
+        if let Some(description) = &entry.description {
+            writeln!(
+                feed,
+                "    <summary>{}</summary>",
+                sanitize_html(description)
+            )?;
+        }
+        // links in the content are relative to the page of the entry
+        writeln!(
+            feed,
+            r#"    <content type="html" xml:base="{}">{}</content>"#,
+            sanitize_html(&link),
+            sanitize_html(&entry.content)
+        )?;
+        writeln!(feed, "  </entry>")?;
+    }
+    writeln!(feed, "</feed>")?;
+    Ok(feed)
+}
@@ -67,10 +67,101 @@ pub fn write_char_sanitized(ch: char, output: &mut String) {
     }
 }
 
+/// Reverses [`sanitize_html`], used to read attribute values of the rendered html
+pub fn unescape_html(input: &str) -> String {
+    const ENTITIES: [(&str, char); 5] = [
+        ("&lt;", '<'),
+        ("&gt;", '>'),
+        ("&amp;", '&'),
+        ("&quot;", '"'),
+        ("&#x27;", '\''),
+    ];
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match ENTITIES.iter().find(|(entity, _)| rest.starts_with(entity)) {
+            Some((entity, ch)) => {
+                output.push(*ch);
+                rest = &rest[entity.len()..];
+            }
+            None => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Percent encodes everything except unreserved characters, so that text can be used as a url path segment
+///
+/// # Examples
+///
+/// ```
+/// use norgmill::html::percent_encode;
+///
+/// assert_eq!(percent_encode("machine learning"), "machine%20learning");
+/// assert_eq!(percent_encode("a/b"), "a%2Fb");
+/// ```
+pub fn percent_encode(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                output.push(byte as char)
+            }
+            _ => output.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    output
+}
+
+/// Decodes `%XX` sequences, invalid sequences are kept as they are
+pub fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let decoded = (bytes[index] == b'%')
+            .then(|| input.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match decoded {
+            Some(byte) => {
+                output.push(byte);
+                index += 3;
+            }
+            None => {
+                output.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&output).into_owned()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_percent_encoding_round_trip() {
+        let input = "notes/日本 & more";
+        assert_eq!(percent_decode(&percent_encode(input)), input);
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+
+    #[test]
+    fn test_unescape_html_round_trip() {
+        let input = "<a href=\"x\">Fish & 'Chips'</a> &copy;";
+        assert_eq!(unescape_html(&sanitize_html(input)), input);
+        assert_eq!(unescape_html("&copy; &"), "&copy; &");
+    }
+
     #[test]
     fn test_sanitize_html_basic_escaping() {
         assert_eq!(sanitize_html("<"), "&lt;");
@@ -0,0 +1,209 @@
+//! iCalendar export of the dated tasks, so that deadlines can be subscribed from a calendar client
+//!
+//! tasks with a due or start date are written as `VTODO` and tasks with a timestamp as `VEVENT`.
+//! recurring tasks get an `RRULE`, those without any date start on their next occurrence
+
+use chrono::{DateTime, Days, NaiveDate, Utc, Weekday};
+
+use crate::{
+    site::content_hash,
+    tasks::{Recurrence, Task, TaskDate},
+};
+
+/// escapes characters which have a meaning in iCalendar text values
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// writes a content line, lines longer than 75 octets are folded as required by RFC 5545
+fn write_line(output: &mut String, line: &str) -> std::fmt::Result {
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            output.push_str("\r\n ");
+            // the space of the continuation line is part of its length
+            length = 1;
+        }
+        output.push(c);
+        length += c.len_utf8();
+    }
+    output.push_str("\r\n");
+    Ok(())
+}
+
+/// `DUE;VALUE=DATE:20240131` for dates without time and `DUE:20240131T143000` otherwise
+fn date_property(name: &str, date: TaskDate) -> String {
+    match date.time {
+        Some(time) => format!(
+            "{name}:{}T{}",
+            date.date.format("%Y%m%d"),
+            time.format("%H%M%S")
+        ),
+        None => format!("{name};VALUE=DATE:{}", date.date.format("%Y%m%d")),
+    }
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn rrule(recurrence: Recurrence) -> String {
+    match recurrence {
+        Recurrence::Weekly(weekday) => format!("RRULE:FREQ=WEEKLY;BYDAY={}", weekday_code(weekday)),
+        Recurrence::Monthly(day) => format!("RRULE:FREQ=MONTHLY;BYMONTHDAY={day}"),
+        Recurrence::Yearly(month, day) => {
+            format!("RRULE:FREQ=YEARLY;BYMONTH={month};BYMONTHDAY={day}")
+        }
+    }
+}
+
+/// `STATUS` of a `VTODO`
+fn todo_status(status: &norg::TodoStatus) -> &'static str {
+    match status {
+        norg::TodoStatus::Done => "COMPLETED",
+        norg::TodoStatus::Canceled => "CANCELLED",
+        norg::TodoStatus::Pending => "IN-PROCESS",
+        norg::TodoStatus::Undone
+        | norg::TodoStatus::NeedsClarification
+        | norg::TodoStatus::Paused
+        | norg::TodoStatus::Urgent
+        | norg::TodoStatus::Recurring(_) => "NEEDS-ACTION",
+    }
+}
+
+/// `STATUS` of a `VEVENT`
+fn event_status(status: &norg::TodoStatus) -> &'static str {
+    match status {
+        norg::TodoStatus::Canceled => "CANCELLED",
+        norg::TodoStatus::NeedsClarification => "TENTATIVE",
+        _ => "CONFIRMED",
+    }
+}
+
+/// `PRIORITY` from 1 (highest) to 9, priorities `A`, `B` and `C` are high, medium and low
+fn priority(task: &Task) -> Option<u8> {
+    match task.priority.as_deref().map(str::trim) {
+        Some("A" | "a") => Some(1),
+        Some("B" | "b") => Some(5),
+        Some("C" | "c") => Some(9),
+        Some(priority) => priority
+            .parse()
+            .ok()
+            .filter(|priority| (1..=9).contains(priority)),
+        None => matches!(task.status, Some(norg::TodoStatus::Urgent)).then_some(1),
+    }
+}
+
+/// stable id of the task, so that calendar clients update the same item after changes of its dates
+fn uid(task: &Task, component: &str) -> String {
+    let id = format!(
+        "{}\n{:?}\n{}\n{component}",
+        task.path.display(),
+        task.heading,
+        task.text
+    );
+    format!("{}@norgmill", content_hash(id.as_bytes()))
+}
+
+fn write_common(
+    output: &mut String,
+    task: &Task,
+    component: &str,
+    stamp: &str,
+) -> std::fmt::Result {
+    write_line(output, &format!("UID:{}", uid(task, component)))?;
+    write_line(output, &format!("DTSTAMP:{stamp}"))?;
+    write_line(output, &format!("SUMMARY:{}", escape_text(&task.text)))?;
+    let location = match &task.heading {
+        Some((_, heading)) => format!("{} > {heading}", task.path.display()),
+        None => task.path.display().to_string(),
+    };
+    write_line(output, &format!("DESCRIPTION:{}", escape_text(&location)))?;
+    if let Some(priority) = priority(task) {
+        write_line(output, &format!("PRIORITY:{priority}"))?;
+    }
+    Ok(())
+}
+
+/// first occurrence of a recurring task on or after `today`
+fn next_occurrence(recurrence: Recurrence, today: NaiveDate) -> Option<NaiveDate> {
+    // every recurrence other than the 29th of february happens within a year
+    recurrence
+        .occurrences(today, today + Days::new(366))
+        .first()
+        .copied()
+}
+
+/// iCalendar with the dated tasks, `stamp` is the time of the export
+pub fn tasks_calendar(tasks: &[Task], stamp: DateTime<Utc>) -> Result<String, std::fmt::Error> {
+    let stamp_text = stamp.format("%Y%m%dT%H%M%SZ").to_string();
+    let today = stamp.date_naive();
+    let mut output = String::new();
+    write_line(&mut output, "BEGIN:VCALENDAR")?;
+    write_line(&mut output, "VERSION:2.0")?;
+    write_line(
+        &mut output,
+        &format!("PRODID:-//norgmill//{}//EN", env!("CARGO_PKG_VERSION")),
+    )?;
+    write_line(&mut output, "CALSCALE:GREGORIAN")?;
+    for task in tasks {
+        let recurrence = task.recurrence();
+        // rules of recurring todos need a start, the due date or the next occurrence is used
+        // unless the task is an event
+        let start = task.start.or_else(|| match recurrence? {
+            _ if task.due.is_some() => task.due,
+            _ if task.timestamp.is_some() => None,
+            recurrence => Some(TaskDate {
+                date: next_occurrence(recurrence, today)?,
+                time: None,
+            }),
+        });
+        if task.due.is_some() || start.is_some() {
+            write_line(&mut output, "BEGIN:VTODO")?;
+            write_common(&mut output, task, "VTODO", &stamp_text)?;
+            if let Some(start) = start {
+                write_line(&mut output, &date_property("DTSTART", start))?;
+            }
+            // due has to be after the start, recurring todos start on their due date
+            if let Some(due) = task
+                .due
+                .filter(|due| start.is_none_or(|start| *due > start))
+            {
+                write_line(&mut output, &date_property("DUE", due))?;
+            }
+            if let Some(status) = &task.status {
+                write_line(&mut output, &format!("STATUS:{}", todo_status(status)))?;
+            }
+            if let Some(recurrence) = recurrence {
+                write_line(&mut output, &rrule(recurrence))?;
+            }
+            write_line(&mut output, "END:VTODO")?;
+        }
+        if let Some(timestamp) = task.timestamp {
+            write_line(&mut output, "BEGIN:VEVENT")?;
+            write_common(&mut output, task, "VEVENT", &stamp_text)?;
+            write_line(&mut output, &date_property("DTSTART", timestamp))?;
+            if let Some(status) = &task.status {
+                write_line(&mut output, &format!("STATUS:{}", event_status(status)))?;
+            }
+            // the rule is already on the todo if the task has one
+            if let Some(recurrence) = recurrence.filter(|_| start.is_none()) {
+                write_line(&mut output, &rrule(recurrence))?;
+            }
+            write_line(&mut output, "END:VEVENT")?;
+        }
+    }
+    write_line(&mut output, "END:VCALENDAR")?;
+    Ok(output)
+}
@@ -0,0 +1,299 @@
+//! journal of the workspace, neorg writes its entries as `journal/YYYY/MM/DD.norg`
+//!
+//! the calendar shows a month with links to the days which have an entry,
+//! day, week and month views show every entry of the period one after the other
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+use miette::{Context, IntoDiagnostic};
+use tracing::{debug, instrument};
+
+use crate::{
+    constants,
+    export::workspace_files,
+    html::sanitize_html,
+    navigation,
+    renderer::{self, backend::HtmlBackend, RenderContext},
+};
+
+/// journal directory of a workspace and its dated entries
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Journal {
+    /// journal directory relative to the workspace root
+    pub directory: PathBuf,
+    /// entries by date, paths are relative to the workspace root
+    pub entries: BTreeMap<NaiveDate, PathBuf>,
+}
+
+impl Journal {
+    /// journal of the workspace, the `journal` directory closest to the root which has dated entries
+    #[instrument]
+    pub fn find(root_dir: &Path) -> miette::Result<Option<Self>> {
+        let files = workspace_files(root_dir)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Couldn't read workspace {}", root_dir.display()))?;
+        let directory = files
+            .iter()
+            .filter_map(|file| {
+                let journal = navigation::journal_root(file)?;
+                navigation::journal_date(file.strip_prefix(journal).ok()?)?;
+                Some(journal)
+            })
+            .min_by_key(|journal| (journal.components().count(), journal.to_path_buf()));
+        let Some(directory) = directory else {
+            debug!("workspace doesn't have a journal");
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            entries: navigation::journal_entries(root_dir, directory),
+            directory: directory.to_path_buf(),
+        }))
+    }
+
+    /// entries of the period in date order
+    pub fn entries_of(&self, period: Period) -> impl Iterator<Item = (&NaiveDate, &PathBuf)> {
+        let last = period.last().unwrap_or(NaiveDate::MAX);
+        self.entries.range(period.first()..=last)
+    }
+}
+
+/// days shown together in a journal view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Day(NaiveDate),
+    /// week starting on monday
+    Week(NaiveDate),
+    /// month starting on the first day
+    Month(NaiveDate),
+}
+
+impl Period {
+    /// week containing the date, `None` when the monday is before the first supported date
+    pub fn week(date: NaiveDate) -> Option<Self> {
+        date.checked_sub_days(Days::new(date.weekday().num_days_from_monday().into()))
+            .map(Self::Week)
+    }
+
+    /// month containing the date
+    pub fn month(date: NaiveDate) -> Self {
+        Self::Month(date.with_day(1).unwrap_or(date))
+    }
+
+    /// month written as `2024-01`
+    pub fn parse_month(month: &str) -> Option<Self> {
+        NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d")
+            .ok()
+            .map(Self::Month)
+    }
+
+    pub fn first(&self) -> NaiveDate {
+        match *self {
+            Self::Day(date) | Self::Week(date) | Self::Month(date) => date,
+        }
+    }
+
+    /// `None` when the period runs past the last supported date
+    pub fn last(&self) -> Option<NaiveDate> {
+        match *self {
+            Self::Day(date) => Some(date),
+            Self::Week(date) => date.checked_add_days(Days::new(6)),
+            Self::Month(date) => date
+                .checked_add_months(Months::new(1))?
+                .checked_sub_days(Days::new(1)),
+        }
+    }
+
+    /// `None` when the previous period starts before the first supported date
+    pub fn prev(&self) -> Option<Self> {
+        match *self {
+            Self::Day(date) => date.checked_sub_days(Days::new(1)).map(Self::Day),
+            Self::Week(date) => date.checked_sub_days(Days::new(7)).map(Self::Week),
+            Self::Month(date) => date.checked_sub_months(Months::new(1)).map(Self::Month),
+        }
+    }
+
+    /// `None` when the next period starts after the last supported date
+    pub fn next(&self) -> Option<Self> {
+        match *self {
+            Self::Day(date) => date.checked_add_days(Days::new(1)).map(Self::Day),
+            Self::Week(date) => date.checked_add_days(Days::new(7)).map(Self::Week),
+            Self::Month(date) => date.checked_add_months(Months::new(1)).map(Self::Month),
+        }
+    }
+
+    /// the period when it and its neighbours are within the supported dates, so that views can link to them
+    pub fn checked(self) -> Option<Self> {
+        (self.last().is_some() && self.prev().is_some() && self.next()?.last().is_some())
+            .then_some(self)
+    }
+
+    pub fn title(&self) -> String {
+        match *self {
+            Self::Day(date) => date.format("%A, %-d %B %Y").to_string(),
+            Self::Week(date) => {
+                let week = date.iso_week();
+                format!("Week {}, {}", week.week(), week.year())
+            }
+            Self::Month(date) => date.format("%B %Y").to_string(),
+        }
+    }
+
+    /// url of the view of the period
+    pub fn link(&self) -> String {
+        match *self {
+            Self::Day(date) => format!("{}/day/{date}", constants::JOURNAL_PATH),
+            Self::Week(date) => format!("{}/week/{date}", constants::JOURNAL_PATH),
+            Self::Month(date) => {
+                format!("{}/month/{}", constants::JOURNAL_PATH, date.format("%Y-%m"))
+            }
+        }
+    }
+}
+
+/// url of the calendar showing the month of the date
+fn calendar_link(date: NaiveDate) -> String {
+    format!(
+        "{}?{}={}",
+        constants::JOURNAL_PATH,
+        constants::ARG_MONTH,
+        date.format("%Y-%m")
+    )
+}
+
+/// links to the previous and next period around `current`, a side is left out when it is outside the supported dates
+fn journal_nav(period: Period, current: &str, link: impl Fn(Period) -> String) -> String {
+    let mut nav = String::from(r#"<nav class="journal_nav">"#);
+    if let Some(prev) = period.prev() {
+        nav.push_str(&format!(
+            r#"<a href="{}">← {}</a> "#,
+            link(prev),
+            prev.title()
+        ));
+    }
+    nav.push_str(current);
+    if let Some(next) = period.next() {
+        nav.push_str(&format!(
+            r#" <a href="{}">{} →</a>"#,
+            link(next),
+            next.title()
+        ));
+    }
+    nav.push_str("</nav>");
+    nav
+}
+
+/// month grid of the journal, days with an entry link to their view and `today` is highlighted
+pub fn calendar(
+    journal: &Journal,
+    month: NaiveDate,
+    today: NaiveDate,
+) -> Result<String, std::fmt::Error> {
+    let period = Period::month(month);
+    let mut content = String::new();
+    let current = format!(r#"<a href="{}">Read {}</a>"#, period.link(), period.title());
+    writeln!(
+        content,
+        "{}",
+        journal_nav(period, &current, |month| calendar_link(month.first()))
+    )?;
+    content.push_str(r#"<table class="journal_calendar">"#);
+    writeln!(content, "<caption>{}</caption>", period.title())?;
+    content.push_str("<thead><tr><th>Week</th>");
+    for day in ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"] {
+        write!(content, "<th>{day}</th>")?;
+    }
+    content.push_str("</tr></thead>\n<tbody>\n");
+    let last = period.last().unwrap_or(NaiveDate::MAX);
+    let mut next_week = Period::week(period.first());
+    while let Some(week) = next_week.filter(|week| week.first() <= last) {
+        let week_number = week.first().iso_week().week();
+        write!(
+            content,
+            r#"<tr><th><a href="{}">{week_number}</a></th>"#,
+            week.link()
+        )?;
+        for date in week.first().iter_days().take(7) {
+            let mut classes = Vec::new();
+            if date.month() != period.first().month() {
+                classes.push("other_month");
+            }
+            if date == today {
+                classes.push("today");
+            }
+            let day = if journal.entries.contains_key(&date) {
+                classes.push("has_entry");
+                format!(
+                    r#"<a href="{}">{}</a>"#,
+                    Period::Day(date).link(),
+                    date.day()
+                )
+            } else {
+                date.day().to_string()
+            };
+            if classes.is_empty() {
+                write!(content, "<td>{day}</td>")?;
+            } else {
+                write!(content, r#"<td class="{}">{day}</td>"#, classes.join(" "))?;
+            }
+        }
+        content.push_str("</tr>\n");
+        next_week = week.next();
+    }
+    content.push_str("</tbody></table>");
+    Ok(content)
+}
+
+/// every entry of the period rendered one after the other, with links to the previous and next period
+#[instrument(skip(journal, variables))]
+pub fn period_view(
+    root_dir: &Path,
+    journal: &Journal,
+    period: Period,
+    variables: &HashMap<String, String>,
+) -> miette::Result<String> {
+    let mut content = String::new();
+    let current = format!(
+        r#"<a href="{}">Calendar</a>"#,
+        calendar_link(period.first())
+    );
+    let nav = journal_nav(period, &current, |period| period.link());
+    content.push_str(&nav);
+    let mut empty = true;
+    for (date, path) in journal.entries_of(period) {
+        empty = false;
+        let source = root_dir.join(path);
+        debug!(?path, "adding journal entry");
+        let input = std::fs::read_to_string(&source)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Couldn't read {}", source.display()))?;
+        let mut ctx = RenderContext::with_variables(variables.clone())
+            .with_document_path(&source)
+            .with_workspace_root(root_dir);
+        let entry = renderer::render_norg(&input, &mut ctx, &mut HtmlBackend)
+            .wrap_err_with(|| format!("Couldn't render {}", source.display()))?;
+        let heading = ctx
+            .document_meta()
+            .title
+            .clone()
+            .unwrap_or_else(|| Period::Day(*date).title());
+        write!(
+            content,
+            "\n<section class=\"journal_entry\"><h2><a href=\"{}/{}\">{}</a></h2>\n{entry}</section>",
+            constants::CURRENT_WORKSPACE_PATH,
+            sanitize_html(&path.to_string_lossy()),
+            sanitize_html(&heading)
+        )
+        .into_diagnostic()?;
+    }
+    if empty {
+        content.push_str("\n<p>No journal entries.</p>");
+    }
+    content.push('\n');
+    content.push_str(&nav);
+    Ok(content)
+}
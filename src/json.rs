@@ -0,0 +1,400 @@
+//! JSON export of the parsed norg AST, so that external tools don't have to depend on rust types
+//!
+//! # Schema
+//!
+//! Output is versioned with [`SCHEMA_VERSION`], which is bumped whenever a field is removed or changes meaning,
+//! new fields can be added without changing the version.
+//!
+//! ```text
+//! document   = { "schema_version": 2, "kind": "tree" | "flat", "nodes": [node] }
+//! node       = { "type": <node type>, ...fields }
+//!   paragraph                    segments: [segment]
+//!   nestable_detached_modifier   modifier: "quote" | "unordered_list" | "ordered_list", level, extensions: [extension],
+//!                                text: node, content: [node] (tree) or node (flat)
+//!   rangeable_detached_modifier  modifier: "definition" | "footnote" | "table", title: [segment],
+//!                                extensions: [extension], content: [node]
+//!   heading                      level, title: [segment], extensions: [extension], content: [node] (tree only)
+//!   carryover_tag                tag_type: "attribute" | "macro", name: [string], parameters: [string], next_object: node
+//!   verbatim_ranged_tag          name: [string], parameters: [string], content: string
+//!   ranged_tag                   name: [string], parameters: [string], content: [node]
+//!   infirm_tag                   name: [string], parameters: [string]
+//! segment    = { "type": <segment type>, ...fields }
+//!   text            text: string
+//!   whitespace
+//!   special         char: string
+//!   escape          char: string
+//!   attached_modifier  modifier: string, content: [segment]
+//!   link            file: string | null, targets: [target], description: [segment] | null
+//!   inline_verbatim text: string
+//!   attached_modifier_opener | attached_modifier_opener_fail | attached_modifier_closer_candidate
+//!   | attached_modifier_closer | attached_modifier_candidate | anchor_definition | anchor
+//!   | inline_link_target
+//!                   no fields, left unresolved by the parser
+//! target     = { "type": "heading", level, title: [segment] }
+//!            | { "type": "footnote" | "definition" | "wiki" | "generic" | "extendable", title: [segment] }
+//!            | { "type": "url" | "path" | "timestamp", value: string }
+//!            | { "type": "line_number", line: number }
+//! extension  = { "type": "todo", status: "undone" | "done" | "needs_clarification" | "paused" | "urgent"
+//!                  | "recurring" | "pending" | "canceled", recurrence: string | null }
+//!            | { "type": "priority" | "timestamp" | "due_date" | "start_date", value: string }
+//! ```
+//!
+//! Nodes don't have source positions, the parser doesn't keep them.
+use serde_json::{json, Value};
+
+/// version of the JSON schema, see the module documentation
+pub const SCHEMA_VERSION: u32 = 2;
+
+fn todo_status(status: &norg::TodoStatus) -> &'static str {
+    match status {
+        norg::TodoStatus::Undone => "undone",
+        norg::TodoStatus::Done => "done",
+        norg::TodoStatus::NeedsClarification => "needs_clarification",
+        norg::TodoStatus::Paused => "paused",
+        norg::TodoStatus::Urgent => "urgent",
+        norg::TodoStatus::Recurring(_) => "recurring",
+        norg::TodoStatus::Pending => "pending",
+        norg::TodoStatus::Canceled => "canceled",
+    }
+}
+
+fn nestable_modifier(modifier: &norg::NestableDetachedModifier) -> &'static str {
+    match modifier {
+        norg::NestableDetachedModifier::Quote => "quote",
+        norg::NestableDetachedModifier::UnorderedList => "unordered_list",
+        norg::NestableDetachedModifier::OrderedList => "ordered_list",
+    }
+}
+
+fn rangeable_modifier(modifier: &norg::RangeableDetachedModifier) -> &'static str {
+    match modifier {
+        norg::RangeableDetachedModifier::Definition => "definition",
+        norg::RangeableDetachedModifier::Footnote => "footnote",
+        norg::RangeableDetachedModifier::Table => "table",
+    }
+}
+
+fn carryover_tag(tag: &norg::CarryoverTag) -> &'static str {
+    match tag {
+        norg::CarryoverTag::Attribute => "attribute",
+        norg::CarryoverTag::Macro => "macro",
+    }
+}
+
+/// segments which only exist while the parser resolves attached modifiers and anchors, only their
+/// type is written as their content isn't part of the schema
+fn unresolved(kind: &str) -> Value {
+    json!({ "type": kind })
+}
+
+fn token(token: &norg::ParagraphSegmentToken) -> Value {
+    match token {
+        norg::ParagraphSegmentToken::Text(text) => json!({ "type": "text", "text": text }),
+        norg::ParagraphSegmentToken::Whitespace => json!({ "type": "whitespace" }),
+        norg::ParagraphSegmentToken::Special(c) => {
+            json!({ "type": "special", "char": c.to_string() })
+        }
+        norg::ParagraphSegmentToken::Escape(c) => {
+            json!({ "type": "escape", "char": c.to_string() })
+        }
+    }
+}
+
+fn segments(segments: &[norg::ParagraphSegment]) -> Value {
+    Value::Array(segments.iter().map(segment).collect())
+}
+
+fn segment(segment: &norg::ParagraphSegment) -> Value {
+    match segment {
+        norg::ParagraphSegment::Token(t) => token(t),
+        norg::ParagraphSegment::AttachedModifier {
+            modifier_type,
+            content,
+        } => json!({
+            "type": "attached_modifier",
+            "modifier": modifier_type.to_string(),
+            "content": segments(content),
+        }),
+        norg::ParagraphSegment::Link {
+            filepath,
+            targets,
+            description,
+        } => json!({
+            "type": "link",
+            "file": filepath,
+            "targets": targets.iter().map(link_target).collect::<Vec<_>>(),
+            "description": description.as_deref().map(segments),
+        }),
+        norg::ParagraphSegment::InlineVerbatim(tokens) => {
+            let mut text = String::new();
+            for token in tokens {
+                match token {
+                    norg::ParagraphSegmentToken::Text(t) => text.push_str(t),
+                    norg::ParagraphSegmentToken::Whitespace => text.push(' '),
+                    norg::ParagraphSegmentToken::Special(c)
+                    | norg::ParagraphSegmentToken::Escape(c) => text.push(*c),
+                }
+            }
+            json!({ "type": "inline_verbatim", "text": text })
+        }
+        norg::ParagraphSegment::AttachedModifierOpener(_) => unresolved("attached_modifier_opener"),
+        norg::ParagraphSegment::AttachedModifierOpenerFail(_) => {
+            unresolved("attached_modifier_opener_fail")
+        }
+        norg::ParagraphSegment::AttachedModifierCloserCandidate(_) => {
+            unresolved("attached_modifier_closer_candidate")
+        }
+        norg::ParagraphSegment::AttachedModifierCloser(_) => unresolved("attached_modifier_closer"),
+        norg::ParagraphSegment::AttachedModifierCandidate { .. } => {
+            unresolved("attached_modifier_candidate")
+        }
+        norg::ParagraphSegment::AnchorDefinition { .. } => unresolved("anchor_definition"),
+        norg::ParagraphSegment::Anchor { .. } => unresolved("anchor"),
+        norg::ParagraphSegment::InlineLinkTarget(_) => unresolved("inline_link_target"),
+    }
+}
+
+fn link_target(target: &norg::LinkTarget) -> Value {
+    match target {
+        norg::LinkTarget::Heading { level, title } => {
+            json!({ "type": "heading", "level": level, "title": segments(title) })
+        }
+        norg::LinkTarget::Footnote(title) => {
+            json!({ "type": "footnote", "title": segments(title) })
+        }
+        norg::LinkTarget::Definition(title) => {
+            json!({ "type": "definition", "title": segments(title) })
+        }
+        norg::LinkTarget::Wiki(title) => json!({ "type": "wiki", "title": segments(title) }),
+        norg::LinkTarget::Generic(title) => json!({ "type": "generic", "title": segments(title) }),
+        norg::LinkTarget::Extendable(title) => {
+            json!({ "type": "extendable", "title": segments(title) })
+        }
+        norg::LinkTarget::Url(value) => json!({ "type": "url", "value": value }),
+        norg::LinkTarget::Path(value) => json!({ "type": "path", "value": value }),
+        norg::LinkTarget::Timestamp(value) => json!({ "type": "timestamp", "value": value }),
+        norg::LinkTarget::LineNumber(line) => json!({ "type": "line_number", "line": line }),
+    }
+}
+
+fn extensions(extensions: &[norg::DetachedModifierExtension]) -> Value {
+    Value::Array(
+        extensions
+            .iter()
+            .map(|extension| match extension {
+                norg::DetachedModifierExtension::Todo(status) => json!({
+                    "type": "todo",
+                    "status": todo_status(status),
+                    "recurrence": match status {
+                        norg::TodoStatus::Recurring(recurrence) => recurrence.clone(),
+                        _ => None,
+                    },
+                }),
+                norg::DetachedModifierExtension::Priority(value) => {
+                    json!({ "type": "priority", "value": value })
+                }
+                norg::DetachedModifierExtension::Timestamp(value) => {
+                    json!({ "type": "timestamp", "value": value })
+                }
+                norg::DetachedModifierExtension::DueDate(value) => {
+                    json!({ "type": "due_date", "value": value })
+                }
+                norg::DetachedModifierExtension::StartDate(value) => {
+                    json!({ "type": "start_date", "value": value })
+                }
+            })
+            .collect(),
+    )
+}
+
+fn tree_node(node: &norg::NorgAST) -> Value {
+    match node {
+        norg::NorgAST::Paragraph(paragraph) => {
+            json!({ "type": "paragraph", "segments": segments(paragraph) })
+        }
+        norg::NorgAST::NestableDetachedModifier {
+            modifier_type,
+            level,
+            extensions: modifier_extensions,
+            text,
+            content,
+        } => json!({
+            "type": "nestable_detached_modifier",
+            "modifier": nestable_modifier(modifier_type),
+            "level": level,
+            "extensions": extensions(modifier_extensions),
+            "text": flat_node(text),
+            "content": content.iter().map(tree_node).collect::<Vec<_>>(),
+        }),
+        norg::NorgAST::RangeableDetachedModifier {
+            modifier_type,
+            title,
+            extensions: modifier_extensions,
+            content,
+        } => json!({
+            "type": "rangeable_detached_modifier",
+            "modifier": rangeable_modifier(modifier_type),
+            "title": segments(title),
+            "extensions": extensions(modifier_extensions),
+            "content": content.iter().map(flat_node).collect::<Vec<_>>(),
+        }),
+        norg::NorgAST::Heading {
+            level,
+            title,
+            extensions: heading_extensions,
+            content,
+        } => json!({
+            "type": "heading",
+            "level": level,
+            "title": segments(title),
+            "extensions": extensions(heading_extensions),
+            "content": content.iter().map(tree_node).collect::<Vec<_>>(),
+        }),
+        norg::NorgAST::CarryoverTag {
+            tag_type,
+            name,
+            parameters,
+            next_object,
+        } => json!({
+            "type": "carryover_tag",
+            "tag_type": carryover_tag(tag_type),
+            "name": name,
+            "parameters": parameters,
+            "next_object": tree_node(next_object),
+        }),
+        norg::NorgAST::VerbatimRangedTag {
+            name,
+            parameters,
+            content,
+        } => json!({
+            "type": "verbatim_ranged_tag",
+            "name": name,
+            "parameters": parameters,
+            "content": content,
+        }),
+        norg::NorgAST::RangedTag {
+            name,
+            parameters,
+            content,
+        } => json!({
+            "type": "ranged_tag",
+            "name": name,
+            "parameters": parameters,
+            "content": content.iter().map(flat_node).collect::<Vec<_>>(),
+        }),
+        norg::NorgAST::InfirmTag { name, parameters } => json!({
+            "type": "infirm_tag",
+            "name": name,
+            "parameters": parameters,
+        }),
+    }
+}
+
+fn flat_node(node: &norg::NorgASTFlat) -> Value {
+    match node {
+        norg::NorgASTFlat::Paragraph(paragraph) => {
+            json!({ "type": "paragraph", "segments": segments(paragraph) })
+        }
+        norg::NorgASTFlat::NestableDetachedModifier {
+            modifier_type,
+            level,
+            extensions: modifier_extensions,
+            content,
+        } => json!({
+            "type": "nestable_detached_modifier",
+            "modifier": nestable_modifier(modifier_type),
+            "level": level,
+            "extensions": extensions(modifier_extensions),
+            "content": flat_node(content),
+        }),
+        norg::NorgASTFlat::RangeableDetachedModifier {
+            modifier_type,
+            title,
+            extensions: modifier_extensions,
+            content,
+        } => json!({
+            "type": "rangeable_detached_modifier",
+            "modifier": rangeable_modifier(modifier_type),
+            "title": segments(title),
+            "extensions": extensions(modifier_extensions),
+            "content": content.iter().map(flat_node).collect::<Vec<_>>(),
+        }),
+        norg::NorgASTFlat::Heading {
+            level,
+            title,
+            extensions: heading_extensions,
+        } => json!({
+            "type": "heading",
+            "level": level,
+            "title": segments(title),
+            "extensions": extensions(heading_extensions),
+        }),
+        norg::NorgASTFlat::CarryoverTag {
+            tag_type,
+            name,
+            parameters,
+            next_object,
+        } => json!({
+            "type": "carryover_tag",
+            "tag_type": carryover_tag(tag_type),
+            "name": name,
+            "parameters": parameters,
+            "next_object": flat_node(next_object),
+        }),
+        norg::NorgASTFlat::VerbatimRangedTag {
+            name,
+            parameters,
+            content,
+        } => json!({
+            "type": "verbatim_ranged_tag",
+            "name": name,
+            "parameters": parameters,
+            "content": content,
+        }),
+        norg::NorgASTFlat::RangedTag {
+            name,
+            parameters,
+            content,
+        } => json!({
+            "type": "ranged_tag",
+            "name": name,
+            "parameters": parameters,
+            "content": content.iter().map(flat_node).collect::<Vec<_>>(),
+        }),
+        norg::NorgASTFlat::InfirmTag { name, parameters } => json!({
+            "type": "infirm_tag",
+            "name": name,
+            "parameters": parameters,
+        }),
+    }
+}
+
+/// tree AST where content of headings and nested list items is inside their parent
+pub fn tree_to_json(nodes: &[norg::NorgAST]) -> Value {
+    json!({
+        "schema_version": SCHEMA_VERSION,
+        "kind": "tree",
+        "nodes": nodes.iter().map(tree_node).collect::<Vec<_>>(),
+    })
+}
+
+/// flat AST as produced by the parser, before headings and lists are nested
+pub fn flat_to_json(nodes: &[norg::NorgASTFlat]) -> Value {
+    json!({
+        "schema_version": SCHEMA_VERSION,
+        "kind": "flat",
+        "nodes": nodes.iter().map(flat_node).collect::<Vec<_>>(),
+    })
+}
+
+/// parses norg document and converts it into JSON
+pub fn parse_to_json(input: &str, flat: bool) -> miette::Result<Value> {
+    if flat {
+        let nodes = norg::parse(input).map_err(|e| miette::miette!("failed to parse: {e:?}"))?;
+        Ok(flat_to_json(&nodes))
+    } else {
+        let nodes =
+            norg::parse_tree(input).map_err(|e| miette::miette!("failed to parse: {e:?}"))?;
+        Ok(tree_to_json(&nodes))
+    }
+}
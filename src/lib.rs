@@ -1,5 +1,15 @@
 #![recursion_limit = "512"]
 
+pub mod agenda;
+pub mod categories;
 pub mod constants;
+pub mod export;
+pub mod feed;
 pub mod html;
+pub mod ical;
+pub mod journal;
+pub mod json;
+pub mod navigation;
 pub mod renderer;
+pub mod site;
+pub mod tasks;
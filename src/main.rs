@@ -1,43 +1,137 @@
 #![recursion_limit = "512"]
-use std::{collections::HashMap, sync::Arc, time::SystemTime};
+use std::{
+    collections::HashMap,
+    io::{IsTerminal, Write},
+    sync::Arc,
+    time::SystemTime,
+};
 
 use axum::{
     extract::{Path, Query, State},
-    response::{Html, Redirect},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, Redirect,
+    },
     routing, Router,
 };
 use clap::{Parser, Subcommand};
 use dotenv::dotenv;
 use miette::{miette, Context, IntoDiagnostic};
-use norgmill::{constants, renderer};
+use norgmill::{
+    constants,
+    journal::{Journal, Period},
+    navigation::{self, Navigation},
+    renderer, site,
+};
 use tokio::net::TcpListener;
 use tracing::{debug, error, info, instrument, level_filters::LevelFilter, trace, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 /// parsed html content, if the file is already parsed and no new change is found then serve it immediately
+///
+/// only the title and the rendered document are cached, navigation is computed on every request
+/// as it changes with the other files of the directory
 #[derive(Debug, Clone)]
 struct ParsedFile {
+    title: String,
     content: String,
+    /// hash of the norg source, pages send it back when they ask to edit the source
+    source_hash: String,
     last_modified_time: std::time::SystemTime,
+    /// files included while rendering, cache is invalid if any of these are modified
+    dependencies: Vec<std::path::PathBuf>,
+    /// pages showing the current date with `.date` are never cached, as they change every day
+    uses_date: bool,
+}
+
+/// number of preview events kept for browsers which are slow to receive them
+const PREVIEW_EVENTS: usize = 16;
+/// largest editor buffer which is previewed, in bytes of its JSON
+const PREVIEW_SIZE_LIMIT: usize = 4 * 1024 * 1024;
+
+/// change pushed to the browsers previewing a file, paths are relative to the workspace root
+#[derive(Debug, Clone)]
+enum PreviewEvent {
+    /// cursor of the editor moved to the line of the buffer with `lines` lines
+    Scroll {
+        file: std::path::PathBuf,
+        line: usize,
+        lines: usize,
+    },
+    /// unsaved buffer of the editor was rendered
+    Content {
+        file: std::path::PathBuf,
+        title: String,
+        content: String,
+        source_hash: String,
+    },
+}
+
+impl PreviewEvent {
+    fn file(&self) -> &std::path::Path {
+        match self {
+            Self::Scroll { file, .. } | Self::Content { file, .. } => file,
+        }
+    }
+
+    fn to_sse(&self) -> Event {
+        match self {
+            Self::Scroll { line, lines, .. } => Event::default()
+                .event("scroll")
+                .data(serde_json::json!({ "line": line, "lines": lines }).to_string()),
+            Self::Content {
+                title,
+                content,
+                source_hash,
+                ..
+            } => Event::default().event("content").data(
+                serde_json::json!({
+                    "title": title,
+                    "content": content,
+                    "source_hash": source_hash,
+                })
+                .to_string(),
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 struct AppState {
     root_dir: std::path::PathBuf,
     parsed_files: dashmap::DashMap<std::path::PathBuf, ParsedFile>,
+    /// workspace level variables, documents can use these with `&name&`
+    variables: HashMap<String, String>,
+    /// workspace directories which have an atom feed
+    feeds: Vec<std::path::PathBuf>,
+    /// categories of workspace documents, updated with the changed files on every category request
+    categories: std::sync::Mutex<norgmill::categories::CategoryIndex>,
+    /// events for the browsers previewing workspace files
+    preview: tokio::sync::broadcast::Sender<PreviewEvent>,
+    /// parsed files included by the workspace documents
+    includes: Arc<renderer::IncludeCache>,
+    /// whether pages can change the workspace files, like the TODO statuses
+    allow_edits: bool,
 }
 
 impl AppState {
-    fn insert_cache_file(&self, file_path: std::path::PathBuf, content: String) {
-        info!(?file_path, "caching rendered file");
-        let new_entry = ParsedFile {
-            content,
-            last_modified_time: SystemTime::now(),
-        };
-        self.parsed_files.insert(file_path, new_entry);
+    /// context for rendering a workspace document, the document path is set by the caller
+    fn render_context(&self) -> renderer::RenderContext {
+        renderer::RenderContext::with_variables(self.variables.clone())
+            .with_workspace_root(&self.root_dir)
+            .with_include_cache(self.includes.clone())
+    }
+
+    fn insert_cache_file(&self, file_path: std::path::PathBuf, parsed_file: ParsedFile) {
+        if parsed_file.uses_date {
+            debug!(?file_path, "not caching file showing the current date");
+            return;
+        }
+        info!(?file_path, dependencies = ?parsed_file.dependencies, "caching rendered file");
+        self.parsed_files.insert(file_path, parsed_file);
     }
 
-    async fn get_cached_file(&self, file_path: &std::path::PathBuf) -> Option<String> {
+    async fn get_cached_file(&self, file_path: &std::path::PathBuf) -> Option<ParsedFile> {
         trace!(?file_path, "checking for cached rendered file");
         let parsed_file = self.parsed_files.get(file_path);
         let metadata = match tokio::fs::metadata(file_path).await {
@@ -51,50 +145,714 @@ impl AppState {
             }
         };
         let last_modified_time = metadata.modified().ok()?;
-        parsed_file
-            .filter(|parsed_file| last_modified_time < parsed_file.last_modified_time)
-            .map(|parsed_entry| parsed_entry.content.clone())
+        let parsed_file = parsed_file
+            .filter(|parsed_file| last_modified_time < parsed_file.last_modified_time)?
+            .clone();
+        for dependency in &parsed_file.dependencies {
+            let dependency_modified = tokio::fs::metadata(dependency)
+                .await
+                .and_then(|metadata| metadata.modified());
+            match dependency_modified {
+                Ok(modified) if modified < parsed_file.last_modified_time => {}
+                Ok(_) => {
+                    debug!(?dependency, "included file is modified");
+                    return None;
+                }
+                Err(e) => {
+                    debug!(?dependency, error = ?e, "included file is not accessible");
+                    return None;
+                }
+            }
+        }
+        Some(parsed_file)
     }
 
+    /// page of the norg file, `root` is the directory served under `view` like `/view/current`
+    ///
+    /// a missing `index.norg` of a directory is served as a listing of the directory,
+    /// so that up links work in directories without an index
     async fn get_or_insert_cached_file(
         &self,
+        root: &std::path::Path,
+        view: &str,
         file_path: &mut std::path::PathBuf,
     ) -> Result<Html<String>, http::StatusCode> {
         update_extension(file_path);
-        if let Some(s) = self.get_cached_file(file_path).await {
+        let document = file_path
+            .strip_prefix(root)
+            .unwrap_or(file_path)
+            .to_path_buf();
+        let directory = document.parent().unwrap_or(std::path::Path::new(""));
+        let (title, content, source_hash) = if document
+            .file_name()
+            .is_some_and(|name| name == "index.norg")
+            && !file_path.exists()
+            && root.join(directory).is_dir()
+        {
+            info!(?file_path, "listing directory without an index");
+            let (title, content) = site::read_directory_index(root, directory).map_err(|e| {
+                error!("Couldn't list directory: {e}");
+                http::StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+            (title, content, None)
+        } else if let Some(cached) = self.get_cached_file(file_path).await {
             info!(?file_path, "returning cached file");
-            Ok(Html(s))
+            (cached.title, cached.content, Some(cached.source_hash))
         } else {
             info!(?file_path, "rendering fresh copy");
-            let rendered_file = read_and_render_file(file_path).await?;
-            self.insert_cache_file(file_path.clone(), rendered_file.0.clone());
-            Ok(rendered_file)
-        }
+            let parsed_file = read_and_render_file(file_path, self.render_context()).await?;
+            self.insert_cache_file(file_path.clone(), parsed_file.clone());
+            (
+                parsed_file.title,
+                parsed_file.content,
+                Some(parsed_file.source_hash),
+            )
+        };
+        let navigation = navigation::navigation(root, &document);
+        Ok(generate_norg_html_page(
+            title,
+            content,
+            view,
+            &navigation,
+            source_hash.as_deref(),
+        ))
     }
 }
 
-#[instrument(skip(file_path))]
-async fn render_norg_file<'a>(file_path: &std::path::PathBuf) -> miette::Result<(String, String)> {
+#[instrument(skip(file_path, ctx))]
+async fn render_norg_file<'a>(
+    file_path: &std::path::PathBuf,
+    ctx: renderer::RenderContext,
+) -> miette::Result<ParsedFile> {
     trace!("rendering norg file");
+    // changes made while rendering are newer than the cached copy
+    let last_modified_time = SystemTime::now();
     let content = tokio::fs::read_to_string(&file_path)
         .await
         .into_diagnostic()
         .wrap_err_with(|| miette!("reading file: {file_path:?}"))?;
     debug!(path = %file_path.display(), "Successfully read file content");
+    let parsed_file = render_norg_source(content, file_path, ctx).await?;
+    Ok(ParsedFile {
+        last_modified_time,
+        ..parsed_file
+    })
+}
+
+/// renders the source of a norg file, which may not be saved yet, links are resolved from `file_path`
+#[instrument(skip(content, file_path, ctx))]
+async fn render_norg_source(
+    content: String,
+    file_path: &std::path::Path,
+    ctx: renderer::RenderContext,
+) -> miette::Result<ParsedFile> {
+    let last_modified_time = SystemTime::now();
+    let source_hash = site::content_hash(content.as_bytes());
 
-    let title = file_path
+    let file_stem = file_path
         .file_stem()
         .expect("norg file without stem cannot be present")
         .to_string_lossy()
         .to_string();
-    let content_div = tokio::task::spawn_blocking(move || {
-        renderer::parse_and_render_norg(&content).wrap_err("Couldn't parse the file")
+    let mut ctx = ctx.with_document_path(file_path).with_todo_items();
+    let (content_div, dependencies, meta, uses_date) = tokio::task::spawn_blocking(move || {
+        renderer::parse_and_render_norg_with(&content, &mut ctx)
+            .wrap_err("Couldn't parse the file")
+            .map(|content_div| {
+                (
+                    content_div,
+                    ctx.dependencies().to_vec(),
+                    ctx.document_meta().clone(),
+                    ctx.uses_date(),
+                )
+            })
     })
     .await
     .into_diagnostic()
     .wrap_err("Couldn't spawn blocking thread")??;
     debug!(path = %file_path.display(), "Successfully generated HTML page");
-    Ok((title, content_div))
+    // page title is the title from document meta, file name is used for documents without one
+    let title = meta.title.unwrap_or(file_stem);
+    Ok(ParsedFile {
+        title,
+        content: content_div,
+        source_hash,
+        last_modified_time,
+        dependencies,
+        uses_date,
+    })
+}
+
+/// JSON AST of a workspace file, flat AST is returned when `flat` query argument is set
+#[instrument(skip(state))]
+async fn ast_of_workspace_file(
+    State(state): State<Arc<AppState>>,
+    Query(qparams): Query<HashMap<String, String>>,
+    Path(norg_file_path): Path<std::path::PathBuf>,
+) -> Result<axum::Json<serde_json::Value>, http::StatusCode> {
+    let mut file_path = state.root_dir.clone();
+    file_path.push(&norg_file_path);
+    update_extension(&mut file_path);
+    let flat = qparams
+        .get(constants::ARG_FLAT)
+        .is_some_and(|val| constants::ARG_RAW_POSSIBLE_VALS.contains(&val.to_lowercase().as_str()));
+    let input = match tokio::fs::read_to_string(&file_path).await {
+        Ok(input) => input,
+        Err(e) => {
+            warn!(path = %file_path.display(), "couldn't read file for AST: {e}");
+            return Err(http::StatusCode::NOT_FOUND);
+        }
+    };
+    match norgmill::json::parse_to_json(&input, flat) {
+        Ok(ast) => Ok(axum::Json(ast)),
+        Err(e) => {
+            error!(path = %file_path.display(), "failed to parse file for AST: {e}");
+            Err(http::StatusCode::UNPROCESSABLE_ENTITY)
+        }
+    }
+}
+
+/// whether the request is sent by a page of this server, browsers send the origin of the page with
+/// `POST` requests, so pages of other sites can't use the api
+fn is_same_origin(headers: &http::HeaderMap) -> bool {
+    let header =
+        |name: http::header::HeaderName| headers.get(name).and_then(|value| value.to_str().ok());
+    match (header(http::header::ORIGIN), header(http::header::HOST)) {
+        (Some(origin), Some(host)) => origin.strip_prefix("http://") == Some(host),
+        _ => false,
+    }
+}
+
+/// whether the request to the editor api is sent by an editor or a page of this server, editors don't
+/// send an origin. the host has to be `localhost` or an ip address, so that other sites can't send
+/// requests from their pages by resolving their own name to this server
+fn is_editor_request(headers: &http::HeaderMap) -> bool {
+    let header =
+        |name: http::header::HeaderName| headers.get(name).and_then(|value| value.to_str().ok());
+    let Some(host) = header(http::header::HOST) else {
+        return false;
+    };
+    let is_local = host.parse::<http::uri::Authority>().is_ok_and(|authority| {
+        let name = authority.host();
+        name == "localhost"
+            || name
+                .trim_matches(['[', ']'])
+                .parse::<std::net::IpAddr>()
+                .is_ok()
+    });
+    is_local
+        && header(http::header::ORIGIN)
+            .is_none_or(|origin| origin.strip_prefix("http://") == Some(host))
+}
+
+/// changes the TODO status of a list item of a workspace file and returns the page rendered again
+///
+/// items are numbered in the page, the numbers are only valid for the source the page was rendered from,
+/// so `409 Conflict` is returned if the hash of the file doesn't match the one given with the page.
+/// `403 Forbidden` unless the server was started with `--allow-edits` and the request is sent by its page
+#[instrument(skip(state, headers))]
+async fn set_todo_status(
+    State(state): State<Arc<AppState>>,
+    Query(qparams): Query<HashMap<String, String>>,
+    Path(norg_file_path): Path<std::path::PathBuf>,
+    headers: http::HeaderMap,
+) -> Result<Html<String>, http::StatusCode> {
+    if !state.allow_edits {
+        warn!(
+            ?norg_file_path,
+            "editing is not allowed, start the server with --allow-edits"
+        );
+        return Err(http::StatusCode::FORBIDDEN);
+    }
+    if !is_same_origin(&headers) {
+        warn!(
+            ?norg_file_path,
+            "TODO change is not sent by a page of the server"
+        );
+        return Err(http::StatusCode::FORBIDDEN);
+    }
+    let arg = |name: &str| qparams.get(name).ok_or(http::StatusCode::BAD_REQUEST);
+    let item = arg(constants::ARG_ITEM)?
+        .parse::<usize>()
+        .map_err(|_| http::StatusCode::BAD_REQUEST)?;
+    let status = arg(constants::ARG_STATUS)?;
+    if renderer::todo::todo_marker(status).is_none() {
+        warn!(%status, "unknown TODO status");
+        return Err(http::StatusCode::BAD_REQUEST);
+    }
+    // only files inside the workspace can be edited
+    if norg_file_path
+        .components()
+        .any(|component| !matches!(component, std::path::Component::Normal(_)))
+    {
+        warn!(?norg_file_path, "path is outside of the workspace");
+        return Err(http::StatusCode::BAD_REQUEST);
+    }
+    let mut file_path = state.root_dir.join(&norg_file_path);
+    update_extension(&mut file_path);
+    let input = match tokio::fs::read_to_string(&file_path).await {
+        Ok(input) => input,
+        Err(e) => {
+            warn!(path = %file_path.display(), "couldn't read file for editing: {e}");
+            return Err(http::StatusCode::NOT_FOUND);
+        }
+    };
+    if site::content_hash(input.as_bytes()) != *arg(constants::ARG_HASH)? {
+        info!(path = %file_path.display(), "file changed since it was rendered");
+        return Err(http::StatusCode::CONFLICT);
+    }
+    let change = {
+        let status = status.to_string();
+        // the document is parsed again for every place the status may be at
+        tokio::task::spawn_blocking(move || renderer::todo::set_todo_status(&input, item, &status))
+            .await
+            .map_err(|_| http::StatusCode::INTERNAL_SERVER_ERROR)?
+    };
+    match change {
+        Some(renderer::todo::TodoChange::Changed(updated)) => {
+            if let Err(e) = tokio::fs::write(&file_path, updated).await {
+                error!(path = %file_path.display(), "couldn't write the TODO status: {e}");
+                return Err(http::StatusCode::INTERNAL_SERVER_ERROR);
+            }
+            info!(path = %file_path.display(), item, %status, "changed TODO status");
+            state.parsed_files.remove(&file_path);
+        }
+        Some(renderer::todo::TodoChange::Unchanged) => {
+            debug!(path = %file_path.display(), item, %status, "TODO item already has the status");
+        }
+        None => {
+            warn!(path = %file_path.display(), item, "couldn't find the TODO status in the source");
+            return Err(http::StatusCode::CONFLICT);
+        }
+    }
+    let root_dir = state.root_dir.clone();
+    state
+        .get_or_insert_cached_file(&root_dir, constants::CURRENT_WORKSPACE_PATH, &mut file_path)
+        .await
+}
+
+/// workspace file of a path given by an editor, absolute paths have to be inside the workspace
+fn editor_file(root_dir: &std::path::Path, file: &str) -> Option<std::path::PathBuf> {
+    let file = std::path::Path::new(file);
+    let mut relative = if file.is_absolute() {
+        // editors may give the path with symlinks resolved
+        let canonical_root = root_dir.canonicalize().ok();
+        let canonical_file = file.canonicalize().ok();
+        file.strip_prefix(root_dir)
+            .ok()
+            .or_else(|| {
+                canonical_file
+                    .as_deref()?
+                    .strip_prefix(canonical_root.as_deref()?)
+                    .ok()
+            })?
+            .to_path_buf()
+    } else {
+        file.to_path_buf()
+    };
+    update_extension(&mut relative);
+    Some(relative)
+}
+
+/// position of the cursor in an editor, browsers previewing the file scroll to the same part of the page
+///
+/// the parser doesn't keep the positions of the elements, so the page is scrolled by the position of
+/// the line in the buffer, which is only close to the element of the line.
+/// position is sent as JSON, `403 Forbidden` unless [`is_editor_request`]
+#[instrument(skip(state, headers))]
+async fn editor_scroll(
+    State(state): State<Arc<AppState>>,
+    headers: http::HeaderMap,
+    axum::Json(position): axum::Json<serde_json::Value>,
+) -> http::StatusCode {
+    if !is_editor_request(&headers) {
+        warn!("scroll position is not sent by an editor");
+        return http::StatusCode::FORBIDDEN;
+    }
+    let number = |name: &str| {
+        position
+            .get(name)
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|number| usize::try_from(number).ok())
+    };
+    let (Some(file), Some(line), Some(lines)) = (
+        position
+            .get(constants::ARG_FILE)
+            .and_then(serde_json::Value::as_str),
+        number(constants::ARG_LINE),
+        number(constants::ARG_LINES),
+    ) else {
+        return http::StatusCode::BAD_REQUEST;
+    };
+    let Some(file) = editor_file(&state.root_dir, file) else {
+        debug!(%file, "file is not in the workspace");
+        return http::StatusCode::NOT_FOUND;
+    };
+    trace!(?file, line, lines, "editor moved");
+    // there may not be any browser previewing the file, that is not an error
+    let _ = state
+        .preview
+        .send(PreviewEvent::Scroll { file, line, lines });
+    http::StatusCode::NO_CONTENT
+}
+
+/// norg source of an unsaved editor buffer, browsers previewing the file show it rendered
+///
+/// buffer is sent as JSON, `403 Forbidden` unless [`is_editor_request`] and `413 Payload Too Large`
+/// for buffers over [`PREVIEW_SIZE_LIMIT`]
+#[instrument(skip(state, headers, buffer))]
+async fn editor_preview(
+    State(state): State<Arc<AppState>>,
+    headers: http::HeaderMap,
+    axum::Json(buffer): axum::Json<serde_json::Value>,
+) -> http::StatusCode {
+    if !is_editor_request(&headers) {
+        warn!("editor buffer is not sent by an editor");
+        return http::StatusCode::FORBIDDEN;
+    }
+    let field = |name: &str| buffer.get(name).and_then(serde_json::Value::as_str);
+    let (Some(file), Some(source)) = (field(constants::ARG_FILE), field(constants::ARG_SOURCE))
+    else {
+        return http::StatusCode::BAD_REQUEST;
+    };
+    let Some(file) = editor_file(&state.root_dir, file) else {
+        debug!(%file, "file is not in the workspace");
+        return http::StatusCode::NOT_FOUND;
+    };
+    // the buffer is rendered as if it was saved, includes are resolved from the file
+    if file.file_stem().is_none()
+        || file
+            .components()
+            .any(|component| !matches!(component, std::path::Component::Normal(_)))
+    {
+        warn!(?file, "path is outside of the workspace");
+        return http::StatusCode::BAD_REQUEST;
+    }
+    let parsed_file = match render_norg_source(
+        source.to_string(),
+        &state.root_dir.join(&file),
+        state.render_context(),
+    )
+    .await
+    {
+        Ok(parsed_file) => parsed_file,
+        Err(e) => {
+            // buffers are often invalid while typing, the preview keeps the last render
+            debug!(?file, "couldn't render the editor buffer: {e:?}");
+            return http::StatusCode::UNPROCESSABLE_ENTITY;
+        }
+    };
+    trace!(?file, "rendered editor buffer");
+    let _ = state.preview.send(PreviewEvent::Content {
+        file,
+        title: parsed_file.title,
+        content: parsed_file.content,
+        source_hash: parsed_file.source_hash,
+    });
+    http::StatusCode::NO_CONTENT
+}
+
+/// server sent events of a workspace file, pages subscribe to these to follow the editor
+#[instrument(skip(state))]
+async fn preview_events(
+    State(state): State<Arc<AppState>>,
+    Path(norg_file_path): Path<std::path::PathBuf>,
+) -> Sse<impl futures_util::stream::Stream<Item = Result<Event, std::convert::Infallible>>> {
+    let mut file = norg_file_path;
+    update_extension(&mut file);
+    debug!(?file, "browser is previewing the file");
+    let events = futures_util::stream::unfold(state.preview.subscribe(), move |mut receiver| {
+        let file = file.clone();
+        async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) if event.file() == file.as_path() => {
+                        return Some((Ok(event.to_sse()), receiver))
+                    }
+                    Ok(_) => {}
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                        debug!(skipped, "browser missed preview events");
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        }
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+/// atom feed of a workspace directory given with `--feed`, e.g. `/feeds/blog.xml`
+#[instrument(skip(state))]
+async fn workspace_feed(
+    State(state): State<Arc<AppState>>,
+    Path(feed): Path<String>,
+) -> Result<impl axum::response::IntoResponse, http::StatusCode> {
+    let directory = std::path::PathBuf::from(feed.strip_suffix(".xml").unwrap_or(&feed));
+    if !state.feeds.contains(&directory) {
+        debug!(?directory, "feed is not enabled for the directory");
+        return Err(http::StatusCode::NOT_FOUND);
+    }
+    let feed_state = state.clone();
+    let feed = tokio::task::spawn_blocking(move || {
+        let entries =
+            norgmill::feed::feed_entries(&feed_state.root_dir, &directory, &feed_state.variables)?;
+        norgmill::feed::atom_feed(
+            &directory,
+            &format!("{}/{feed}", constants::FEED_PATH),
+            &norgmill::feed::feed_author(&feed_state.root_dir, &feed_state.variables),
+            &entries,
+            |path| format!("{}/{}", constants::CURRENT_WORKSPACE_PATH, path.display()),
+        )
+        .into_diagnostic()
+    })
+    .await;
+    match feed {
+        Ok(Ok(feed)) => Ok((
+            [(axum::http::header::CONTENT_TYPE, "application/atom+xml")],
+            feed,
+        )),
+        Ok(Err(e)) => {
+            error!("Couldn't generate the feed: {e}");
+            Err(http::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+        Err(e) => {
+            error!("Couldn't spawn blocking thread: {e}");
+            Err(http::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// runs `f` with the category index updated with the changed workspace files
+async fn with_categories<T: Send + 'static>(
+    state: Arc<AppState>,
+    f: impl FnOnce(&norgmill::categories::CategoryIndex) -> Result<T, std::fmt::Error> + Send + 'static,
+) -> Result<T, http::StatusCode> {
+    let result = tokio::task::spawn_blocking(move || {
+        let mut index = state
+            .categories
+            .lock()
+            .map_err(|_| miette!("category index is poisoned"))?;
+        index.update(&state.root_dir)?;
+        f(&index).into_diagnostic()
+    })
+    .await;
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => {
+            error!("Couldn't generate category page: {e}");
+            Err(http::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+        Err(e) => {
+            error!("Couldn't spawn blocking thread: {e}");
+            Err(http::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+#[instrument(skip(state))]
+async fn categories_overview(
+    State(state): State<Arc<AppState>>,
+) -> Result<Html<String>, http::StatusCode> {
+    let content = with_categories(state, |index| {
+        norgmill::categories::categories_overview(index, |name| {
+            format!(
+                "{}/{}",
+                constants::CATEGORIES_PATH,
+                norgmill::html::percent_encode(name)
+            )
+        })
+    })
+    .await?;
+    Ok(generate_norg_html_page(
+        "Categories".to_string(),
+        content,
+        constants::CURRENT_WORKSPACE_PATH,
+        &Navigation::default(),
+        None,
+    ))
+}
+
+#[instrument(skip(state))]
+async fn category_page(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Html<String>, http::StatusCode> {
+    let title = format!("Category: {name}");
+    let content = with_categories(state, move |index| {
+        norgmill::categories::category_page(&name, &index.documents(&name), |path| {
+            format!("{}/{}", constants::CURRENT_WORKSPACE_PATH, path.display())
+        })
+    })
+    .await?;
+    Ok(generate_norg_html_page(
+        title,
+        content,
+        constants::CURRENT_WORKSPACE_PATH,
+        &Navigation::default(),
+        None,
+    ))
+}
+
+/// open tasks of the workspace grouped by their dates
+#[instrument(skip(state))]
+async fn workspace_agenda(
+    State(state): State<Arc<AppState>>,
+) -> Result<Html<String>, http::StatusCode> {
+    let today = chrono::Local::now().date_naive();
+    let content = tokio::task::spawn_blocking(move || {
+        let tasks = norgmill::tasks::workspace_tasks(&state.root_dir)?;
+        let agenda = norgmill::agenda::agenda(&tasks, today);
+        norgmill::agenda::agenda_page(&agenda, |task| task.link(constants::CURRENT_WORKSPACE_PATH))
+            .into_diagnostic()
+    })
+    .await;
+    let content = match content {
+        Ok(Ok(content)) => content,
+        Ok(Err(e)) => {
+            error!("Couldn't generate the agenda: {e}");
+            return Err(http::StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Err(e) => {
+            error!("Couldn't spawn blocking thread: {e}");
+            return Err(http::StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    Ok(generate_norg_html_page(
+        "Agenda".to_string(),
+        content,
+        constants::CURRENT_WORKSPACE_PATH,
+        &Navigation::default(),
+        None,
+    ))
+}
+
+/// dated tasks of the workspace as iCalendar, so that they can be subscribed from calendar clients
+#[instrument(skip(state))]
+async fn tasks_ics(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl axum::response::IntoResponse, http::StatusCode> {
+    let calendar = tokio::task::spawn_blocking(move || {
+        let tasks = norgmill::tasks::workspace_tasks(&state.root_dir)?;
+        norgmill::ical::tasks_calendar(&tasks, chrono::Utc::now()).into_diagnostic()
+    })
+    .await;
+    match calendar {
+        Ok(Ok(calendar)) => Ok((
+            [(
+                axum::http::header::CONTENT_TYPE,
+                "text/calendar; charset=utf-8",
+            )],
+            calendar,
+        )),
+        Ok(Err(e)) => {
+            error!("Couldn't generate the calendar: {e}");
+            Err(http::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+        Err(e) => {
+            error!("Couldn't spawn blocking thread: {e}");
+            Err(http::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// runs `f` with the journal of the workspace, `404` if the workspace doesn't have a journal
+async fn with_journal(
+    state: Arc<AppState>,
+    f: impl FnOnce(&AppState, &Journal) -> miette::Result<String> + Send + 'static,
+) -> Result<String, http::StatusCode> {
+    let result = tokio::task::spawn_blocking(move || {
+        Journal::find(&state.root_dir)?
+            .map(|journal| f(&state, &journal))
+            .transpose()
+    })
+    .await;
+    match result {
+        Ok(Ok(Some(content))) => Ok(content),
+        Ok(Ok(None)) => Err(http::StatusCode::NOT_FOUND),
+        Ok(Err(e)) => {
+            error!("Couldn't generate journal page: {e}");
+            Err(http::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+        Err(e) => {
+            error!("Couldn't spawn blocking thread: {e}");
+            Err(http::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// month grid of the journal, the month is given with the `month` query argument and defaults to the current one
+#[instrument(skip(state))]
+async fn journal_calendar(
+    State(state): State<Arc<AppState>>,
+    Query(qparams): Query<HashMap<String, String>>,
+) -> Result<Html<String>, http::StatusCode> {
+    let today = chrono::Local::now().date_naive();
+    let month = match qparams.get(constants::ARG_MONTH) {
+        Some(month) => Period::parse_month(month)
+            .and_then(Period::checked)
+            .ok_or(http::StatusCode::BAD_REQUEST)?,
+        None => Period::month(today),
+    };
+    let content = with_journal(state, move |_, journal| {
+        norgmill::journal::calendar(journal, month.first(), today).into_diagnostic()
+    })
+    .await?;
+    Ok(generate_norg_html_page(
+        format!("Journal: {}", month.title()),
+        content,
+        constants::CURRENT_WORKSPACE_PATH,
+        &Navigation::default(),
+        None,
+    ))
+}
+
+/// every journal entry of the period
+async fn journal_period(
+    state: Arc<AppState>,
+    period: Option<Period>,
+) -> Result<Html<String>, http::StatusCode> {
+    let period = period
+        .and_then(Period::checked)
+        .ok_or(http::StatusCode::BAD_REQUEST)?;
+    let content = with_journal(state, move |state, journal| {
+        norgmill::journal::period_view(&state.root_dir, journal, period, &state.variables)
+    })
+    .await?;
+    Ok(generate_norg_html_page(
+        period.title(),
+        content,
+        constants::CURRENT_WORKSPACE_PATH,
+        &Navigation::default(),
+        None,
+    ))
+}
+
+fn parse_day(date: &str) -> Option<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+#[instrument(skip(state))]
+async fn journal_day(
+    State(state): State<Arc<AppState>>,
+    Path(date): Path<String>,
+) -> Result<Html<String>, http::StatusCode> {
+    journal_period(state, parse_day(&date).map(Period::Day)).await
+}
+
+#[instrument(skip(state))]
+async fn journal_week(
+    State(state): State<Arc<AppState>>,
+    Path(date): Path<String>,
+) -> Result<Html<String>, http::StatusCode> {
+    journal_period(state, parse_day(&date).and_then(Period::week)).await
+}
+
+#[instrument(skip(state))]
+async fn journal_month(
+    State(state): State<Arc<AppState>>,
+    Path(month): Path<String>,
+) -> Result<Html<String>, http::StatusCode> {
+    journal_period(state, Period::parse_month(&month)).await
 }
 
 fn should_it_render_raw(qparams: HashMap<String, String>) -> bool {
@@ -136,11 +894,12 @@ async fn read_raw_file(file_path: &std::path::PathBuf) -> Result<Html<String>, h
 
 async fn read_and_render_file(
     file_path: &std::path::PathBuf,
-) -> Result<Html<String>, http::StatusCode> {
+    ctx: renderer::RenderContext,
+) -> Result<ParsedFile, http::StatusCode> {
     // if the extension is not .norg then set it and load the norg file
     debug!(path = %file_path.display(), "Constructed full path for index route");
-    match render_norg_file(file_path).await {
-        Ok((title, body)) => Ok(generate_norg_html_page(title, body)),
+    match render_norg_file(file_path, ctx).await {
+        Ok(rendered) => Ok(rendered),
         Err(e) => {
             error!("Failed to render norg file: {e}");
             Err(http::StatusCode::INTERNAL_SERVER_ERROR)
@@ -148,9 +907,33 @@ async fn read_and_render_file(
     }
 }
 
-fn generate_norg_html_page(title: String, content: String) -> Html<String> {
-    let escaped_title = norgmill::html::sanitize_html(&title);
-
+/// page around the rendered content, navigation links point to documents under `view`
+fn generate_norg_html_page(
+    title: String,
+    content: String,
+    view: &str,
+    navigation: &Navigation,
+    source_hash: Option<&str>,
+) -> Html<String> {
+    let page_link = |path: &Option<std::path::PathBuf>| {
+        path.as_ref()
+            .map(|path| format!("{view}/{}", path.display()))
+    };
+    let (up, next, prev) = (
+        page_link(&navigation.up),
+        page_link(&navigation.next),
+        page_link(&navigation.prev),
+    );
+    let breadcrumbs: Vec<(String, String)> = navigation
+        .breadcrumbs
+        .iter()
+        .map(|breadcrumb| {
+            (
+                format!("{view}/{}", breadcrumb.index.display()),
+                breadcrumb.title.clone(),
+            )
+        })
+        .collect();
     let styles_and_scripts = if cfg!(debug_assertions) {
         r#"<link rel="stylesheet" href="/static/style.css">
 <script src="/static/scripts.js"></script>"#
@@ -158,54 +941,25 @@ fn generate_norg_html_page(title: String, content: String) -> Html<String> {
         &format!(
             r#"<style>{}</style>
 <script>{}</script>"#,
-            include_str!("../assets/style.css"),
-            include_str!("../assets/scripts.js")
+            site::STYLE,
+            site::SCRIPTS
         )
     };
-
-    let page = format!(
-        r##"<!DOCTYPE html>
-<html lang="en">
-<head>
-    <title>{title}</title>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <link rel="preconnect" href="https://fonts.googleapis.com">
-    <link rel="preconnect" href="https://fonts.gstatic.com">
-    <link rel="icon" href="/favicon.svg" type="image/svg+xml">
-    <link rel="stylesheet" href="https://fonts.googleapis.com/css2?family=Source+Sans+Pro:wght@600;700&family=Source+Serif+Pro:wght@400;700&display=swap">
-    {styles_and_scripts}
-</head>
-<body>
-    <header class="site-header">
-        <div class="header-content">
-            <h1 class="site-title">{title}</h1>
-            <nav>
-                <a href="{home_path}">Home</a>
-                <a href="#">Up</a>
-                <a href="#">Next</a>
-                <a href="#">Prev</a>
-                <a href="?raw=1">Raw</a>
-                <button class="theme-toggle" aria-label="Toggle dark/light mode">
-                    <span class="icon">☀️</span>
-                </button>
-            </nav>
-        </div>
-    </header>
-    <main class="norg_content">
-        <article>
-            {content}
-        </article>
-    </main>
-</body>
-</html>"##,
-        title = escaped_title,
-        home_path = constants::CURRENT_WORKSPACE_PATH,
-        content = content,
-        styles_and_scripts = styles_and_scripts
-    );
-
-    Html(page)
+    Html(site::html_page(
+        &title,
+        &content,
+        &site::PageLinks {
+            home: constants::CURRENT_WORKSPACE_PATH,
+            styles_and_scripts,
+            favicon: "/favicon.svg",
+            raw: "?raw=1",
+            up: up.as_deref(),
+            next: next.as_deref(),
+            prev: prev.as_deref(),
+            breadcrumbs: &breadcrumbs,
+            source_hash,
+        },
+    ))
 }
 
 #[instrument]
@@ -219,12 +973,14 @@ async fn render_home_file(
         error!("Couldn't get user home directory, this is really odd");
         return Err(http::StatusCode::INTERNAL_SERVER_ERROR);
     };
-    let mut file_path = std::path::PathBuf::from(home_path);
-    file_path.push(&norg_file_path);
+    let home = std::path::PathBuf::from(home_path);
+    let mut file_path = home.join(&norg_file_path);
     if should_it_render_raw(qparams) {
         read_raw_file(&file_path).await
     } else {
-        state.get_or_insert_cached_file(&mut file_path).await
+        state
+            .get_or_insert_cached_file(&home, constants::HOME_PATH, &mut file_path)
+            .await
     }
 }
 
@@ -240,7 +996,13 @@ async fn render_root_system_file(
     if should_it_render_raw(qparams) {
         read_raw_file(&file_path).await
     } else {
-        state.get_or_insert_cached_file(&mut file_path).await
+        state
+            .get_or_insert_cached_file(
+                std::path::Path::new("/"),
+                constants::SYSTEM_PATH,
+                &mut file_path,
+            )
+            .await
     }
 }
 
@@ -257,7 +1019,10 @@ async fn render_current_workspace_file(
         read_raw_file(&file_path).await
     } else {
         update_extension(&mut file_path);
-        state.get_or_insert_cached_file(&mut file_path).await
+        let root_dir = state.root_dir.clone();
+        state
+            .get_or_insert_cached_file(&root_dir, constants::CURRENT_WORKSPACE_PATH, &mut file_path)
+            .await
     }
 }
 
@@ -266,6 +1031,63 @@ enum Functionality {
     Serve {
         #[arg(short, long)]
         root_dir: std::path::PathBuf,
+        /// workspace variable in `name=value` form, can be given multiple times
+        #[arg(long = "var", value_parser = renderer::parse_variable)]
+        variables: Vec<(String, String)>,
+        /// workspace directory to publish an atom feed for, can be given multiple times
+        #[arg(long = "feed")]
+        feeds: Vec<std::path::PathBuf>,
+        /// let pages change the TODO statuses of the workspace files, requests from pages of other sites
+        /// are refused, but they aren't authenticated so anyone who can reach the server can edit the files
+        #[arg(long)]
+        allow_edits: bool,
+    },
+    /// read a norg file in the terminal, output is paged with `$PAGER` when shown in a terminal
+    Read {
+        file: std::path::PathBuf,
+        /// don't use colors, this is the default when output is not a terminal or `NO_COLOR` is set
+        #[arg(long)]
+        plain: bool,
+        /// width at which paragraphs are wrapped, defaults to `$COLUMNS` or 80
+        #[arg(short, long)]
+        width: Option<usize>,
+        /// workspace variable in `name=value` form, can be given multiple times
+        #[arg(long = "var", value_parser = renderer::parse_variable)]
+        variables: Vec<(String, String)>,
+    },
+    /// convert every norg file of the workspace into gemtext, keeping the directory structure
+    Gemtext {
+        #[arg(short, long)]
+        root_dir: std::path::PathBuf,
+        #[arg(short, long)]
+        output_dir: std::path::PathBuf,
+        /// workspace variable in `name=value` form, can be given multiple times
+        #[arg(long = "var", value_parser = renderer::parse_variable)]
+        variables: Vec<(String, String)>,
+    },
+    /// build a static html site of the workspace which can be hosted without the server
+    Build {
+        #[arg(short, long)]
+        root_dir: std::path::PathBuf,
+        #[arg(short, long)]
+        output_dir: std::path::PathBuf,
+        /// workspace variable in `name=value` form, can be given multiple times
+        #[arg(long = "var", value_parser = renderer::parse_variable)]
+        variables: Vec<(String, String)>,
+        /// workspace directory to publish an atom feed for, can be given multiple times
+        #[arg(long = "feed")]
+        feeds: Vec<std::path::PathBuf>,
+        /// render every file, even if it didn't change since the previous build
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// export the dated tasks of the workspace as iCalendar
+    Ics {
+        #[arg(short, long)]
+        root_dir: std::path::PathBuf,
+        /// file to write the calendar to, printed to stdout if not given
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
     },
 }
 
@@ -278,7 +1100,12 @@ struct CmdlineArgs {
 }
 
 #[instrument]
-async fn serve(root_dir: std::path::PathBuf) -> miette::Result<()> {
+async fn serve(
+    root_dir: std::path::PathBuf,
+    variables: HashMap<String, String>,
+    feeds: Vec<std::path::PathBuf>,
+    allow_edits: bool,
+) -> miette::Result<()> {
     info!("starting server");
 
     let app = Router::new()
@@ -299,6 +1126,30 @@ async fn serve(root_dir: std::path::PathBuf) -> miette::Result<()> {
             routing::get(render_current_workspace_file),
         )
         .route(constants::paths::HOME_FILES, routing::get(render_home_file))
+        .route(constants::paths::FEEDS, routing::get(workspace_feed))
+        .route(
+            constants::CATEGORIES_PATH,
+            routing::get(categories_overview),
+        )
+        .route(constants::paths::CATEGORY, routing::get(category_page))
+        .route(constants::AGENDA_PATH, routing::get(workspace_agenda))
+        .route(constants::paths::TASKS_ICS, routing::get(tasks_ics))
+        .route(constants::JOURNAL_PATH, routing::get(journal_calendar))
+        .route(constants::paths::JOURNAL_DAY, routing::get(journal_day))
+        .route(constants::paths::JOURNAL_WEEK, routing::get(journal_week))
+        .route(constants::paths::JOURNAL_MONTH, routing::get(journal_month))
+        .route(
+            constants::paths::AST_API,
+            routing::get(ast_of_workspace_file),
+        )
+        .route(constants::paths::TODO_API, routing::post(set_todo_status))
+        .route(constants::paths::SCROLL_API, routing::post(editor_scroll))
+        .route(
+            constants::paths::PREVIEW_API,
+            routing::post(editor_preview)
+                .layer(axum::extract::DefaultBodyLimit::max(PREVIEW_SIZE_LIMIT)),
+        )
+        .route(constants::paths::EVENTS_API, routing::get(preview_events))
         .route(
             constants::paths::SYSTEM_FILES,
             routing::get(render_root_system_file),
@@ -308,7 +1159,7 @@ async fn serve(root_dir: std::path::PathBuf) -> miette::Result<()> {
             routing::get(|| async {
                 (
                     [(axum::http::header::CONTENT_TYPE, "image/svg+xml")],
-                    include_str!("../assets/neorg.svg"),
+                    site::FAVICON,
                 )
             }),
         )
@@ -321,6 +1172,12 @@ async fn serve(root_dir: std::path::PathBuf) -> miette::Result<()> {
         .with_state(std::sync::Arc::new(AppState {
             root_dir,
             parsed_files: dashmap::DashMap::new(),
+            variables,
+            feeds,
+            categories: Default::default(),
+            preview: tokio::sync::broadcast::channel(PREVIEW_EVENTS).0,
+            includes: Default::default(),
+            allow_edits,
         }));
 
     let listener = TcpListener::bind("0.0.0.0:8080")
@@ -341,6 +1198,57 @@ async fn serve(root_dir: std::path::PathBuf) -> miette::Result<()> {
     Ok(())
 }
 
+#[instrument(skip(variables))]
+fn read(
+    file: std::path::PathBuf,
+    plain: bool,
+    width: Option<usize>,
+    variables: HashMap<String, String>,
+) -> miette::Result<()> {
+    let content = std::fs::read_to_string(&file)
+        .into_diagnostic()
+        .wrap_err_with(|| miette!("reading file: {file:?}"))?;
+    let is_terminal = std::io::stdout().is_terminal();
+    let width = width
+        .or_else(|| std::env::var("COLUMNS").ok()?.parse().ok())
+        .unwrap_or(80);
+    let color = !plain && is_terminal && std::env::var_os("NO_COLOR").is_none();
+    debug!(width, color, "rendering for terminal");
+
+    let mut ctx = renderer::RenderContext::with_variables(variables).with_document_path(&file);
+    let mut backend = renderer::backend::TerminalBackend::new(width, color);
+    let rendered = renderer::render_norg(&content, &mut ctx, &mut backend)
+        .wrap_err("Couldn't render the file")?;
+
+    if is_terminal {
+        let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -R".to_string());
+        let mut pager_args = pager.split_whitespace();
+        if let Some(program) = pager_args.next() {
+            match std::process::Command::new(program)
+                .args(pager_args)
+                .stdin(std::process::Stdio::piped())
+                .spawn()
+            {
+                Ok(mut child) => {
+                    if let Some(mut stdin) = child.stdin.take() {
+                        // pager may exit before reading everything, that is not an error
+                        if let Err(e) = stdin.write_all(rendered.as_bytes()) {
+                            debug!(error = %e, "pager closed the input");
+                        }
+                    }
+                    child.wait().into_diagnostic().wrap_err("Pager failed")?;
+                    return Ok(());
+                }
+                Err(e) => warn!(%pager, error = %e, "couldn't start pager, printing to stdout"),
+            }
+        }
+    }
+    std::io::stdout()
+        .write_all(rendered.as_bytes())
+        .into_diagnostic()
+        .wrap_err("Failed to write to stdout")
+}
+
 #[tokio::main]
 #[instrument]
 async fn main() -> miette::Result<()> {
@@ -386,9 +1294,80 @@ async fn main() -> miette::Result<()> {
     debug!("log level set to {log_level}");
     info!(command = ?args.command, "Executing command");
     match args.command {
-        Functionality::Serve { root_dir } => serve(root_dir)
-            .await
-            .wrap_err("Couldn't run the http server")?,
+        Functionality::Serve {
+            root_dir,
+            variables,
+            feeds,
+            allow_edits,
+        } => serve(
+            root_dir,
+            variables.into_iter().collect(),
+            feeds,
+            allow_edits,
+        )
+        .await
+        .wrap_err("Couldn't run the http server")?,
+        Functionality::Read {
+            file,
+            plain,
+            width,
+            variables,
+        } => read(file, plain, width, variables.into_iter().collect())?,
+        Functionality::Gemtext {
+            root_dir,
+            output_dir,
+            variables,
+        } => {
+            let rendered = norgmill::export::export_workspace(
+                &root_dir,
+                &output_dir,
+                "gmi",
+                &variables.into_iter().collect(),
+                |path| Box::new(renderer::backend::GemtextBackend::for_document(path)),
+            )
+            .wrap_err("Couldn't export the workspace to gemtext")?;
+            println!("converted {rendered} files into {}", output_dir.display());
+        }
+        Functionality::Build {
+            root_dir,
+            output_dir,
+            variables,
+            feeds,
+            force,
+        } => {
+            let summary = site::build_site(
+                &root_dir,
+                &output_dir,
+                &variables.into_iter().collect(),
+                &feeds,
+                force,
+            )
+            .wrap_err("Couldn't build the static site")?;
+            println!(
+                "built {} pages into {} ({} copied, {} unchanged, {} removed)",
+                summary.rendered,
+                output_dir.display(),
+                summary.copied,
+                summary.unchanged,
+                summary.removed
+            );
+        }
+        Functionality::Ics { root_dir, output } => {
+            let tasks = norgmill::tasks::workspace_tasks(&root_dir)
+                .wrap_err("Couldn't collect the tasks")?;
+            let calendar = norgmill::ical::tasks_calendar(&tasks, chrono::Utc::now())
+                .into_diagnostic()
+                .wrap_err("Couldn't generate the calendar")?;
+            match output {
+                Some(output) => std::fs::write(&output, calendar)
+                    .into_diagnostic()
+                    .wrap_err_with(|| format!("Couldn't write {}", output.display()))?,
+                None => std::io::stdout()
+                    .write_all(calendar.as_bytes())
+                    .into_diagnostic()
+                    .wrap_err("Couldn't write the calendar")?,
+            }
+        }
     };
     Ok(())
 }
@@ -0,0 +1,185 @@
+//! up, next and previous documents of a page, computed from the workspace file tree
+//!
+//! documents of a directory follow each other by file name, documents below a `journal` directory
+//! follow each other by their date across months and years, written either as
+//! `journal/2024/01/31.norg` or `journal/2024-01-31.norg`
+//!
+//! breadcrumbs are the directories from the workspace root down to the document, named by the
+//! `@document.meta` title of their `index.norg`
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use chrono::NaiveDate;
+use tracing::{trace, warn};
+
+use crate::{
+    export::{is_norg_file, workspace_files},
+    renderer,
+};
+
+/// directory whose documents are ordered by date
+pub const JOURNAL_DIR: &str = "journal";
+
+/// directory above a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breadcrumb {
+    /// `index.norg` of the directory, it may not exist if the directory has a generated index
+    pub index: PathBuf,
+    /// meta title of the index, or name of the directory
+    pub title: String,
+}
+
+/// neighbours of a document, paths are relative to the workspace root
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Navigation {
+    /// `index.norg` of the parent directory, it may not exist if the directory has a generated index
+    pub up: Option<PathBuf>,
+    pub prev: Option<PathBuf>,
+    pub next: Option<PathBuf>,
+    /// directories from the workspace root down to the one containing the document
+    pub breadcrumbs: Vec<Breadcrumb>,
+}
+
+fn is_index(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == "index.norg")
+}
+
+fn is_hidden(name: &std::ffi::OsStr) -> bool {
+    name.to_string_lossy().starts_with('.')
+}
+
+/// date of a journal entry from its path relative to the journal directory
+pub fn journal_date(path: &Path) -> Option<NaiveDate> {
+    if !is_norg_file(path) {
+        return None;
+    }
+    let date = path.with_extension("");
+    let parts: Vec<_> = date
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect();
+    let date = match parts.as_slice() {
+        [year, month, day] => format!("{year}-{month}-{day}"),
+        [date] => date.to_string(),
+        _ => return None,
+    };
+    NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok()
+}
+
+/// journal directory containing the document, relative to the workspace root
+pub(crate) fn journal_root(document: &Path) -> Option<&Path> {
+    document.ancestors().skip(1).find(|directory| {
+        directory
+            .file_name()
+            .is_some_and(|name| name == JOURNAL_DIR)
+    })
+}
+
+/// dated entries of the journal by their date, paths are relative to the workspace root
+pub(crate) fn journal_entries(root_dir: &Path, journal: &Path) -> BTreeMap<NaiveDate, PathBuf> {
+    let files = match workspace_files(&root_dir.join(journal)) {
+        Ok(files) => files,
+        Err(e) => {
+            warn!(?journal, "Couldn't read journal directory: {e}");
+            return BTreeMap::new();
+        }
+    };
+    files
+        .into_iter()
+        .filter_map(|file| Some((journal_date(&file)?, journal.join(file))))
+        .collect()
+}
+
+/// norg documents of the directory other than the index, in file name order
+fn directory_documents(root_dir: &Path, directory: &Path) -> Vec<PathBuf> {
+    let entries = match std::fs::read_dir(root_dir.join(directory)) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!(?directory, "Couldn't read directory: {e}");
+            return Vec::new();
+        }
+    };
+    let mut documents: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| !is_hidden(&entry.file_name()))
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_file()))
+        .map(|entry| directory.join(entry.file_name()))
+        .filter(|path| is_norg_file(path) && !is_index(path))
+        .collect();
+    documents.sort();
+    documents
+}
+
+/// breadcrumb of a directory, named after the title of its index if it has one
+fn breadcrumb(root_dir: &Path, directory: &Path) -> Breadcrumb {
+    let index = directory.join("index.norg");
+    let title = std::fs::read_to_string(root_dir.join(&index))
+        .ok()
+        .and_then(|input| match renderer::document_meta(&input) {
+            Ok(meta) => meta.title,
+            Err(e) => {
+                warn!(?index, "Couldn't read document meta: {e}");
+                None
+            }
+        })
+        .or_else(|| {
+            // the workspace root is named after the root directory itself
+            directory
+                .file_name()
+                .or_else(|| root_dir.file_name())
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "/".to_string());
+    Breadcrumb { index, title }
+}
+
+/// up, next and previous documents of `document`, which is relative to `root_dir`
+///
+/// indexes are the top of their directory, so they only have an up link
+pub fn navigation(root_dir: &Path, document: &Path) -> Navigation {
+    let directory = document.parent().unwrap_or(Path::new(""));
+    let up_directory = if is_index(document) {
+        directory.parent()
+    } else {
+        Some(directory)
+    };
+    let mut breadcrumbs: Vec<Breadcrumb> = up_directory
+        .into_iter()
+        .flat_map(Path::ancestors)
+        .map(|directory| breadcrumb(root_dir, directory))
+        .collect();
+    breadcrumbs.reverse();
+    let mut navigation = Navigation {
+        up: up_directory.map(|directory| directory.join("index.norg")),
+        breadcrumbs,
+        ..Default::default()
+    };
+    if is_index(document) {
+        return navigation;
+    }
+
+    let siblings = match journal_root(document) {
+        Some(journal)
+            if document
+                .strip_prefix(journal)
+                .ok()
+                .and_then(journal_date)
+                .is_some() =>
+        {
+            journal_entries(root_dir, journal).into_values().collect()
+        }
+        _ => directory_documents(root_dir, directory),
+    };
+    if let Some(position) = siblings.iter().position(|sibling| sibling == document) {
+        navigation.prev = position
+            .checked_sub(1)
+            .and_then(|prev| siblings.get(prev))
+            .cloned();
+        navigation.next = siblings.get(position + 1).cloned();
+    }
+    trace!(?document, ?navigation, "navigation of the document");
+    navigation
+}
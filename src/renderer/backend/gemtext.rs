@@ -0,0 +1,361 @@
+//! gemtext output for gemini, it is line based so inline links are moved to `=>` lines after the block
+use std::fmt::Write;
+use tracing::{debug, error, trace, warn};
+
+use super::{Backend, DocumentMeta, Footnote, IncludeSource, ListKind};
+use crate::renderer::link;
+
+#[derive(Debug)]
+pub struct GemtextBackend {
+    /// prefix for `$/` links, relative path from the document to the root of the workspace
+    workspace_root: String,
+    /// links found in the current block, written after the block is finished
+    links: Vec<(String, String)>,
+}
+
+impl Default for GemtextBackend {
+    fn default() -> Self {
+        Self {
+            workspace_root: "/".to_string(),
+            links: Vec::new(),
+        }
+    }
+}
+
+impl GemtextBackend {
+    /// backend for a document in workspace, path is relative to workspace root
+    /// and is used to make workspace links relative
+    pub fn for_document(path: &std::path::Path) -> Self {
+        let depth = path.components().count().saturating_sub(1);
+        Self {
+            workspace_root: "../".repeat(depth),
+            ..Default::default()
+        }
+    }
+
+    /// writes `=>` lines for links found in the block
+    fn flush_links(&mut self, output: &mut String) -> std::fmt::Result {
+        for (url, label) in self.links.drain(..) {
+            if label.is_empty() || label == url {
+                writeln!(output, "=> {url}")?;
+            } else {
+                writeln!(output, "=> {url} {label}")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// converts norg file link to the exported `.gmi` file
+    fn gemtext_path(&self, file: link::NorgFile) -> String {
+        let path = file.path();
+        let path = path.strip_suffix(".norg").unwrap_or(path);
+        match file {
+            link::NorgFile::CurrentWorkspace(_) => format!("{}{path}.gmi", self.workspace_root),
+            link::NorgFile::System(_) => format!("/{path}.gmi"),
+            link::NorgFile::Home(_) => format!("~/{path}.gmi"),
+            link::NorgFile::Workspace(_) | link::NorgFile::Relative(_) => format!("{path}.gmi"),
+        }
+    }
+}
+
+/// gemtext has no inline markup, so blocks are kept in single line
+fn single_line(content: &str) -> String {
+    content.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// lines starting with ``` would end the preformatted block early, so they get a zero width space in front
+fn preformatted(text: &str) -> String {
+    text.trim_matches('\n')
+        .split('\n')
+        .map(|line| {
+            if line.starts_with("```") {
+                format!("\u{200B}{line}")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl Backend for GemtextBackend {
+    fn document(&mut self, content: &str, output: &mut String) -> std::fmt::Result {
+        writeln!(output, "{}", content.trim())?;
+        self.flush_links(output)
+    }
+
+    fn content_block(&mut self, content: &str, output: &mut String) -> std::fmt::Result {
+        output.push_str(content);
+        Ok(())
+    }
+
+    fn footnotes(&mut self, footnotes: &[Footnote], output: &mut String) -> std::fmt::Result {
+        if footnotes.is_empty() {
+            return Ok(());
+        }
+        output.push_str("\n## Footnotes\n\n");
+        for Footnote { title, content } in footnotes {
+            writeln!(output, "[{}] {}", title.trim(), single_line(content))?;
+            self.flush_links(output)?;
+        }
+        Ok(())
+    }
+
+    fn text(&mut self, text: &str, output: &mut String) -> std::fmt::Result {
+        output.push_str(text);
+        Ok(())
+    }
+
+    fn paragraph(&mut self, content: &str, output: &mut String) -> std::fmt::Result {
+        writeln!(output, "{}", single_line(content))?;
+        self.flush_links(output)?;
+        output.push('\n');
+        Ok(())
+    }
+
+    fn attached_modifier(
+        &mut self,
+        modifier: char,
+        content: &str,
+        extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        if let Some(extension) = extension {
+            debug!(
+                extension,
+                "attached modifier extensions are ignored in gemtext"
+            );
+        }
+        match modifier {
+            '%' => trace!("skipping comment"),
+            '*' | '/' | '-' | '_' | '!' | '`' | '^' | ',' => output.push_str(content),
+            _ => {
+                warn!(modifier = %modifier, "unknown modifier");
+                output.push_str(content);
+            }
+        }
+        Ok(())
+    }
+
+    fn inline_math(
+        &mut self,
+        math: &str,
+        _extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        output.push_str(math.trim());
+        Ok(())
+    }
+
+    fn variable(
+        &mut self,
+        name: &str,
+        value: Option<&str>,
+        _extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        match value {
+            Some(value) => output.push_str(value),
+            None => write!(output, "&{name}&")?,
+        }
+        Ok(())
+    }
+
+    fn inline_code(&mut self, code: &str, output: &mut String) -> std::fmt::Result {
+        output.push_str(code);
+        Ok(())
+    }
+
+    fn link(
+        &mut self,
+        file_path: Option<&str>,
+        targets: &[norg::LinkTarget],
+        description: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let file = file_path.and_then(link::parse_norg_file);
+        let url = match (file, targets.first()) {
+            (None, Some(norg::LinkTarget::Footnote(title))) => {
+                return write!(output, "[{}]", link::raw_title(title).trim());
+            }
+            // gemini clients don't support fragments, so links to other file point to the file itself
+            (Some(file), _) => Some(self.gemtext_path(file)),
+            (None, Some(norg::LinkTarget::Url(url))) => Some(url.to_string()),
+            (None, Some(norg::LinkTarget::Path(path))) => Some(path.trim().to_string()),
+            (None, Some(norg::LinkTarget::Heading { title, .. }))
+            | (None, Some(norg::LinkTarget::Definition(title))) => {
+                trace!("links inside the document can't be followed in gemtext");
+                let title = link::raw_title(title);
+                output.push_str(description.unwrap_or(title.trim()));
+                return Ok(());
+            }
+            (None, Some(target)) => {
+                error!(?target, "link target is not yet supported");
+                None
+            }
+            (None, None) => None,
+        };
+        let Some(url) = url else {
+            output.push_str(description.unwrap_or_default());
+            return Ok(());
+        };
+        let label = single_line(description.unwrap_or(&url));
+        output.push_str(&label);
+        self.links.push((url, label));
+        Ok(())
+    }
+
+    fn heading(
+        &mut self,
+        level: u16,
+        title: &str,
+        content: &str,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        // gemtext has only three levels of headings
+        let level = level.clamp(1, 3) as usize;
+        writeln!(output, "{} {}", "#".repeat(level), single_line(title))?;
+        self.flush_links(output)?;
+        write!(output, "\n{content}")
+    }
+
+    fn list(&mut self, _kind: ListKind, items: &str, output: &mut String) -> std::fmt::Result {
+        writeln!(output, "{items}")
+    }
+
+    fn list_item(
+        &mut self,
+        _kind: ListKind,
+        _level: u16,
+        extensions: &[norg::DetachedModifierExtension],
+        text: &str,
+        content: &str,
+        _todo: Option<usize>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        output.push_str("* ");
+        for extension in extensions {
+            match extension {
+                norg::DetachedModifierExtension::Todo(norg::TodoStatus::Done) => {
+                    output.push_str("[x] ")
+                }
+                norg::DetachedModifierExtension::Todo(_) => output.push_str("[ ] "),
+                other => warn!(extension = ?other, "list item extension is not yet supported"),
+            }
+        }
+        writeln!(output, "{}", single_line(text))?;
+        self.flush_links(output)?;
+        // gemtext lists can't be nested, so nested content follows the item
+        let content = content.trim();
+        if !content.is_empty() {
+            writeln!(output, "{content}")?;
+        }
+        Ok(())
+    }
+
+    fn quote(&mut self, _level: u16, content: &str, output: &mut String) -> std::fmt::Result {
+        let (quoted, links): (Vec<_>, Vec<_>) = content
+            .trim()
+            .lines()
+            .filter(|line| !line.is_empty())
+            .partition(|line| !line.starts_with("=>"));
+        for line in quoted {
+            writeln!(output, "> {}", line.trim_start_matches("> "))?;
+        }
+        for line in links {
+            writeln!(output, "{line}")?;
+        }
+        output.push('\n');
+        Ok(())
+    }
+
+    fn definition_list(&mut self, items: &str, output: &mut String) -> std::fmt::Result {
+        output.push_str(items);
+        Ok(())
+    }
+
+    fn definition(&mut self, title: &str, content: &str, output: &mut String) -> std::fmt::Result {
+        writeln!(output, "{}: {}", single_line(title), content.trim())?;
+        self.flush_links(output)?;
+        output.push('\n');
+        Ok(())
+    }
+
+    fn table(&mut self, rows: &[Vec<String>], output: &mut String) -> std::fmt::Result {
+        // tables are aligned in preformatted block, same as how they are written in plain text
+        let rows: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| row.iter().map(|cell| single_line(cell)).collect())
+            .collect();
+        let columns = rows.first().map(Vec::len).unwrap_or_default();
+        let widths: Vec<usize> = (0..columns)
+            .map(|column| {
+                rows.iter()
+                    .map(|row| row[column].chars().count())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+        output.push_str("```\n");
+        for row in &rows {
+            let cells: Vec<_> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .collect();
+            writeln!(output, "{}", cells.join(" | ").trim_end())?;
+        }
+        output.push_str("```\n");
+        self.flush_links(output)?;
+        output.push('\n');
+        Ok(())
+    }
+
+    fn code_block(
+        &mut self,
+        language: Option<&str>,
+        code: &str,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        write!(
+            output,
+            "```{}\n{}\n```\n\n",
+            language.unwrap_or_default(),
+            preformatted(code)
+        )
+    }
+
+    fn math_block(&mut self, math: &str, output: &mut String) -> std::fmt::Result {
+        write!(output, "```math\n{}\n```\n\n", preformatted(math))
+    }
+
+    fn document_meta(&mut self, meta: &DocumentMeta, output: &mut String) -> std::fmt::Result {
+        if let Some(title) = &meta.title {
+            writeln!(output, "# {}\n", single_line(title))?;
+        }
+        if let Some(description) = &meta.description {
+            writeln!(output, "{}\n", single_line(description))?;
+        }
+        Ok(())
+    }
+
+    fn include(
+        &mut self,
+        source: &IncludeSource,
+        content: &str,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        trace!(label = source.label, "adding included content");
+        output.push_str(content);
+        Ok(())
+    }
+
+    fn date(&mut self, _datetime: &str, formatted: &str, output: &mut String) -> std::fmt::Result {
+        output.push_str(formatted);
+        Ok(())
+    }
+
+    fn error(&mut self, message: &str, output: &mut String) -> std::fmt::Result {
+        write!(output, "Error: {}\n\n", single_line(message))
+    }
+}
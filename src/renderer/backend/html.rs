@@ -0,0 +1,485 @@
+//! html output, this is what the server shows in the browser
+use std::fmt::Write;
+use tracing::{debug, error, trace, warn};
+
+use super::{Backend, DocumentMeta, Footnote, IncludeSource, ListKind};
+use crate::{
+    constants,
+    html::{percent_encode, sanitize_html},
+    renderer::link,
+};
+
+#[derive(Debug, Default)]
+pub struct HtmlBackend;
+
+/// whether `key` can be used as the name of a `data-` attribute without changing the element
+fn is_attribute_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// whether `color` is a single hex or named colour, so that it can't add other css declarations
+fn is_color(color: &str) -> bool {
+    match color.strip_prefix('#') {
+        Some(hex) => {
+            matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit())
+        }
+        None => !color.is_empty() && color.chars().all(|c| c.is_ascii_alphabetic()),
+    }
+}
+
+/// converts attached modifier extension (content inside `(...)` following the modifier) into html attributes
+///
+/// attributes are delimited by `|`, `lang` and `color` are inbuilt hierarchies,
+/// other `key:value` pairs are added as data attributes and plain attributes as classes
+fn element_attributes(modifier: char, base_class: Option<&str>, extension: Option<&str>) -> String {
+    let mut classes: Vec<String> = base_class.into_iter().map(String::from).collect();
+    let mut attributes = String::new();
+    for attribute in extension
+        .unwrap_or_default()
+        .split('|')
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+    {
+        match attribute.split_once(':') {
+            Some(("lang", lang)) if modifier == '`' => {
+                classes.push(format!("language-{}", sanitize_html(lang)));
+            }
+            Some(("lang", lang)) => {
+                write!(attributes, " lang=\"{}\"", sanitize_html(lang))
+                    .expect("string formatting is infallible");
+            }
+            Some(("color", color)) if is_color(color) => {
+                write!(attributes, " style=\"color:{color}\"")
+                    .expect("string formatting is infallible");
+            }
+            Some(("color", color)) => warn!(color, "ignoring invalid color extension"),
+            Some((key, value)) if is_attribute_key(key) => {
+                write!(attributes, " data-{}=\"{}\"", key, sanitize_html(value))
+                    .expect("string formatting is infallible");
+            }
+            Some((key, _)) => warn!(key, "ignoring extension with invalid attribute name"),
+            None => classes.push(sanitize_html(attribute).into_owned()),
+        }
+    }
+    debug!(?classes, attributes, "parsed attached modifier extension");
+    if !classes.is_empty() {
+        write!(attributes, " class=\"{}\"", classes.join(" "))
+            .expect("string formatting is infallible");
+    }
+    attributes
+}
+
+/// value of `data-status` attribute for todo items
+fn todo_status(status: &norg::TodoStatus) -> &'static str {
+    match status {
+        norg::TodoStatus::Undone => "undone",
+        norg::TodoStatus::Done => "done",
+        norg::TodoStatus::NeedsClarification => "needs-clarification",
+        norg::TodoStatus::Paused => "paused",
+        norg::TodoStatus::Urgent => "urgent",
+        norg::TodoStatus::Recurring(_) => "recurring",
+        norg::TodoStatus::Pending => "pending",
+        norg::TodoStatus::Canceled => "canceled",
+    }
+}
+
+/// url of the linked norg file or raw file along with the fragment of the target
+fn href(file_path: Option<&str>, targets: &[norg::LinkTarget]) -> String {
+    let norg_file_url = file_path
+        .and_then(link::parse_norg_file)
+        .map(|file| file.url());
+    debug!(?norg_file_url, "norg file found?");
+
+    let fragment_or_external_link = targets.first().and_then(|target| match target {
+        norg::LinkTarget::Heading { level, title } => Some(format!(
+            "#{}",
+            link::fragment_id(&link::raw_title(title), &format!("h{level}"))
+        )),
+        norg::LinkTarget::LineNumber(_) => {
+            error!("<!-- Unsupported feature: line number on anchor -->");
+            Some("#".to_string())
+        }
+        norg::LinkTarget::Footnote(title) => Some(format!(
+            "#{}",
+            link::fragment_id(&link::raw_title(title), "f")
+        )),
+        norg::LinkTarget::Definition(title) => Some(format!(
+            "#{}",
+            link::fragment_id(&link::raw_title(title), "d")
+        )),
+        norg::LinkTarget::Path(raw_path) => {
+            let mut raw_path_iter = raw_path.trim_start().chars();
+            match raw_path_iter.next() {
+                None => {
+                    warn!("empty raw file path found as a link");
+                    None
+                }
+                Some('/') => Some(format!(
+                    "{}/{}?raw=1",
+                    constants::SYSTEM_PATH,
+                    raw_path_iter.as_str()
+                )),
+                Some('~') => Some(format!(
+                    "{}{}?raw=1",
+                    constants::HOME_PATH,
+                    raw_path_iter.as_str()
+                )),
+                Some(_) => Some(raw_path.trim_start().to_string()),
+            }
+        }
+        norg::LinkTarget::Url(text) => Some(text.to_string()),
+        unsupported => {
+            error!(target = ?unsupported, "link target is not yet supported");
+            None
+        }
+    });
+    debug!(?fragment_or_external_link, "href found?");
+
+    match (norg_file_url, fragment_or_external_link) {
+        (Some(url), Some(fragment)) => url + &fragment,
+        (Some(url), None) => url,
+        (None, link) => link.unwrap_or("#".to_string()),
+    }
+}
+
+/// link to the included file, so that reader can jump to the source of included content
+fn include_href(source: &IncludeSource) -> String {
+    let mut href = match source.workspace_path {
+        Some(relative) => format!(
+            "{}/{}",
+            constants::CURRENT_WORKSPACE_PATH,
+            relative.display()
+        ),
+        None => format!("{}{}", constants::SYSTEM_PATH, source.path.display()),
+    };
+    if let Some((level, title)) = source.heading {
+        write!(href, "#{}", link::fragment_id(title, &format!("h{level}")))
+            .expect("string formatting is infallible");
+    }
+    href
+}
+
+impl Backend for HtmlBackend {
+    fn document(&mut self, content: &str, output: &mut String) -> std::fmt::Result {
+        write!(output, "<div class=norg_content>{content}</div>")
+    }
+
+    fn content_block(&mut self, content: &str, output: &mut String) -> std::fmt::Result {
+        write!(output, "<div class=\"content_block\">{content}</div>")
+    }
+
+    fn footnotes(&mut self, footnotes: &[Footnote], output: &mut String) -> std::fmt::Result {
+        if footnotes.is_empty() {
+            return Ok(());
+        }
+        output.push_str("<footer><ol>");
+        for Footnote { title, content } in footnotes {
+            write!(output, "<li id=\"{title}_footnote\">{content}")?;
+            // TODO: create a backref for each footnote
+            let backref_tag = format!("#{title}_footnote_backref");
+            write!(
+                output,
+                "<a href=\"{backref_tag}\" aria-label=\"{backref_tag}\">↩</a></li>"
+            )?;
+        }
+        output.push_str("</ol></footer>");
+        Ok(())
+    }
+
+    fn text(&mut self, text: &str, output: &mut String) -> std::fmt::Result {
+        output.push_str(&sanitize_html(text));
+        Ok(())
+    }
+
+    fn paragraph(&mut self, content: &str, output: &mut String) -> std::fmt::Result {
+        write!(output, "<p>{content}</p>")
+    }
+
+    fn attached_modifier(
+        &mut self,
+        modifier: char,
+        content: &str,
+        extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let attrs = element_attributes(modifier, None, extension);
+        match modifier {
+            '*' => {
+                trace!("rendering bold text");
+                write!(output, "<strong{attrs}>{content}</strong>")
+            }
+            '/' => {
+                trace!("rendering italic text");
+                write!(output, "<em{attrs}>{content}</em>")
+            }
+            '-' => {
+                trace!("rendering striked text");
+                write!(output, "<s{attrs}>{content}</s>")
+            }
+            '_' => {
+                trace!("rendering underlined text");
+                write!(output, "<u{attrs}>{content}</u>")
+            }
+            '!' => {
+                trace!("rendering spoiler text");
+                write!(
+                    output,
+                    "<span{}>{content}</span>",
+                    element_attributes(modifier, Some("spoiler"), extension)
+                )
+            }
+            '`' => {
+                trace!("rendering inline code");
+                write!(output, "<code{attrs}>{content}</code>")
+            }
+            '^' => {
+                trace!("rendering superscript");
+                write!(output, "<sup{attrs}>{content}</sup>")
+            }
+            ',' => {
+                trace!("rendering subscript");
+                write!(output, "<sub{attrs}>{content}</sub>")
+            }
+            '%' => {
+                trace!("rendering commented text");
+                write!(output, "<!-- {content} -->")
+            }
+            _ => {
+                warn!(modifier = %modifier, "unknown modifier");
+                Ok(())
+            }
+        }
+    }
+
+    fn inline_math(
+        &mut self,
+        math: &str,
+        extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        trace!("rendering math equation");
+        write!(
+            output,
+            "<span{}>{}</span>",
+            element_attributes('$', Some("math"), extension),
+            sanitize_html(math)
+        )
+    }
+
+    fn variable(
+        &mut self,
+        name: &str,
+        value: Option<&str>,
+        extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        match value {
+            Some(value) => write!(
+                output,
+                "<span{} title=\"{}\">{}</span>",
+                element_attributes('&', Some("variable"), extension),
+                sanitize_html(name),
+                sanitize_html(value)
+            ),
+            None => write!(
+                output,
+                "<span{} title=\"undefined variable\">&amp;{}&amp;</span>",
+                element_attributes('&', Some("variable undefined"), extension),
+                sanitize_html(name)
+            ),
+        }
+    }
+
+    fn inline_code(&mut self, code: &str, output: &mut String) -> std::fmt::Result {
+        write!(output, "<code>{}</code>", sanitize_html(code))
+    }
+
+    fn link(
+        &mut self,
+        file_path: Option<&str>,
+        targets: &[norg::LinkTarget],
+        description: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let href = href(file_path, targets);
+        // description is rendered html, but the href shown in its place is not
+        let title = match description {
+            Some(description) => description.into(),
+            None => sanitize_html(&href),
+        };
+        write!(
+            output,
+            "<span><a href=\"{}\">{title}</a></span>",
+            sanitize_html(&href)
+        )
+    }
+
+    fn heading(
+        &mut self,
+        level: u16,
+        title: &str,
+        content: &str,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let heading_class = format!("heading_{level}");
+        write!(output, "<div class=\"heading_block\">")?;
+        match level {
+            1..5 => write!(
+                output,
+                "<h{level} class=\"{heading_class}\">{title}</h{level}>"
+            )?,
+            _ => write!(output, "<h6 class=\"{heading_class}\">{title}</h6>")?,
+        };
+        write!(output, "{content}</div>")
+    }
+
+    fn list(&mut self, kind: ListKind, items: &str, output: &mut String) -> std::fmt::Result {
+        let tag = match kind {
+            ListKind::Ordered => "ol",
+            ListKind::Unordered => "ul",
+        };
+        write!(output, "<{tag}>{items}</{tag}>")
+    }
+
+    fn list_item(
+        &mut self,
+        kind: ListKind,
+        level: u16,
+        extensions: &[norg::DetachedModifierExtension],
+        text: &str,
+        content: &str,
+        todo: Option<usize>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        write!(output, "<li class={kind}_l{level}")?;
+        if let Some(todo) = todo {
+            write!(output, " data-todo=\"{todo}\"")?;
+        }
+        let mut add_todo = false;
+        for extension in extensions {
+            match extension {
+                norg::DetachedModifierExtension::Todo(status) => {
+                    let status = todo_status(status);
+                    debug!(status, "Rendering TodoStatus");
+                    write!(output, " data-status=\"{status}\"")?;
+                    add_todo = true;
+                }
+                other => warn!(extension = ?other, "list item extension is not yet supported"),
+            }
+        }
+        output.push('>');
+        if add_todo {
+            output.push_str("<span class=\"status-indicator\"></span>");
+        }
+        write!(output, "{text}{content}</li>")
+    }
+
+    fn quote(&mut self, _level: u16, content: &str, output: &mut String) -> std::fmt::Result {
+        write!(output, "<blockquote>{content}</blockquote>")
+    }
+
+    fn definition_list(&mut self, items: &str, output: &mut String) -> std::fmt::Result {
+        write!(output, "<dl>{items}</dl>")
+    }
+
+    fn definition(&mut self, title: &str, content: &str, output: &mut String) -> std::fmt::Result {
+        write!(output, "<dt>{title}</dt><dd>{content}</dd>")
+    }
+
+    fn table(&mut self, rows: &[Vec<String>], output: &mut String) -> std::fmt::Result {
+        output.push_str("<table>");
+        for row in rows {
+            output.push_str("<tr>");
+            for cell in row {
+                write!(output, "<td>{cell}</td>")?;
+            }
+            output.push_str("</tr>");
+        }
+        output.push_str("</table>");
+        Ok(())
+    }
+
+    fn code_block(
+        &mut self,
+        language: Option<&str>,
+        code: &str,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let language = language.unwrap_or("text");
+        debug!("found language: {language}");
+        write!(output, "<pre class={language}>{code}</pre>")
+    }
+
+    fn math_block(&mut self, math: &str, output: &mut String) -> std::fmt::Result {
+        write!(
+            output,
+            "<div class=\"math\">{}</div>",
+            sanitize_html(math.trim())
+        )
+    }
+
+    fn document_meta(&mut self, meta: &DocumentMeta, output: &mut String) -> std::fmt::Result {
+        write!(output, "<div class=\"metadata\">")?;
+        for (key, value) in meta.entries() {
+            let value = sanitize_html(&value);
+            match key {
+                "title" => write!(output, "<div>Title: {value}</div>")?,
+                "description" => write!(output, "<div>Description: {value}</div>")?,
+                "authors" => write!(output, "<div>👤 {value}</div>")?,
+                "categories" => {
+                    let links: Vec<_> = meta
+                        .categories
+                        .iter()
+                        .map(|category| {
+                            format!(
+                                "<a class=\"category\" href=\"{}/{}\">{}</a>",
+                                constants::CATEGORIES_PATH,
+                                percent_encode(category),
+                                sanitize_html(category)
+                            )
+                        })
+                        .collect();
+                    write!(output, "<div>📂 {}</div>", links.join(", "))?
+                }
+                "created" => write!(output, "<div>🕒 {value}</div>")?,
+                "updated" => write!(output, "<div>Last Updated {value}</div>")?,
+                "version" => write!(output, "<div>📌 {value}</div>")?,
+                _ => warn!(key, "Unknown key while parsing document meta, ignoring"),
+            }
+        }
+        write!(output, "</div>")
+    }
+
+    fn include(
+        &mut self,
+        source: &IncludeSource,
+        content: &str,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        write!(
+            output,
+            "<div class=\"include\"><a class=\"include-source\" href=\"{}\">{}</a>{content}</div>",
+            sanitize_html(&include_href(source)),
+            sanitize_html(source.label)
+        )
+    }
+
+    fn date(&mut self, datetime: &str, formatted: &str, output: &mut String) -> std::fmt::Result {
+        write!(
+            output,
+            "<time datetime=\"{datetime}\">{}</time>",
+            sanitize_html(formatted)
+        )
+    }
+
+    fn error(&mut self, message: &str, output: &mut String) -> std::fmt::Result {
+        write!(
+            output,
+            "<div class=\"macro-error\">{}</div>",
+            sanitize_html(message)
+        )
+    }
+}
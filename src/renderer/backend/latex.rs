@@ -0,0 +1,413 @@
+//! latex output, produces a complete `.tex` document which can be compiled with pdflatex
+use std::fmt::Write;
+use tracing::{debug, error, trace, warn};
+
+use super::{Backend, DocumentMeta, Footnote, IncludeSource, ListKind};
+use crate::renderer::link;
+
+#[derive(Debug, Default)]
+pub struct LatexBackend;
+
+const PREAMBLE: &str = r"\documentclass{article}
+\usepackage[utf8]{inputenc}
+\usepackage[T1]{fontenc}
+\usepackage{amsmath}
+\usepackage{amssymb}
+\usepackage[normalem]{ulem}
+\usepackage{listings}
+\usepackage{hyperref}
+\lstset{basicstyle=\ttfamily\small,breaklines=true}
+% footnotes are filled after the document is rendered, this hides the ones without definition
+\newcommand{\NorgFootnote}[1]{}
+";
+
+/// languages which are known to listings package, others are rendered with verbatim
+fn listings_language(language: &str) -> Option<&'static str> {
+    Some(match language.to_lowercase().as_str() {
+        "c" => "C",
+        "cpp" | "c++" => "C++",
+        "java" => "Java",
+        "python" | "py" => "Python",
+        "bash" | "sh" | "shell" => "bash",
+        "html" => "HTML",
+        "xml" => "XML",
+        "sql" => "SQL",
+        "lua" => "Lua",
+        "haskell" => "Haskell",
+        "ruby" => "Ruby",
+        "perl" => "Perl",
+        "tex" | "latex" => "TeX",
+        "matlab" => "Matlab",
+        _ => return None,
+    })
+}
+
+/// escapes characters which have special meaning in latex
+fn escape(text: &str, output: &mut String) {
+    for c in text.chars() {
+        match c {
+            '\\' => output.push_str(r"\textbackslash{}"),
+            '~' => output.push_str(r"\textasciitilde{}"),
+            '^' => output.push_str(r"\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                output.push('\\');
+                output.push(c);
+            }
+            c => output.push(c),
+        }
+    }
+}
+
+/// escapes characters which are not allowed in the url of `\href`
+fn escape_url(url: &str) -> String {
+    url.replace('\\', "/")
+        .replace('%', r"\%")
+        .replace('#', r"\#")
+        .replace(['{', '}'], "")
+}
+
+/// label used for `\label` and `\hyperref`, only alphanumeric characters are kept
+fn label(title: &str) -> String {
+    title
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect::<String>()
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn footnote_placeholder(title: &str) -> String {
+    format!(r"\NorgFootnote{{{}}}", label(title))
+}
+
+impl Backend for LatexBackend {
+    fn document(&mut self, content: &str, output: &mut String) -> std::fmt::Result {
+        write!(
+            output,
+            "{PREAMBLE}\n\\begin{{document}}\n\n{}\n\n\\end{{document}}\n",
+            content.trim()
+        )
+    }
+
+    fn content_block(&mut self, content: &str, output: &mut String) -> std::fmt::Result {
+        output.push_str(content);
+        Ok(())
+    }
+
+    fn footnotes(&mut self, footnotes: &[Footnote], output: &mut String) -> std::fmt::Result {
+        // footnotes are written where they are referenced, so placeholders are replaced with the content
+        for Footnote { title, content } in footnotes {
+            let placeholder = footnote_placeholder(title);
+            if !output.contains(&placeholder) {
+                warn!(%title, "footnote is not referenced in the document, skipping");
+            }
+            *output = output.replace(&placeholder, &format!(r"\footnote{{{}}}", content.trim()));
+        }
+        Ok(())
+    }
+
+    fn text(&mut self, text: &str, output: &mut String) -> std::fmt::Result {
+        escape(text, output);
+        Ok(())
+    }
+
+    fn paragraph(&mut self, content: &str, output: &mut String) -> std::fmt::Result {
+        write!(output, "{}\n\n", content.trim())
+    }
+
+    fn attached_modifier(
+        &mut self,
+        modifier: char,
+        content: &str,
+        extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        if let Some(extension) = extension {
+            debug!(
+                extension,
+                "attached modifier extensions are ignored in latex"
+            );
+        }
+        match modifier {
+            '*' => write!(output, r"\textbf{{{content}}}"),
+            '/' => write!(output, r"\emph{{{content}}}"),
+            '-' => write!(output, r"\sout{{{content}}}"),
+            '_' => write!(output, r"\uline{{{content}}}"),
+            // there are no spoilers in print
+            '!' => write!(output, "{content}"),
+            '`' => write!(output, r"\texttt{{{content}}}"),
+            '^' => write!(output, r"\textsuperscript{{{content}}}"),
+            ',' => write!(output, r"\textsubscript{{{content}}}"),
+            '%' => {
+                trace!("skipping comment");
+                Ok(())
+            }
+            _ => {
+                warn!(modifier = %modifier, "unknown modifier");
+                write!(output, "{content}")
+            }
+        }
+    }
+
+    fn inline_math(
+        &mut self,
+        math: &str,
+        _extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        write!(output, "${}$", math.trim())
+    }
+
+    fn variable(
+        &mut self,
+        name: &str,
+        value: Option<&str>,
+        _extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        match value {
+            Some(value) => self.text(value, output),
+            None => self.text(&format!("&{name}&"), output),
+        }
+    }
+
+    fn inline_code(&mut self, code: &str, output: &mut String) -> std::fmt::Result {
+        output.push_str(r"\texttt{");
+        escape(code, output);
+        output.push('}');
+        Ok(())
+    }
+
+    fn link(
+        &mut self,
+        file_path: Option<&str>,
+        targets: &[norg::LinkTarget],
+        description: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let file = file_path.and_then(link::parse_norg_file);
+        let mut fallback = String::new();
+        match (file, targets.first()) {
+            (None, Some(norg::LinkTarget::Footnote(title))) => {
+                trace!("adding footnote reference");
+                return write!(output, "{}", footnote_placeholder(&link::raw_title(title)));
+            }
+            (None, Some(norg::LinkTarget::Heading { title, .. })) => {
+                let title = link::raw_title(title);
+                escape(&title, &mut fallback);
+                return write!(
+                    output,
+                    r"\hyperref[sec:{}]{{{}}}",
+                    label(&title),
+                    description.unwrap_or(&fallback)
+                );
+            }
+            (None, Some(norg::LinkTarget::Url(url))) => {
+                return match description {
+                    Some(description) => {
+                        write!(output, r"\href{{{}}}{{{description}}}", escape_url(url))
+                    }
+                    None => write!(output, r"\url{{{}}}", escape_url(url)),
+                };
+            }
+            (Some(file), _) => escape(file.path(), &mut fallback),
+            (None, Some(norg::LinkTarget::Path(path))) => escape(path.trim(), &mut fallback),
+            (None, Some(target)) => {
+                error!(?target, "link target is not yet supported");
+            }
+            (None, None) => {}
+        }
+        // links to other files can't be followed from the printed document
+        match description {
+            Some(description) if !fallback.is_empty() => {
+                write!(output, r"{description} (\texttt{{{fallback}}})")
+            }
+            Some(description) => write!(output, "{description}"),
+            None => write!(output, r"\texttt{{{fallback}}}"),
+        }
+    }
+
+    fn heading(
+        &mut self,
+        level: u16,
+        title: &str,
+        content: &str,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let command = match level {
+            1 => "section",
+            2 => "subsection",
+            3 => "subsubsection",
+            4 => "paragraph",
+            _ => "subparagraph",
+        };
+        // labels are made from the escaped title, which keeps the same alphanumeric characters
+        write!(
+            output,
+            "\\{command}{{{}}}\\label{{sec:{}}}\n\n{content}",
+            title.trim(),
+            label(title)
+        )
+    }
+
+    fn list(&mut self, kind: ListKind, items: &str, output: &mut String) -> std::fmt::Result {
+        let environment = match kind {
+            ListKind::Ordered => "enumerate",
+            ListKind::Unordered => "itemize",
+        };
+        write!(
+            output,
+            "\\begin{{{environment}}}\n{items}\\end{{{environment}}}\n\n"
+        )
+    }
+
+    fn list_item(
+        &mut self,
+        _kind: ListKind,
+        _level: u16,
+        extensions: &[norg::DetachedModifierExtension],
+        text: &str,
+        content: &str,
+        _todo: Option<usize>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        output.push_str(r"\item");
+        for extension in extensions {
+            match extension {
+                norg::DetachedModifierExtension::Todo(norg::TodoStatus::Done) => {
+                    output.push_str(r"[$\boxtimes$]")
+                }
+                norg::DetachedModifierExtension::Todo(_) => output.push_str(r"[$\square$]"),
+                other => warn!(extension = ?other, "list item extension is not yet supported"),
+            }
+        }
+        let text = text.trim();
+        // `\item` takes a following `[` as its optional argument, the empty group ends it
+        if text.starts_with('[') {
+            output.push_str("{}");
+        }
+        writeln!(output, " {text}")?;
+        if !content.trim().is_empty() {
+            writeln!(output, "{}", content.trim())?;
+        }
+        Ok(())
+    }
+
+    fn quote(&mut self, _level: u16, content: &str, output: &mut String) -> std::fmt::Result {
+        write!(
+            output,
+            "\\begin{{quote}}\n{}\n\\end{{quote}}\n\n",
+            content.trim()
+        )
+    }
+
+    fn definition_list(&mut self, items: &str, output: &mut String) -> std::fmt::Result {
+        write!(
+            output,
+            "\\begin{{description}}\n{items}\\end{{description}}\n\n"
+        )
+    }
+
+    fn definition(&mut self, title: &str, content: &str, output: &mut String) -> std::fmt::Result {
+        // braces keep a `]` of the title from ending the optional argument
+        writeln!(output, r"\item[{{{}}}] {}", title.trim(), content.trim())
+    }
+
+    fn table(&mut self, rows: &[Vec<String>], output: &mut String) -> std::fmt::Result {
+        let columns = rows.first().map(Vec::len).unwrap_or_default();
+        if columns == 0 {
+            return Ok(());
+        }
+        writeln!(
+            output,
+            "\\begin{{center}}\n\\begin{{tabular}}{{|{}}}\n\\hline",
+            "l|".repeat(columns)
+        )?;
+        for row in rows {
+            let cells: Vec<_> = row.iter().map(|cell| cell.trim()).collect();
+            writeln!(output, "{} \\\\\n\\hline", cells.join(" & "))?;
+        }
+        write!(output, "\\end{{tabular}}\n\\end{{center}}\n\n")
+    }
+
+    fn code_block(
+        &mut self,
+        language: Option<&str>,
+        code: &str,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let code = code.trim_matches('\n');
+        match language.and_then(listings_language) {
+            Some(language) => write!(
+                output,
+                "\\begin{{lstlisting}}[language={language}]\n{code}\n\\end{{lstlisting}}\n\n"
+            ),
+            None => {
+                debug!(
+                    ?language,
+                    "language is not known to listings, using verbatim"
+                );
+                write!(output, "\\begin{{verbatim}}\n{code}\n\\end{{verbatim}}\n\n")
+            }
+        }
+    }
+
+    fn math_block(&mut self, math: &str, output: &mut String) -> std::fmt::Result {
+        write!(output, "\\[\n{}\n\\]\n\n", math.trim())
+    }
+
+    fn document_meta(&mut self, meta: &DocumentMeta, output: &mut String) -> std::fmt::Result {
+        let escaped = |value: &str| {
+            let mut text = String::new();
+            escape(value, &mut text);
+            text
+        };
+        if let Some(title) = &meta.title {
+            writeln!(output, r"\title{{{}}}", escaped(title))?;
+        }
+        if !meta.authors.is_empty() {
+            let authors: Vec<_> = meta.authors.iter().map(|author| escaped(author)).collect();
+            writeln!(output, r"\author{{{}}}", authors.join(r" \and "))?;
+        }
+        if let Some(created) = &meta.created {
+            writeln!(output, r"\date{{{}}}", escaped(created))?;
+        }
+        if meta.title.is_some() {
+            output.push_str("\\maketitle\n\n");
+        }
+        if let Some(description) = meta.description.as_deref().map(escaped) {
+            write!(
+                output,
+                "\\begin{{abstract}}\n{description}\n\\end{{abstract}}\n\n"
+            )?;
+        }
+        Ok(())
+    }
+
+    fn include(
+        &mut self,
+        source: &IncludeSource,
+        content: &str,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        trace!(label = source.label, "adding included content");
+        output.push_str(content);
+        Ok(())
+    }
+
+    fn date(&mut self, _datetime: &str, formatted: &str, output: &mut String) -> std::fmt::Result {
+        self.text(formatted, output)
+    }
+
+    fn error(&mut self, message: &str, output: &mut String) -> std::fmt::Result {
+        output.push_str(r"\textbf{Error:} ");
+        escape(message, output);
+        output.push_str("\n\n");
+        Ok(())
+    }
+}
@@ -0,0 +1,399 @@
+//! github flavored markdown output, elements without markdown equivalent are mapped to the closest one
+use std::fmt::Write;
+use tracing::{debug, error, trace, warn};
+
+use super::{Backend, DocumentMeta, Footnote, IncludeSource, ListKind};
+use crate::renderer::link;
+
+#[derive(Debug, Default)]
+pub struct MarkdownBackend {
+    /// front matter of the document, written at the start wherever `@document.meta` is placed
+    front_matter: String,
+}
+
+/// characters which have a meaning in markdown when found in text
+const SPECIAL_CHARACTERS: [char; 8] = ['\\', '*', '_', '`', '[', ']', '<', '>'];
+
+/// anchor which github generates for a heading, also used for footnote labels
+fn slug(title: &str) -> String {
+    title
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// removes the escapes added by [`MarkdownBackend::text`], markdown doesn't process escapes inside
+/// code spans so they would be shown as they are
+fn unescape(content: &str) -> String {
+    let mut unescaped = String::with_capacity(content.len());
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            c => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+/// smallest run of backticks which doesn't occur in the code, so that code can be wrapped with it
+fn code_fence(code: &str, min_len: usize) -> String {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in code.chars() {
+        if c == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    "`".repeat(min_len.max(longest + 1))
+}
+
+/// indents every non empty line, used for content nested in list items and definitions
+fn indent(content: &str, width: usize) -> String {
+    let padding = " ".repeat(width);
+    content
+        .trim_end()
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{padding}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// norg files are exported next to each other, so links point to the `.md` file with same path
+fn markdown_path(file: link::NorgFile) -> String {
+    let path = file.path();
+    let path = path.strip_suffix(".norg").unwrap_or(path);
+    match file {
+        link::NorgFile::System(_) | link::NorgFile::CurrentWorkspace(_) => format!("/{path}.md"),
+        link::NorgFile::Home(_) => format!("~/{path}.md"),
+        link::NorgFile::Workspace(_) | link::NorgFile::Relative(_) => format!("{path}.md"),
+    }
+}
+
+/// escapes characters which would break link destination
+fn link_destination(destination: &str) -> String {
+    if destination.contains([' ', '(', ')']) {
+        format!("<{destination}>")
+    } else {
+        destination.to_string()
+    }
+}
+
+impl Backend for MarkdownBackend {
+    fn document(&mut self, content: &str, output: &mut String) -> std::fmt::Result {
+        output.push_str(&std::mem::take(&mut self.front_matter));
+        writeln!(output, "{}", content.trim())
+    }
+
+    fn content_block(&mut self, content: &str, output: &mut String) -> std::fmt::Result {
+        output.push_str(content);
+        Ok(())
+    }
+
+    fn footnotes(&mut self, footnotes: &[Footnote], output: &mut String) -> std::fmt::Result {
+        if !footnotes.is_empty() {
+            output.push('\n');
+        }
+        for Footnote { title, content } in footnotes {
+            writeln!(
+                output,
+                "[^{}]: {}",
+                slug(title),
+                indent(content.trim(), 4).trim_start()
+            )?;
+        }
+        Ok(())
+    }
+
+    fn text(&mut self, text: &str, output: &mut String) -> std::fmt::Result {
+        for c in text.chars() {
+            if SPECIAL_CHARACTERS.contains(&c) {
+                output.push('\\');
+            }
+            output.push(c);
+        }
+        Ok(())
+    }
+
+    fn paragraph(&mut self, content: &str, output: &mut String) -> std::fmt::Result {
+        write!(output, "{}\n\n", content.trim())
+    }
+
+    fn attached_modifier(
+        &mut self,
+        modifier: char,
+        content: &str,
+        extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        if let Some(extension) = extension {
+            debug!(
+                extension,
+                "attached modifier extensions are dropped in markdown"
+            );
+        }
+        match modifier {
+            '*' => write!(output, "**{content}**"),
+            '/' => write!(output, "*{content}*"),
+            '-' => write!(output, "~~{content}~~"),
+            '_' => write!(output, "<ins>{content}</ins>"),
+            // there are no spoilers in markdown
+            '!' => write!(output, "{content}"),
+            '`' => {
+                let content = unescape(content);
+                let fence = code_fence(&content, 1);
+                write!(output, "{fence}{content}{fence}")
+            }
+            '^' => write!(output, "<sup>{content}</sup>"),
+            ',' => write!(output, "<sub>{content}</sub>"),
+            '%' => write!(output, "<!-- {content} -->"),
+            _ => {
+                warn!(modifier = %modifier, "unknown modifier");
+                Ok(())
+            }
+        }
+    }
+
+    fn inline_math(
+        &mut self,
+        math: &str,
+        _extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        write!(output, "${}$", math.trim())
+    }
+
+    fn variable(
+        &mut self,
+        name: &str,
+        value: Option<&str>,
+        _extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        match value {
+            Some(value) => self.text(value, output),
+            None => self.text(&format!("&{name}&"), output),
+        }
+    }
+
+    fn inline_code(&mut self, code: &str, output: &mut String) -> std::fmt::Result {
+        let fence = code_fence(code, 1);
+        // code starting or ending with backtick needs space so that it is not part of the fence
+        if code.starts_with('`') || code.ends_with('`') {
+            write!(output, "{fence} {code} {fence}")
+        } else {
+            write!(output, "{fence}{code}{fence}")
+        }
+    }
+
+    fn link(
+        &mut self,
+        file_path: Option<&str>,
+        targets: &[norg::LinkTarget],
+        description: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let file = file_path.and_then(link::parse_norg_file).map(markdown_path);
+        let target = match targets.first() {
+            Some(norg::LinkTarget::Footnote(title)) if file.is_none() => {
+                trace!("rendering footnote reference");
+                return write!(output, "[^{}]", slug(&link::raw_title(title)));
+            }
+            Some(
+                norg::LinkTarget::Heading { title, .. }
+                | norg::LinkTarget::Definition(title)
+                | norg::LinkTarget::Footnote(title),
+            ) => Some(format!("#{}", slug(&link::raw_title(title)))),
+            Some(norg::LinkTarget::Url(url)) => Some(url.to_string()),
+            Some(norg::LinkTarget::Path(path)) => Some(path.trim().to_string()),
+            Some(unsupported) => {
+                error!(target = ?unsupported, "link target is not yet supported");
+                None
+            }
+            None => None,
+        };
+        let destination = match (file, target) {
+            (Some(file), Some(fragment)) if fragment.starts_with('#') => file + &fragment,
+            (Some(file), _) => file,
+            (None, target) => target.unwrap_or("#".to_string()),
+        };
+        match description {
+            Some(description) => write!(
+                output,
+                "[{description}]({})",
+                link_destination(&destination)
+            ),
+            None if destination.contains("://") => write!(output, "<{destination}>"),
+            None => write!(
+                output,
+                "[{destination}]({})",
+                link_destination(&destination)
+            ),
+        }
+    }
+
+    fn heading(
+        &mut self,
+        level: u16,
+        title: &str,
+        content: &str,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let level = level.clamp(1, 6) as usize;
+        write!(
+            output,
+            "{} {}\n\n{content}",
+            "#".repeat(level),
+            title.trim()
+        )
+    }
+
+    fn list(&mut self, _kind: ListKind, items: &str, output: &mut String) -> std::fmt::Result {
+        writeln!(output, "{items}")
+    }
+
+    fn list_item(
+        &mut self,
+        kind: ListKind,
+        _level: u16,
+        extensions: &[norg::DetachedModifierExtension],
+        text: &str,
+        content: &str,
+        _todo: Option<usize>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let marker = match kind {
+            ListKind::Ordered => "1. ",
+            ListKind::Unordered => "- ",
+        };
+        output.push_str(marker);
+        for extension in extensions {
+            match extension {
+                norg::DetachedModifierExtension::Todo(norg::TodoStatus::Done) => {
+                    output.push_str("[x] ")
+                }
+                norg::DetachedModifierExtension::Todo(_) => output.push_str("[ ] "),
+                other => warn!(extension = ?other, "list item extension is not yet supported"),
+            }
+        }
+        writeln!(output, "{}", text.trim())?;
+        if !content.trim().is_empty() {
+            writeln!(output, "{}", indent(content, marker.len()))?;
+        }
+        Ok(())
+    }
+
+    fn quote(&mut self, _level: u16, content: &str, output: &mut String) -> std::fmt::Result {
+        for line in content.trim().lines() {
+            if line.is_empty() {
+                output.push_str(">\n");
+            } else {
+                writeln!(output, "> {line}")?;
+            }
+        }
+        output.push('\n');
+        Ok(())
+    }
+
+    fn definition_list(&mut self, items: &str, output: &mut String) -> std::fmt::Result {
+        output.push_str(items);
+        Ok(())
+    }
+
+    fn definition(&mut self, title: &str, content: &str, output: &mut String) -> std::fmt::Result {
+        // markdown doesn't have definition lists, so term is written in bold followed by its description
+        write!(output, "**{}**  \n{}\n\n", title.trim(), content.trim())
+    }
+
+    fn table(&mut self, rows: &[Vec<String>], output: &mut String) -> std::fmt::Result {
+        let Some((header, body)) = rows.split_first() else {
+            return Ok(());
+        };
+        let write_row = |row: &[String], output: &mut String| {
+            let cells: Vec<_> = row
+                .iter()
+                .map(|cell| cell.trim().replace('|', "\\|").replace('\n', " "))
+                .collect();
+            writeln!(output, "| {} |", cells.join(" | "))
+        };
+        write_row(header, output)?;
+        writeln!(output, "|{}", " --- |".repeat(header.len()))?;
+        for row in body {
+            write_row(row, output)?;
+        }
+        output.push('\n');
+        Ok(())
+    }
+
+    fn code_block(
+        &mut self,
+        language: Option<&str>,
+        code: &str,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let fence = code_fence(code, 3);
+        let code = code.strip_prefix('\n').unwrap_or(code);
+        write!(output, "{fence}{}\n{code}", language.unwrap_or_default())?;
+        if !code.ends_with('\n') {
+            output.push('\n');
+        }
+        write!(output, "{fence}\n\n")
+    }
+
+    fn math_block(&mut self, math: &str, output: &mut String) -> std::fmt::Result {
+        write!(output, "$$\n{}\n$$\n\n", math.trim())
+    }
+
+    fn document_meta(&mut self, meta: &DocumentMeta, _output: &mut String) -> std::fmt::Result {
+        if !self.front_matter.is_empty() {
+            warn!("document has more than one meta tag, only the first is used as front matter");
+            return Ok(());
+        }
+        // front matter is shown as a table by github, values are quoted as JSON strings are valid
+        // YAML strings, so that values like `a: b` stay a single value
+        self.front_matter.push_str("---\n");
+        for (key, value) in meta.entries() {
+            let value = serde_json::Value::String(value);
+            writeln!(self.front_matter, "{key}: {value}")?;
+        }
+        self.front_matter.push_str("---\n\n");
+        Ok(())
+    }
+
+    fn include(
+        &mut self,
+        source: &IncludeSource,
+        content: &str,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        trace!(label = source.label, "adding included content");
+        output.push_str(content);
+        Ok(())
+    }
+
+    fn date(&mut self, _datetime: &str, formatted: &str, output: &mut String) -> std::fmt::Result {
+        self.text(formatted, output)
+    }
+
+    fn error(&mut self, message: &str, output: &mut String) -> std::fmt::Result {
+        write!(output, "> **Error:** ")?;
+        self.text(message, output)?;
+        output.push_str("\n\n");
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+//! output formats for the renderer
+//!
+//! renderer walks the norg ast and calls [`Backend`] for every element, nested elements are rendered first
+//! and given as already rendered content, so backend only decides how an element looks in the output format
+pub use super::{list::ListKind, DocumentMeta};
+
+mod gemtext;
+mod html;
+mod latex;
+mod markdown;
+mod terminal;
+
+pub use gemtext::GemtextBackend;
+pub use html::HtmlBackend;
+pub use latex::LatexBackend;
+pub use markdown::MarkdownBackend;
+pub use terminal::TerminalBackend;
+
+/// rendered footnote, title and content are already rendered by the backend
+#[derive(Debug)]
+pub struct Footnote {
+    pub title: String,
+    pub content: String,
+}
+
+/// source of the included content
+#[derive(Debug)]
+pub struct IncludeSource<'a> {
+    /// file path as given in the include
+    pub label: &'a str,
+    /// absolute path of the included file
+    pub path: &'a std::path::Path,
+    /// path relative to workspace root, if the file is inside the workspace
+    pub workspace_path: Option<&'a std::path::Path>,
+    /// level and title of the included heading
+    pub heading: Option<(u16, &'a str)>,
+}
+
+pub trait Backend: std::fmt::Debug {
+    /// wraps whole document, footnotes are rendered separately after the document
+    fn document(&mut self, content: &str, output: &mut String) -> std::fmt::Result;
+
+    /// content of the document or a heading
+    fn content_block(&mut self, content: &str, output: &mut String) -> std::fmt::Result;
+
+    fn footnotes(&mut self, footnotes: &[Footnote], output: &mut String) -> std::fmt::Result;
+
+    /// plain text, backend is responsible for escaping
+    fn text(&mut self, text: &str, output: &mut String) -> std::fmt::Result;
+
+    fn paragraph(&mut self, content: &str, output: &mut String) -> std::fmt::Result;
+
+    /// `*bold*`, `/italic/` etc, extension is the content of `(...)` following the modifier
+    fn attached_modifier(
+        &mut self,
+        modifier: char,
+        content: &str,
+        extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result;
+
+    /// `$math$`, math is raw text which is not escaped
+    fn inline_math(
+        &mut self,
+        math: &str,
+        extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result;
+
+    /// `&name&`, value is none if variable is not defined
+    fn variable(
+        &mut self,
+        name: &str,
+        value: Option<&str>,
+        extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result;
+
+    /// inline verbatim, code is raw text which is not escaped
+    fn inline_code(&mut self, code: &str, output: &mut String) -> std::fmt::Result;
+
+    fn link(
+        &mut self,
+        file_path: Option<&str>,
+        targets: &[norg::LinkTarget],
+        description: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result;
+
+    /// heading with its rendered title and content
+    fn heading(
+        &mut self,
+        level: u16,
+        title: &str,
+        content: &str,
+        output: &mut String,
+    ) -> std::fmt::Result;
+
+    /// consecutive list items of same kind
+    fn list(&mut self, kind: ListKind, items: &str, output: &mut String) -> std::fmt::Result;
+
+    /// single list item, content is the nested content of the item,
+    /// todo is the number of the item for changing its TODO status, see [`crate::renderer::todo`]
+    fn list_item(
+        &mut self,
+        kind: ListKind,
+        level: u16,
+        extensions: &[norg::DetachedModifierExtension],
+        text: &str,
+        content: &str,
+        todo: Option<usize>,
+        output: &mut String,
+    ) -> std::fmt::Result;
+
+    /// quote along with nested quotes
+    fn quote(&mut self, level: u16, content: &str, output: &mut String) -> std::fmt::Result;
+
+    /// consecutive definitions
+    fn definition_list(&mut self, items: &str, output: &mut String) -> std::fmt::Result;
+
+    fn definition(&mut self, title: &str, content: &str, output: &mut String) -> std::fmt::Result;
+
+    /// table cells arranged in rows, every row has same number of cells
+    fn table(&mut self, rows: &[Vec<String>], output: &mut String) -> std::fmt::Result;
+
+    /// `@code` block, code is raw text which is not escaped
+    fn code_block(
+        &mut self,
+        language: Option<&str>,
+        code: &str,
+        output: &mut String,
+    ) -> std::fmt::Result;
+
+    /// `@math` block, math is raw text which is not escaped
+    fn math_block(&mut self, math: &str, output: &mut String) -> std::fmt::Result;
+
+    /// `@document.meta` of the document
+    fn document_meta(&mut self, meta: &DocumentMeta, output: &mut String) -> std::fmt::Result;
+
+    /// content included from other file
+    fn include(
+        &mut self,
+        source: &IncludeSource,
+        content: &str,
+        output: &mut String,
+    ) -> std::fmt::Result;
+
+    /// date inserted by `.date` macro, datetime is in `YYYY-MM-DD` format
+    fn date(&mut self, datetime: &str, formatted: &str, output: &mut String) -> std::fmt::Result;
+
+    /// errors which should be visible to the reader, like undefined macros
+    fn error(&mut self, message: &str, output: &mut String) -> std::fmt::Result;
+}
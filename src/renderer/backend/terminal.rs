@@ -0,0 +1,504 @@
+//! text output for reading notes in the terminal, with or without ansi colors
+use std::fmt::Write;
+use tracing::{debug, error, trace, warn};
+
+use super::{Backend, DocumentMeta, Footnote, IncludeSource, ListKind};
+use crate::renderer::link;
+
+/// marks ordered list items, these are numbered once all items of the list are known
+const ORDERED_MARKER: char = '\u{E000}';
+/// tabs of code blocks are expanded to columns of this width, so that the box stays aligned
+const TAB_WIDTH: usize = 4;
+
+#[derive(Debug)]
+pub struct TerminalBackend {
+    /// paragraphs are wrapped at this width
+    width: usize,
+    /// use ansi escape codes for styling
+    color: bool,
+}
+
+impl TerminalBackend {
+    pub fn new(width: usize, color: bool) -> Self {
+        Self {
+            width: width.max(20),
+            color,
+        }
+    }
+
+    /// plain text without any escape codes, useful for piping to other tools
+    pub fn plain(width: usize) -> Self {
+        Self::new(width, false)
+    }
+
+    /// text styled with ansi escape codes
+    pub fn ansi(width: usize) -> Self {
+        Self::new(width, true)
+    }
+
+    /// wraps content in SGR codes, `on` and `off` are parameters of the codes like `1` and `22` for bold
+    fn style(&self, on: &str, off: &str, content: &str) -> String {
+        if self.color {
+            format!("\x1b[{on}m{content}\x1b[{off}m")
+        } else {
+            content.to_string()
+        }
+    }
+}
+
+/// replaces the tabs of a line with spaces up to the next tab stop
+fn expand_tabs(line: &str) -> String {
+    let mut expanded = String::with_capacity(line.len());
+    for c in line.chars() {
+        if c == '\t' {
+            let spaces = TAB_WIDTH - expanded.chars().count() % TAB_WIDTH;
+            expanded.extend(std::iter::repeat_n(' ', spaces));
+        } else {
+            expanded.push(c);
+        }
+    }
+    expanded
+}
+
+/// width of the text as shown in terminal, escape codes are not counted
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip till the end of the SGR sequence
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+/// wraps words of the text so that lines are not longer than width, long words are kept as it is
+fn wrap(text: &str, width: usize) -> String {
+    let mut lines = Vec::new();
+    for paragraph_line in text.lines() {
+        let mut line = String::new();
+        for word in paragraph_line.split_whitespace() {
+            if !line.is_empty() && visible_width(&line) + 1 + visible_width(word) > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// indents every line except the first, first line is expected to follow a marker of same width
+fn hanging_indent(content: &str, width: usize) -> String {
+    let padding = " ".repeat(width);
+    content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            if index == 0 || line.is_empty() {
+                line.to_string()
+            } else {
+                format!("{padding}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// indents every non empty line
+fn indent(content: &str, width: usize) -> String {
+    let padding = " ".repeat(width);
+    content
+        .trim_end()
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{padding}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// character shown inside `[ ]` for todo items, same as in the norg source
+fn todo_marker(status: &norg::TodoStatus) -> char {
+    match status {
+        norg::TodoStatus::Undone => ' ',
+        norg::TodoStatus::Done => 'x',
+        norg::TodoStatus::NeedsClarification => '?',
+        norg::TodoStatus::Paused => '=',
+        norg::TodoStatus::Urgent => '!',
+        norg::TodoStatus::Recurring(_) => '+',
+        norg::TodoStatus::Pending => '-',
+        norg::TodoStatus::Canceled => '_',
+    }
+}
+
+impl Backend for TerminalBackend {
+    fn document(&mut self, content: &str, output: &mut String) -> std::fmt::Result {
+        writeln!(output, "{}", content.trim_end())
+    }
+
+    fn content_block(&mut self, content: &str, output: &mut String) -> std::fmt::Result {
+        output.push_str(content);
+        Ok(())
+    }
+
+    fn footnotes(&mut self, footnotes: &[Footnote], output: &mut String) -> std::fmt::Result {
+        if footnotes.is_empty() {
+            return Ok(());
+        }
+        writeln!(output, "\n{}", "─".repeat(self.width.min(40)))?;
+        for Footnote { title, content } in footnotes {
+            let label = format!("[{}] ", title.trim());
+            let text = wrap(
+                content.trim(),
+                self.width.saturating_sub(visible_width(&label)).max(10),
+            );
+            writeln!(
+                output,
+                "{}{}",
+                self.style("1", "22", &label),
+                hanging_indent(&text, visible_width(&label))
+            )?;
+        }
+        Ok(())
+    }
+
+    fn text(&mut self, text: &str, output: &mut String) -> std::fmt::Result {
+        // control characters in the document shouldn't be able to mess with the terminal,
+        // tabs are kept as they indent code
+        output.extend(
+            text.chars()
+                .filter(|c| *c != ORDERED_MARKER && (!c.is_control() || matches!(c, '\n' | '\t'))),
+        );
+        Ok(())
+    }
+
+    fn paragraph(&mut self, content: &str, output: &mut String) -> std::fmt::Result {
+        write!(output, "{}\n\n", wrap(content.trim(), self.width))
+    }
+
+    fn attached_modifier(
+        &mut self,
+        modifier: char,
+        content: &str,
+        extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        if let Some(extension) = extension {
+            debug!(
+                extension,
+                "attached modifier extensions are ignored in terminal"
+            );
+        }
+        let styled = match modifier {
+            '*' => self.style("1", "22", content),
+            '/' => self.style("3", "23", content),
+            '-' => self.style("9", "29", content),
+            '_' => self.style("4", "24", content),
+            '!' => self.style("7", "27", content),
+            '`' => self.style("36", "39", content),
+            '^' => format!("^({content})"),
+            ',' => format!("_({content})"),
+            '%' => {
+                trace!("skipping comment");
+                String::new()
+            }
+            _ => {
+                warn!(modifier = %modifier, "unknown modifier");
+                content.to_string()
+            }
+        };
+        output.push_str(&styled);
+        Ok(())
+    }
+
+    fn inline_math(
+        &mut self,
+        math: &str,
+        _extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let mut text = String::new();
+        self.text(math, &mut text)?;
+        output.push_str(&self.style("35", "39", &text));
+        Ok(())
+    }
+
+    fn variable(
+        &mut self,
+        name: &str,
+        value: Option<&str>,
+        _extension: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        match value {
+            Some(value) => self.text(value, output),
+            None => self.text(&format!("&{name}&"), output),
+        }
+    }
+
+    fn inline_code(&mut self, code: &str, output: &mut String) -> std::fmt::Result {
+        let mut text = String::new();
+        self.text(code, &mut text)?;
+        output.push_str(&self.style("36", "39", &text));
+        Ok(())
+    }
+
+    fn link(
+        &mut self,
+        file_path: Option<&str>,
+        targets: &[norg::LinkTarget],
+        description: Option<&str>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let file = file_path.and_then(link::parse_norg_file);
+        let target = match targets.first() {
+            Some(norg::LinkTarget::Footnote(title)) if file.is_none() => {
+                let label = format!("[{}]", link::raw_title(title).trim());
+                output.push_str(&self.style("1", "22", &label));
+                return Ok(());
+            }
+            Some(
+                norg::LinkTarget::Heading { title, .. }
+                | norg::LinkTarget::Definition(title)
+                | norg::LinkTarget::Footnote(title),
+            ) => Some(link::raw_title(title).trim().to_string()),
+            Some(norg::LinkTarget::Url(url)) => Some(url.to_string()),
+            Some(norg::LinkTarget::Path(path)) => Some(path.trim().to_string()),
+            Some(unsupported) => {
+                error!(target = ?unsupported, "link target is not yet supported");
+                None
+            }
+            None => None,
+        };
+        let destination = match (file, target) {
+            (Some(file), Some(target)) => format!("{}: {target}", file.path()),
+            (Some(file), None) => file.path().to_string(),
+            (None, target) => target.unwrap_or_default(),
+        };
+        match description {
+            Some(description) if !destination.is_empty() => {
+                let mut text = String::new();
+                self.text(&destination, &mut text)?;
+                write!(
+                    output,
+                    "{} ({})",
+                    self.style("4", "24", description),
+                    self.style("2", "22", &text)
+                )
+            }
+            Some(description) => {
+                output.push_str(&self.style("4", "24", description));
+                Ok(())
+            }
+            None => {
+                let mut text = String::new();
+                self.text(&destination, &mut text)?;
+                output.push_str(&self.style("4", "24", &text));
+                Ok(())
+            }
+        }
+    }
+
+    fn heading(
+        &mut self,
+        level: u16,
+        title: &str,
+        content: &str,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let title = format!("{} {}", "#".repeat(level.into()), title.trim());
+        let title = match level {
+            1 => self.style("1;4", "22;24", &title),
+            _ => self.style("1", "22", &title),
+        };
+        write!(output, "{title}\n\n{content}")
+    }
+
+    fn list(&mut self, _kind: ListKind, items: &str, output: &mut String) -> std::fmt::Result {
+        // nested lists are already numbered, so only markers at the start of the line belong to this list
+        let mut number = 0;
+        for line in items.lines() {
+            match line.strip_prefix(ORDERED_MARKER) {
+                Some(rest) => {
+                    number += 1;
+                    writeln!(output, "{number}. {rest}")?;
+                }
+                None => writeln!(output, "{line}")?,
+            }
+        }
+        output.push('\n');
+        Ok(())
+    }
+
+    fn list_item(
+        &mut self,
+        kind: ListKind,
+        _level: u16,
+        extensions: &[norg::DetachedModifierExtension],
+        text: &str,
+        content: &str,
+        _todo: Option<usize>,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let (marker, marker_width) = match kind {
+            ListKind::Ordered => (ORDERED_MARKER.to_string(), 3),
+            ListKind::Unordered => ("• ".to_string(), 2),
+        };
+        let mut text_prefix = String::new();
+        for extension in extensions {
+            match extension {
+                norg::DetachedModifierExtension::Todo(status) => {
+                    write!(text_prefix, "[{}] ", todo_marker(status))?;
+                }
+                other => warn!(extension = ?other, "list item extension is not yet supported"),
+            }
+        }
+        let text = wrap(
+            &format!("{text_prefix}{}", text.trim()),
+            self.width.saturating_sub(marker_width).max(10),
+        );
+        writeln!(output, "{marker}{}", hanging_indent(&text, marker_width))?;
+        let content = content.trim_end();
+        if !content.is_empty() {
+            writeln!(output, "{}", indent(content, marker_width))?;
+        }
+        Ok(())
+    }
+
+    fn quote(&mut self, _level: u16, content: &str, output: &mut String) -> std::fmt::Result {
+        let bar = self.style("2", "22", "│");
+        for line in content.trim().lines() {
+            writeln!(output, "{bar} {line}")?;
+        }
+        output.push('\n');
+        Ok(())
+    }
+
+    fn definition_list(&mut self, items: &str, output: &mut String) -> std::fmt::Result {
+        output.push_str(items);
+        Ok(())
+    }
+
+    fn definition(&mut self, title: &str, content: &str, output: &mut String) -> std::fmt::Result {
+        let content = wrap(content.trim(), self.width.saturating_sub(4).max(10));
+        write!(
+            output,
+            "{}\n{}\n\n",
+            self.style("1", "22", title.trim()),
+            indent(&content, 4)
+        )
+    }
+
+    fn table(&mut self, rows: &[Vec<String>], output: &mut String) -> std::fmt::Result {
+        let rows: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.split_whitespace().collect::<Vec<_>>().join(" "))
+                    .collect()
+            })
+            .collect();
+        let columns = rows.first().map(Vec::len).unwrap_or_default();
+        let widths: Vec<usize> = (0..columns)
+            .map(|column| {
+                rows.iter()
+                    .map(|row| visible_width(&row[column]))
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+        let border = |left: &str, middle: &str, right: &str| {
+            let lines: Vec<_> = widths.iter().map(|width| "─".repeat(width + 2)).collect();
+            format!("{left}{}{right}", lines.join(middle))
+        };
+        writeln!(output, "{}", border("┌", "┬", "┐"))?;
+        for (index, row) in rows.iter().enumerate() {
+            if index > 0 {
+                writeln!(output, "{}", border("├", "┼", "┤"))?;
+            }
+            output.push('│');
+            for (cell, width) in row.iter().zip(&widths) {
+                let padding = " ".repeat(width - visible_width(cell));
+                write!(output, " {cell}{padding} │")?;
+            }
+            output.push('\n');
+        }
+        writeln!(output, "{}\n", border("└", "┴", "┘"))
+    }
+
+    fn code_block(
+        &mut self,
+        language: Option<&str>,
+        code: &str,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        let mut code_text = String::new();
+        self.text(code.trim_matches('\n'), &mut code_text)?;
+        let code_text = code_text
+            .lines()
+            .map(expand_tabs)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let code_width = code_text.lines().map(visible_width).max().unwrap_or(0);
+        let label = language.map(|l| format!("─ {l} ")).unwrap_or_default();
+        let inner_width = code_width.max(visible_width(&label)) + 2;
+        writeln!(
+            output,
+            "┌{label}{}┐",
+            "─".repeat(inner_width - visible_width(&label))
+        )?;
+        for line in code_text.lines() {
+            let padding = " ".repeat(code_width - visible_width(line));
+            writeln!(output, "│ {}{padding} │", self.style("36", "39", line))?;
+        }
+        writeln!(output, "└{}┘\n", "─".repeat(inner_width))
+    }
+
+    fn math_block(&mut self, math: &str, output: &mut String) -> std::fmt::Result {
+        let mut text = String::new();
+        self.text(math.trim_matches('\n'), &mut text)?;
+        writeln!(output, "{}\n", indent(&self.style("35", "39", &text), 4))
+    }
+
+    fn document_meta(&mut self, meta: &DocumentMeta, output: &mut String) -> std::fmt::Result {
+        for (key, value) in meta.entries() {
+            let mut text = String::new();
+            self.text(&format!("{key}: {value}"), &mut text)?;
+            writeln!(output, "{}", self.style("2", "22", &text))?;
+        }
+        output.push('\n');
+        Ok(())
+    }
+
+    fn include(
+        &mut self,
+        source: &IncludeSource,
+        content: &str,
+        output: &mut String,
+    ) -> std::fmt::Result {
+        trace!(label = source.label, "adding included content");
+        output.push_str(content);
+        Ok(())
+    }
+
+    fn date(&mut self, _datetime: &str, formatted: &str, output: &mut String) -> std::fmt::Result {
+        self.text(formatted, output)
+    }
+
+    fn error(&mut self, message: &str, output: &mut String) -> std::fmt::Result {
+        let mut text = String::new();
+        self.text(&format!("error: {message}"), &mut text)?;
+        write!(output, "{}\n\n", self.style("31", "39", &text))
+    }
+}
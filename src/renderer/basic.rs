@@ -1,87 +1,67 @@
 //! basic markup tokens rendering
 
-use std::fmt::Write;
 use tracing::{instrument, trace, warn};
 
-use super::paragraph;
+use super::{backend::Backend, paragraph, RenderContext};
 
 type Modifier = char;
 
-#[instrument(skip(content, output))]
+/// free form modifiers (`*| text |*`) are wrapped in pipes, returns content inside pipes
+fn free_form_content(content: &[norg::ParagraphSegment]) -> Option<&[norg::ParagraphSegment]> {
+    use norg::{ParagraphSegment::Token, ParagraphSegmentToken::Special};
+    match content {
+        [Token(Special('|')), inner @ .., Token(Special('|'))] => Some(inner),
+        _ => None,
+    }
+}
+
+#[instrument(skip(content, ctx, backend, output))]
 pub fn render_attached(
     modifier: Modifier,
     content: &[norg::ParagraphSegment],
+    extension: Option<&str>,
+    ctx: &RenderContext,
+    backend: &mut dyn Backend,
     output: &mut String,
 ) -> std::fmt::Result {
+    if modifier == '&' {
+        let mut name = String::new();
+        content
+            .iter()
+            .for_each(|seg| paragraph::write_raw_segment(seg, &mut name));
+        let name = name.trim();
+        let value = ctx.variable(name);
+        match value {
+            Some(value) => trace!(name, value, "substituting variable"),
+            None => warn!(name, "variable is not defined"),
+        }
+        return backend.variable(name, value, extension, output);
+    }
+
+    if modifier == '$' {
+        // math is passed as it is, so that backends which understand math can use it directly
+        let mut math = String::new();
+        free_form_content(content)
+            .unwrap_or(content)
+            .iter()
+            .for_each(|seg| paragraph::write_raw_segment(seg, &mut math));
+        return backend.inline_math(&math, extension, output);
+    }
+
     // render segments first
     let mut segments_collector = String::new();
-    content
-        .iter()
-        .try_for_each(|seg| paragraph::render_segment(seg, &mut segments_collector))?;
-
+    if let Some(free_form) = free_form_content(content) {
+        trace!("rendering free form modifier");
+        // content of free form modifiers is verbatim, even backslashes are not escapes
+        let mut raw_content = String::new();
+        free_form
+            .iter()
+            .for_each(|seg| paragraph::write_raw_segment(seg, &mut raw_content));
+        backend.text(&raw_content, &mut segments_collector)?;
+    } else {
+        paragraph::render_segment_list(content, ctx, backend, &mut segments_collector)?;
+    }
 
     // apply modifiers for rendered segments
-    match modifier {
-        '*' => {
-            trace!("rendering bold text");
-            write!(output, "<strong>{}</strong>", segments_collector)?;
-        }
-        '/' => {
-            trace!("rendering italic text");
-            write!(output, "<em>{}</em>", segments_collector)?;
-        }
-        '-' => {
-            trace!("rendering striked text");
-            write!(output, "<s>{}</s>", segments_collector)?;
-        }
-        '_' => {
-            trace!("rendering underlined text");
-            write!(output, "<u>{}</u>", segments_collector)?;
-        }
-        '!' => {
-            trace!("rendering spoiler text");
-            write!(
-                output,
-                "<span class=\"spoiler\">{}</span>",
-                segments_collector
-            )?;
-        }
-        '`' => {
-            trace!("rendering inline code");
-            write!(output, "<code>{}</code>", segments_collector)?;
-        }
-        '^' => {
-            trace!("rendering superscript");
-            write!(output, "<sup>{}</sup>", segments_collector)?;
-        }
-        ',' => {
-            trace!("rendering subscript");
-            write!(output, "<sub>{}</sub>", segments_collector)?;
-        }
-        '$' => {
-            trace!("rendering math equation");
-            write!(output, "<span class=\"math\">{}</span>", segments_collector)?;
-        }
-        '&' => {
-            trace!("rendering variable");
-            write!(
-                output,
-                "<span class=\"variable\">{}</span>",
-                segments_collector
-            )?;
-        }
-        '%' => {
-            trace!("rendering commented text");
-            write!(
-                output,
-                "<!-- {} -->",
-                segments_collector
-            )?;
-        }
-
-        _ => {
-            warn!("unknown modifier");
-        }
-    };
-    Ok(())
+    backend.attached_modifier(modifier, &segments_collector, extension, output)
 }
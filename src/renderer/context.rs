@@ -0,0 +1,147 @@
+//! state shared between renderers while rendering a single document
+use std::{collections::HashMap, sync::Arc};
+use tracing::trace;
+
+use super::{
+    macros::{IncludeCache, Macro},
+    DocumentMeta,
+};
+
+/// footnote title, extensions and content, these are rendered at the end of the document
+pub(super) type Footnote = (
+    Vec<norg::ParagraphSegment>,
+    Vec<norg::DetachedModifierExtension>,
+    Vec<norg::NorgASTFlat>,
+);
+
+#[derive(Debug, Clone, Default)]
+pub struct RenderContext {
+    pub(super) footnotes: Vec<Footnote>,
+    /// values of `&name&` variables, workspace variables are overridden by document meta and `=set` tags
+    variables: HashMap<String, String>,
+    /// macros defined with `=name ... =end` in the document
+    pub(super) macros: HashMap<String, Macro>,
+    /// path of the file being rendered, relative includes are resolved from its directory
+    pub(super) document_path: Option<std::path::PathBuf>,
+    /// root of the workspace, used to resolve `$/` paths
+    pub(super) workspace_root: Option<std::path::PathBuf>,
+    /// macros which are being expanded currently, used to detect recursive macros
+    pub(super) macro_stack: Vec<String>,
+    /// files which are being included currently, used to detect recursive includes
+    pub(super) include_stack: Vec<std::path::PathBuf>,
+    /// parsed included files, shared with other renders of the workspace
+    pub(super) include_cache: Option<Arc<IncludeCache>>,
+    /// every file included while rendering, rendered output should be invalidated if any of these changes
+    pub(super) dependencies: Vec<std::path::PathBuf>,
+    /// parsed `@document.meta` of the document
+    pub(super) meta: DocumentMeta,
+    /// whether the document or its includes show the current date with `.date`
+    pub(super) uses_date: bool,
+    /// number of the next TODO list item, `None` unless the items are numbered or while rendering
+    /// content whose items aren't numbered, see [`super::todo`]
+    todo_items: Option<usize>,
+}
+
+impl RenderContext {
+    /// creates context with workspace level variables
+    pub fn with_variables(variables: HashMap<String, String>) -> Self {
+        Self {
+            variables,
+            ..Default::default()
+        }
+    }
+
+    /// sets the path of the document, this is required for including other files with relative paths
+    pub fn with_document_path(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.document_path = Some(path.into());
+        self
+    }
+
+    /// sets the workspace root, this is required for including files with `$/` paths
+    pub fn with_workspace_root(mut self, root: impl Into<std::path::PathBuf>) -> Self {
+        self.workspace_root = Some(root.into());
+        self
+    }
+
+    /// included files are parsed once and kept in the cache until they are modified
+    pub fn with_include_cache(mut self, cache: Arc<IncludeCache>) -> Self {
+        self.include_cache = Some(cache);
+        self
+    }
+
+    /// numbers the TODO list items of the document, so that pages can change their status
+    pub fn with_todo_items(mut self) -> Self {
+        self.todo_items = Some(0);
+        self
+    }
+
+    /// files included in the rendered document
+    pub fn dependencies(&self) -> &[std::path::PathBuf] {
+        &self.dependencies
+    }
+
+    /// whether the rendered output shows the current date, such output is only valid for the day
+    /// it was rendered on
+    pub fn uses_date(&self) -> bool {
+        self.uses_date
+    }
+
+    /// `@document.meta` of the rendered document
+    pub fn document_meta(&self) -> &DocumentMeta {
+        &self.meta
+    }
+
+    /// number of the next TODO list item
+    pub(super) fn next_todo_item(&mut self) -> Option<usize> {
+        let item = self.todo_items?;
+        self.todo_items = Some(item + 1);
+        Some(item)
+    }
+
+    /// renders content whose TODO list items aren't numbered, as it isn't rendered in document order
+    pub(super) fn without_todo_items<T>(&mut self, render: impl FnOnce(&mut Self) -> T) -> T {
+        let todo_items = self.todo_items.take();
+        let result = render(self);
+        self.todo_items = todo_items;
+        result
+    }
+
+    pub(super) fn add_dependency(&mut self, path: &std::path::Path) {
+        if !self.dependencies.iter().any(|dep| dep == path) {
+            self.dependencies.push(path.to_path_buf());
+        }
+    }
+
+    pub(super) fn set_variable(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let (name, value) = (name.into(), value.into());
+        trace!(%name, %value, "defining variable");
+        self.variables.insert(name, value);
+    }
+
+    pub(super) fn variable(&self, name: &str) -> Option<&str> {
+        self.variables.get(name).map(String::as_str)
+    }
+
+    /// binds variables for a scope, returns previous values which should be given back to [`Self::restore_variables`]
+    pub(super) fn bind_variables(
+        &mut self,
+        bindings: impl IntoIterator<Item = (String, String)>,
+    ) -> Vec<(String, Option<String>)> {
+        bindings
+            .into_iter()
+            .map(|(name, value)| {
+                let previous = self.variables.insert(name.clone(), value);
+                (name, previous)
+            })
+            .collect()
+    }
+
+    pub(super) fn restore_variables(&mut self, previous: Vec<(String, Option<String>)>) {
+        for (name, value) in previous.into_iter().rev() {
+            match value {
+                Some(value) => self.variables.insert(name, value),
+                None => self.variables.remove(&name),
+            };
+        }
+    }
+}
@@ -1,12 +1,13 @@
-use super::paragraph;
-use std::fmt::Write;
+use super::{backend::Backend, paragraph, RenderContext};
 use tracing::{debug, instrument, trace, warn};
 
-#[instrument(skip(extensions, content, output))]
+#[instrument(skip(extensions, content, ctx, backend, output))]
 pub fn render_definition(
     title: Vec<norg::ParagraphSegment>,
     extensions: Vec<norg::DetachedModifierExtension>,
     content: Vec<norg::NorgASTFlat>,
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
     output: &mut String,
 ) -> std::fmt::Result {
     // FIX: this renders headings in separate lines for separate words
@@ -15,29 +16,22 @@ pub fn render_definition(
         warn!(extensions=?extensions, "extensions are not supported for definition" );
     }
 
-    write!(output, "<dt>")?;
     debug!(
         num_title_segments = title.len(),
         "Rendering definition term"
     );
-    for segment in &title {
-        paragraph::render_segment(segment, output)?;
-    }
-    write!(output, "</dt>")?;
+    let title = paragraph::render_segments(&title, ctx, backend)?;
 
-    write!(output, "<dd>")?;
-    for cont_ast in content {
-        match cont_ast {
-            norg::NorgASTFlat::Paragraph(paras) => paras
-                .iter()
-                .try_for_each(|seg| paragraph::render_segment(seg, output))?,
-            _ => {
-                warn!(?cont_ast, "Unsupported token found inside definition");
-            }
+    let mut details = String::new();
+    match content.as_slice() {
+        // single paragraph definitions are kept inline
+        [norg::NorgASTFlat::Paragraph(paras)] => {
+            paragraph::render_segment_list(paras, ctx, backend, &mut details)?
         }
+        // ranged definitions can have multiple paragraphs, code blocks etc
+        blocks => super::render_flat_block(blocks, ctx, backend, &mut details)?,
     }
-    write!(output, "</dd>")?;
 
     debug!("Finished rendering definition term and details");
-    Ok(())
+    backend.definition(&title, &details, output)
 }
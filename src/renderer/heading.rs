@@ -1,26 +1,20 @@
 //! module which does rendering of headings
-use crate::renderer::paragraph;
-use std::fmt::Write;
+use crate::renderer::{backend::Backend, paragraph, RenderContext};
 use tracing::{debug, instrument, trace, warn};
 
-#[instrument(skip(content, footnotes, output))]
+#[instrument(skip(content, ctx, backend, output))]
 pub fn render_heading(
     level: u16,
     title: Vec<norg::ParagraphSegment>,
     extensions: Vec<norg::DetachedModifierExtension>,
     content: Vec<norg::NorgAST>,
-    footnotes: &mut Vec<(
-        Vec<norg::ParagraphSegment>,
-        Vec<norg::DetachedModifierExtension>,
-        Vec<norg::NorgASTFlat>,
-    )>,
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
     output: &mut String,
 ) -> std::fmt::Result {
     trace!("rendering heading");
 
-    write!(output, "<div class=\"heading_block\">")?;
-
-    let title_text = paragraph::render_segments(&title)?;
+    let title_text = paragraph::render_segments(&title, ctx, backend)?;
     debug!(?title, "adding heading");
 
     // Apply extensions first since modifiers which are applied at the end should not be applied to inner lists
@@ -28,26 +22,11 @@ pub fn render_heading(
         warn!(?extension, "unimplemented for extensions of headings");
     }
 
-    let heading_class = format!("heading_{level}");
-    let sanitized_title = crate::html::sanitize_html(&title_text);
-    match level {
-        1..5 => write!(
-            output,
-            "<h{level} class=\"{}\">{}</h{level}>",
-            heading_class, sanitized_title
-        )?,
-        _ => write!(
-            output,
-            "<h6 class=\"{}\">{}</h6>",
-            heading_class, sanitized_title
-        )?,
-    };
-
+    let mut rendered_content = String::new();
     if !content.is_empty() {
         let mut content_iter = content.into_iter().peekable();
-        super::render_ast(&mut content_iter, footnotes, output)?;
+        super::render_ast(&mut content_iter, ctx, backend, &mut rendered_content)?;
     }
 
-    write!(output, "</div>")?;
-    Ok(())
+    backend.heading(level, &title_text, &rendered_content, output)
 }
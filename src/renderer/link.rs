@@ -1,160 +1,93 @@
-//! this module handles rendering of links
+//! this module handles locations used in links, backends decide how these are written in the output
 
 use crate::{constants, renderer::paragraph};
-use std::fmt::Write;
-use tracing::{debug, error, instrument, trace, warn};
+use tracing::{error, instrument, trace};
 
-#[instrument(skip(output, description_segments))]
-pub fn render_link(
-    file_path: Option<&str>,
-    targets: &[norg::LinkTarget],
-    description_segments: Option<&[norg::ParagraphSegment]>,
-    output: &mut String,
-) -> std::fmt::Result {
-    trace!("rendering link");
-    let norg_file_path = file_path.and_then(|norg_path| {
-        let mut norg_path_iter = norg_path.trim_start().chars();
-        match norg_path_iter
-            .next()
-            .expect("there should be some character found if the norg file path is specified")
-        {
-            '/' => {
-                // this is norg file from root of the file system
-                Some(format!(
-                    "{}/{}",
-                    constants::SYSTEM_PATH,
-                    norg_path_iter.as_str().trim_start(),
-                ))
-            }
-            '$' => {
-                // this is norg file from root of workspace
-                // get next character to see if it is space or not
-                let work_dir_path = norg_path_iter.as_str().trim_start();
-                match work_dir_path.chars().next() {
-                    Some('/') => {
-                        // this path is from root of the workspace
-                        Some(format!(
-                            "{}{}",
-                            constants::CURRENT_WORKSPACE_PATH,
-                            norg_path_iter.as_str().trim_start(),
-                        ))
-                    }
-                    None => {
-                        error!("invalid File path found, empty path");
-                        None
-                    }
-                    _ => {
-                        // path is from different workspace
-                        Some(format!("{}/{}", constants::WORKSPACE_PATH, work_dir_path,))
-                    }
-                }
-            }
-            '~' => {
-                // this is norg file from home directory
-                Some(format!(
-                    "{}{}",
-                    constants::HOME_PATH,
-                    norg_path_iter.as_str().trim_start(),
-                ))
-            }
-            _ => {
-                // this is the relative path to file
-                Some(norg_path.trim_start().to_string())
-            }
+/// location of the norg file given in a link `{:path:}`, path is kept without the location prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NorgFile<'a> {
+    /// `/path` from the root of the file system
+    System(&'a str),
+    /// `$/path` from the root of current workspace
+    CurrentWorkspace(&'a str),
+    /// `$name/path` from other workspace, path contains the name of the workspace
+    Workspace(&'a str),
+    /// `~/path` from the home directory
+    Home(&'a str),
+    /// path relative to the current file
+    Relative(&'a str),
+}
+
+impl NorgFile<'_> {
+    /// path of the file, relative to its location
+    pub fn path(&self) -> &str {
+        match self {
+            Self::System(path)
+            | Self::CurrentWorkspace(path)
+            | Self::Workspace(path)
+            | Self::Home(path)
+            | Self::Relative(path) => path,
         }
-    });
-    debug!(?norg_file_path, "norg file found?");
+    }
 
-    let fragment_or_external_link = targets.first().and_then(|target| {
-        match target {
-            norg::LinkTarget::Heading { level, title } => {
-                let link = format!(
-                    "#{}_h{}",
-                    paragraph::render_segments(title)
-                        .expect("string formatting is infallible")
-                        .replace(' ', "_"),
-                    level
-                );
-                Some(link)
-            }
-            norg::LinkTarget::LineNumber(_) => {
-                error!("<!-- Unsupported feature: line number on anchor -->");
-                Some("#".to_string())
-            }
-            norg::LinkTarget::Footnote(title) => Some(format!(
-                "#{}_f",
-                paragraph::render_segments(title)
-                    .expect("string formatting is infallible")
-                    .replace(' ', "_")
-            )),
-            norg::LinkTarget::Definition(title) => Some(format!(
-                "#{}_d",
-                paragraph::render_segments(title)
-                    .expect("string formatting is infallible")
-                    .replace(' ', "_")
-            )),
-            norg::LinkTarget::Wiki(title) => {
-                error!(target = ?title, "wiki links are not yet supported");
-                None
-            }
-            norg::LinkTarget::Generic(generics) => {
-                error!(target=?generics, "wiki links are not yet supported");
-                None
-            }
-            norg::LinkTarget::Extendable(extendable) => {
-                error!(target=?extendable, "extendable links are not yet supported");
-                None
-            }
-            norg::LinkTarget::Path(raw_path) => {
-                let mut raw_path_iter = raw_path.trim_start().chars();
-                match raw_path_iter.next() {
-                    None => {
-                        warn!("empty raw file path found as a link");
-                        None
-                    }
-                    Some('/') => {
-                        // root raw file path
-                        Some(format!(
-                            "{}/{}?raw=1",
-                            constants::SYSTEM_PATH,
-                            raw_path_iter.as_str()
-                        ))
-                    }
-                    Some('~') => {
-                        // home raw file path
-                        Some(format!(
-                            "{}{}?raw=1",
-                            constants::HOME_PATH,
-                            raw_path_iter.as_str()
-                        ))
-                    }
-                    Some(_) => Some(raw_path.trim_start().to_string()),
-                }
-            }
-            norg::LinkTarget::Url(text) => Some(text.to_string()),
-            norg::LinkTarget::Timestamp(text) => {
-                error!(target=?text, "timestamp links are not yet supported");
-                None
-            }
+    /// url from which the server renders this file
+    pub fn url(&self) -> String {
+        match self {
+            Self::System(path) => format!("{}/{path}", constants::SYSTEM_PATH),
+            Self::CurrentWorkspace(path) => format!("{}/{path}", constants::CURRENT_WORKSPACE_PATH),
+            Self::Workspace(path) => format!("{}/{path}", constants::WORKSPACE_PATH),
+            Self::Home(path) => format!("{}/{path}", constants::HOME_PATH),
+            Self::Relative(path) => path.to_string(),
         }
-    });
-    debug!(?fragment_or_external_link, "href found?");
+    }
+}
 
-    let href = if let Some(mut norg_file_path) = norg_file_path {
-        if let Some(fragment) = fragment_or_external_link {
-            norg_file_path.push_str(&fragment);
-            norg_file_path
-        } else {
-            norg_file_path
+#[instrument]
+pub fn parse_norg_file(norg_path: &str) -> Option<NorgFile<'_>> {
+    trace!("parsing norg file path of link");
+    let norg_path = norg_path.trim_start();
+    let mut norg_path_iter = norg_path.chars();
+    match norg_path_iter.next() {
+        None => {
+            error!("invalid File path found, empty path");
+            None
         }
-    } else {
-        fragment_or_external_link.unwrap_or("#".to_string())
-    };
+        // this is norg file from root of the file system
+        Some('/') => Some(NorgFile::System(norg_path_iter.as_str().trim_start())),
+        Some('$') => {
+            let work_dir_path = norg_path_iter.as_str().trim_start();
+            match work_dir_path.strip_prefix('/') {
+                // this path is from root of the workspace
+                Some(path) => Some(NorgFile::CurrentWorkspace(path)),
+                None if work_dir_path.is_empty() => {
+                    error!("invalid File path found, empty path");
+                    None
+                }
+                // path is from different workspace
+                None => Some(NorgFile::Workspace(work_dir_path)),
+            }
+        }
+        // this is norg file from home directory
+        Some('~') => Some(NorgFile::Home(
+            norg_path_iter.as_str().trim_start().trim_start_matches('/'),
+        )),
+        // this is the relative path to file
+        Some(_) => Some(NorgFile::Relative(norg_path)),
+    }
+}
 
-    let title = description_segments
-        .map(paragraph::render_segments)
-        .unwrap_or_else(|| Ok(href.clone()))?;
-    write!(output, "<a href={href}>{title}</a>")
+/// title of a link target as written in the source
+pub fn raw_title(title: &[norg::ParagraphSegment]) -> String {
+    let mut raw = String::new();
+    title
+        .iter()
+        .for_each(|seg| paragraph::write_raw_segment(seg, &mut raw));
+    raw
 }
 
-// TODO: Create a function to generate fragment tags, that will be used to create anchor tags and link to that tag
+/// id of the element which can be targeted by links, suffix tells the kind of the element
+/// like `h1` for first level heading, `f` for footnote and `d` for definition
+// TODO: use this to create anchor tags for the targets
+pub fn fragment_id(title: &str, suffix: &str) -> String {
+    format!("{}_{suffix}", title.replace(' ', "_"))
+}
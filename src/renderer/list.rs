@@ -1,45 +1,8 @@
-use std::fmt::Write;
-use tracing::{debug, trace, warn};
+use tracing::trace;
 
-fn apply_extensions(
-    extensions: Vec<norg::DetachedModifierExtension>,
-    output: &mut String,
-) -> Result<bool, std::fmt::Error> {
-    let mut add_todo = false;
-    for token in extensions {
-        match token {
-            norg::DetachedModifierExtension::Todo(todo_status) => {
-                trace!("applying todo extension");
-                let status = match todo_status {
-                    norg::TodoStatus::Undone => "undone",
-                    norg::TodoStatus::Done => "done",
-                    norg::TodoStatus::NeedsClarification => "needs-clarification",
-                    norg::TodoStatus::Paused => "paused",
-                    norg::TodoStatus::Urgent => "urgent",
-                    norg::TodoStatus::Recurring(_date) => {
-                        "recurring"
-                        // debug!(?date, "Rendering TodoStatus: Recurring");
-                        // if let Some(date) = date {
-                        //     spb.span(|sspb| sspb.class("todo-date").text(date));
-                        // }
-                    }
-                    norg::TodoStatus::Pending => "pending",
-                    norg::TodoStatus::Canceled => "canceled",
-                };
-                debug!(status, "Rendering TodoStatus");
-                write!(output, " data-status=\"{}\"", status)?;
-                add_todo = true;
-            }
-            norg::DetachedModifierExtension::Priority(_) => todo!(),
-            norg::DetachedModifierExtension::Timestamp(_) => todo!(),
-            norg::DetachedModifierExtension::DueDate(_) => todo!(),
-            norg::DetachedModifierExtension::StartDate(_) => todo!(),
-        }
-    }
-    Ok(add_todo)
-}
+use super::{backend::Backend, RenderContext};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ListKind {
     Ordered,
     Unordered,
@@ -56,47 +19,79 @@ impl std::fmt::Display for ListKind {
 
 /// extract text from div > paragraph
 /// this since it is better to keep list item in text instead of div > paragraph
+#[allow(clippy::too_many_arguments)]
 pub fn render_list_element(
     text: Box<norg::NorgASTFlat>,
     inner_content: Vec<norg::NorgAST>,
     level: u16,
     kind: ListKind,
     extensions: Vec<norg::DetachedModifierExtension>,
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
     output: &mut String,
 ) -> std::fmt::Result {
+    trace!(%kind, level, "rendering list item");
+    let todo = todo_item(&extensions, ctx);
     let mut text_content = String::new();
-    super::render_flat_ast(&text, &mut text_content)?;
+    render_item_text(&text, ctx, backend, &mut text_content)?;
 
-    // TODO: replace this and take from root footnote builder
-    // but footnote is not allowed in list element
-    let mut footnotes = Vec::new();
     let mut inner_content_rendered = String::new();
     if !inner_content.is_empty() {
         let mut tokens = inner_content.into_iter().peekable();
-        super::render_ast(&mut tokens, &mut footnotes, &mut inner_content_rendered)?;
-    }
-    if !footnotes.is_empty() {
-        warn!("Footnotes are present in list items which shouldn't be possible");
+        super::render_ast(&mut tokens, ctx, backend, &mut inner_content_rendered)?;
     }
 
-    // Start list item with extensions as attributes
-    write!(output, "<li class={kind}_l{level}")?;
-    let add_todo = apply_extensions(extensions, output)?;
-    output.push('>');
+    backend.list_item(
+        kind,
+        level,
+        &extensions,
+        &text_content,
+        &inner_content_rendered,
+        todo,
+        output,
+    )
+}
 
-    // Add status indicator span for todo items
-    if add_todo {
-        output.push_str("<span class=\"status-indicator\"></span>");
+/// number of the item if it has a TODO status, items are numbered before their nested content
+fn todo_item(
+    extensions: &[norg::DetachedModifierExtension],
+    ctx: &mut RenderContext,
+) -> Option<usize> {
+    if super::todo::has_todo(extensions) {
+        ctx.next_todo_item()
+    } else {
+        None
     }
+}
 
-    // Add the text content
-    output.push_str(&text_content);
-
-    // Add inner content if present
-    if !inner_content_rendered.is_empty() {
-        output.push_str(&inner_content_rendered);
+/// renders text of the list item, paragraphs are kept inline
+/// and any other block (slide/indent segment content) is rendered as it is
+fn render_item_text(
+    text: &norg::NorgASTFlat,
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
+    output: &mut String,
+) -> std::fmt::Result {
+    match text {
+        norg::NorgASTFlat::Paragraph(segments) => {
+            super::paragraph::render_segment_list(segments, ctx, backend, output)
+        }
+        _ => super::render_flat_ast(text, ctx, backend, output),
     }
+}
 
-    output.push_str("</li>");
-    Ok(())
+/// renders list item which is part of slide or indent segment, these items doesn't have nested content
+pub fn render_flat_list_element(
+    text: &norg::NorgASTFlat,
+    level: u16,
+    kind: ListKind,
+    extensions: &[norg::DetachedModifierExtension],
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
+    output: &mut String,
+) -> std::fmt::Result {
+    let todo = todo_item(extensions, ctx);
+    let mut text_content = String::new();
+    render_item_text(text, ctx, backend, &mut text_content)?;
+    backend.list_item(kind, level, extensions, &text_content, "", todo, output)
 }
@@ -0,0 +1,480 @@
+//! macro tags (`=name params ... =end`) and their invocations with infirm tags (`.name args`)
+//!
+//! parameters of the macro are available as variables inside the macro body,
+//! along with user defined macros there are few builtin macros
+//! - `.include file` renders other norg file in place, `.include {:file:* Heading}` renders only the heading
+//! - `.date [format]` current date, format is strftime style string
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use tracing::{debug, error, instrument, trace, warn};
+
+use super::{
+    backend::{Backend, IncludeSource},
+    link, RenderContext,
+};
+
+/// standard infirm tags of the spec, these are not macros even if nothing renders them yet
+const STANDARD_INFIRM_TAGS: [&str; 2] = ["image", "toc"];
+
+/// first part of the name given to the ranged tags which are macro definitions, the parser gives
+/// `|name` and `=name` tags the same node so [`mark_definitions`] tells them apart with this
+pub(super) const DEFINITION_MARK: &str = "=";
+
+/// parsed file which can be included, its macro definitions are marked
+#[derive(Debug, Clone)]
+struct IncludedFile {
+    modified: SystemTime,
+    tokens: Vec<norg::NorgAST>,
+}
+
+/// parsed included files shared between renders, so that a file included by many documents is
+/// parsed only once until it is modified
+#[derive(Debug, Default)]
+pub struct IncludeCache {
+    files: Mutex<HashMap<PathBuf, IncludedFile>>,
+}
+
+impl IncludeCache {
+    fn get(&self, path: &Path, modified: SystemTime) -> Option<IncludedFile> {
+        let files = self.files.lock().ok()?;
+        files
+            .get(path)
+            .filter(|file| file.modified == modified)
+            .cloned()
+    }
+
+    fn insert(&self, path: &Path, file: IncludedFile) {
+        if let Ok(mut files) = self.files.lock() {
+            files.insert(path.to_path_buf(), file);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Macro {
+    parameters: Vec<String>,
+    content: Vec<norg::NorgASTFlat>,
+}
+
+/// whether each `|name` and `=name` ranged tag of the source is a macro tag, in document order
+///
+/// tags inside verbatim tags are their text and tags without an `end` aren't parsed as tags,
+/// so neither of them are included
+fn macro_tag_kinds(input: &str) -> Vec<bool> {
+    let mut kinds: Vec<Option<bool>> = Vec::new();
+    let mut open: Vec<(char, usize)> = Vec::new();
+    let mut verbatim = false;
+    for line in input.lines().map(str::trim) {
+        if verbatim {
+            verbatim = line != "@end";
+            continue;
+        }
+        let mut chars = line.chars();
+        let Some(prefix) = chars.next().filter(|c| matches!(c, '@' | '|' | '=')) else {
+            continue;
+        };
+        let Some(name) = chars
+            .as_str()
+            .split_whitespace()
+            .next()
+            .filter(|name| name.starts_with(char::is_alphabetic))
+        else {
+            continue;
+        };
+        match (prefix, name) {
+            ('@', "end") => {}
+            ('@', _) => verbatim = true,
+            (_, "end") => {
+                // tags opened after the closed one are never closed
+                if let Some(position) = open.iter().rposition(|(c, _)| *c == prefix) {
+                    kinds[open[position].1] = Some(prefix == '=');
+                    open.truncate(position);
+                }
+            }
+            _ => {
+                open.push((prefix, kinds.len()));
+                kinds.push(None);
+            }
+        }
+    }
+    kinds.into_iter().flatten().collect()
+}
+
+fn ranged_tags<'a>(tokens: &'a mut [norg::NorgAST], tags: &mut Vec<&'a mut Vec<String>>) {
+    for token in tokens {
+        match token {
+            norg::NorgAST::Heading { content, .. }
+            | norg::NorgAST::NestableDetachedModifier { content, .. } => ranged_tags(content, tags),
+            norg::NorgAST::RangeableDetachedModifier { content, .. } => {
+                flat_ranged_tags(content, tags)
+            }
+            norg::NorgAST::CarryoverTag { next_object, .. } => {
+                ranged_tags(std::slice::from_mut(next_object.as_mut()), tags)
+            }
+            norg::NorgAST::RangedTag { name, content, .. } => {
+                tags.push(name);
+                flat_ranged_tags(content, tags);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn flat_ranged_tags<'a>(tokens: &'a mut [norg::NorgASTFlat], tags: &mut Vec<&'a mut Vec<String>>) {
+    for token in tokens {
+        match token {
+            norg::NorgASTFlat::NestableDetachedModifier { content, .. } => {
+                flat_ranged_tags(std::slice::from_mut(content.as_mut()), tags)
+            }
+            norg::NorgASTFlat::RangeableDetachedModifier { content, .. } => {
+                flat_ranged_tags(content, tags)
+            }
+            norg::NorgASTFlat::CarryoverTag { next_object, .. } => {
+                flat_ranged_tags(std::slice::from_mut(next_object.as_mut()), tags)
+            }
+            norg::NorgASTFlat::RangedTag { name, content, .. } => {
+                tags.push(name);
+                flat_ranged_tags(content, tags);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// prefixes the names of the `=name` tags of the document with [`DEFINITION_MARK`]
+///
+/// ranged tags of the parsed document are matched with the tags of the source in document order,
+/// nothing is marked if they don't match, so that a `|name` tag is never taken as a definition
+pub(super) fn mark_definitions(input: &str, tokens: &mut [norg::NorgAST]) {
+    let kinds = macro_tag_kinds(input);
+    let mut tags = Vec::new();
+    ranged_tags(tokens, &mut tags);
+    if kinds.len() != tags.len() {
+        warn!(
+            source = kinds.len(),
+            parsed = tags.len(),
+            "ranged tags of the source don't match the parsed document, no macros are defined"
+        );
+        return;
+    }
+    tags.into_iter()
+        .zip(kinds)
+        .filter(|(_, is_macro)| *is_macro)
+        .for_each(|(name, _)| name.insert(0, DEFINITION_MARK.to_string()));
+}
+
+/// records macro definition, redefining a macro replaces the older one
+pub(super) fn define(
+    name: &[String],
+    parameters: &[String],
+    content: &[norg::NorgASTFlat],
+    ctx: &mut RenderContext,
+) {
+    let name = name.join(".");
+    debug!(%name, ?parameters, "defining macro");
+    ctx.macros.insert(
+        name,
+        Macro {
+            parameters: parameters.to_vec(),
+            content: content.to_vec(),
+        },
+    );
+}
+
+#[instrument(skip(ctx, backend, output))]
+pub(super) fn invoke(
+    name: &[String],
+    arguments: &[String],
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
+    output: &mut String,
+) -> std::fmt::Result {
+    let name = name.join(".");
+    match name.as_str() {
+        "include" => include(arguments, ctx, backend, output),
+        "date" => date(arguments, ctx, backend, output),
+        _ => expand(&name, arguments, ctx, backend, output),
+    }
+}
+
+fn expand(
+    name: &str,
+    arguments: &[String],
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
+    output: &mut String,
+) -> std::fmt::Result {
+    let Some(definition) = ctx.macros.get(name).cloned() else {
+        if STANDARD_INFIRM_TAGS.contains(&name) {
+            warn!(name, "rendering is not implemented for the infirm tag");
+            return Ok(());
+        }
+        warn!(name, "macro is not defined");
+        return backend.error(&format!("undefined macro `{name}`"), output);
+    };
+    if ctx.macro_stack.iter().any(|expanding| expanding == name) {
+        error!(name, stack = ?ctx.macro_stack, "recursive macro invocation");
+        return backend.error(&format!("recursive invocation of macro `{name}`"), output);
+    }
+    if arguments.len() != definition.parameters.len() {
+        warn!(
+            name,
+            expected = definition.parameters.len(),
+            found = arguments.len(),
+            "number of arguments doesn't match macro parameters"
+        );
+    }
+
+    // extra arguments are given to the last parameter, so that text with spaces can be passed
+    let mut bindings: Vec<(String, String)> = definition
+        .parameters
+        .iter()
+        .cloned()
+        .zip(arguments.iter().cloned())
+        .collect();
+    if let (Some(last), Some(extra)) = (
+        bindings.last_mut(),
+        arguments.get(definition.parameters.len()..),
+    ) {
+        extra.iter().for_each(|arg| {
+            last.1.push(' ');
+            last.1.push_str(arg);
+        });
+    }
+
+    trace!(name, ?bindings, "expanding macro");
+    ctx.macro_stack.push(name.to_string());
+    let previous = ctx.bind_variables(bindings);
+    // body of the macro is written at its definition, not at the invocation
+    let result = ctx.without_todo_items(|ctx| {
+        super::render_flat_block(&definition.content, ctx, backend, output)
+    });
+    ctx.restore_variables(previous);
+    ctx.macro_stack.pop();
+    result
+}
+
+/// resolves path of the norg file same as links, `$/` from workspace root, `~/` from home
+/// and others relative to the current document
+fn resolve_path(file: &str, ctx: &RenderContext) -> Option<std::path::PathBuf> {
+    let file = file.trim();
+    let mut path = if let Some(workspace_path) = file.strip_prefix("$/") {
+        ctx.workspace_root.as_ref()?.join(workspace_path)
+    } else if let Some(home_path) = file.strip_prefix("~/") {
+        std::path::PathBuf::from(std::env::var("HOME").ok()?).join(home_path)
+    } else if file.starts_with('/') {
+        std::path::PathBuf::from(file)
+    } else {
+        ctx.document_path
+            .as_ref()
+            .and_then(|document| document.parent())
+            .or(ctx.workspace_root.as_deref())
+            .unwrap_or(std::path::Path::new("."))
+            .join(file)
+    };
+    if path.extension().is_none_or(|ext| ext != "norg") {
+        path.set_extension("norg");
+    }
+    Some(path)
+}
+
+/// file and optional heading which should be included, `{:file:* Heading}` or just `file`
+#[derive(Debug)]
+struct IncludeTarget {
+    file: String,
+    heading: Option<(u16, String)>,
+}
+
+fn parse_include_target(arguments: &[String]) -> Option<IncludeTarget> {
+    let target = arguments.join(" ");
+    let target = target.trim();
+    let Some(link) = target.strip_prefix("{:").and_then(|t| t.strip_suffix('}')) else {
+        return (!target.is_empty()).then(|| IncludeTarget {
+            file: target.to_string(),
+            heading: None,
+        });
+    };
+    let (file, heading) = match link.split_once(':') {
+        Some((file, heading)) => {
+            let heading = heading.trim();
+            let title = heading.trim_start_matches('*');
+            let level = (heading.len() - title.len()) as u16;
+            if level == 0 {
+                warn!(heading, "only headings can be included from a file");
+                return None;
+            }
+            (file, Some((level, title.trim().to_string())))
+        }
+        None => (link, None),
+    };
+    (!file.trim().is_empty()).then(|| IncludeTarget {
+        file: file.trim().to_string(),
+        heading,
+    })
+}
+
+/// titles are compared ignoring case and repeated whitespaces, same as how links are resolved
+fn is_same_title(title: &[norg::ParagraphSegment], expected: &str) -> bool {
+    link::raw_title(title)
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .eq(expected.split_whitespace().map(str::to_lowercase))
+}
+
+/// finds heading with given level and title, along with its content
+fn find_heading(tokens: Vec<norg::NorgAST>, level: u16, title: &str) -> Option<norg::NorgAST> {
+    tokens.into_iter().find_map(|token| match token {
+        norg::NorgAST::Heading {
+            level: heading_level,
+            title: ref heading_title,
+            ..
+        } if heading_level == level && is_same_title(heading_title, title) => Some(token),
+        norg::NorgAST::Heading { content, .. } => find_heading(content, level, title),
+        _ => None,
+    })
+}
+
+/// parsed tokens of the file, taken from the include cache of the context if the file is not modified
+fn parse_included(path: &Path, ctx: &mut RenderContext) -> Result<Vec<norg::NorgAST>, String> {
+    let modified = std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|e| e.to_string())?;
+    let cache = ctx.include_cache.clone();
+    let file = match cache.as_ref().and_then(|cache| cache.get(path, modified)) {
+        Some(file) => {
+            trace!(?path, "included file is cached");
+            file
+        }
+        None => {
+            let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+            let mut tokens = norg::parse_tree(&content).map_err(|e| format!("{e:?}"))?;
+            mark_definitions(&content, &mut tokens);
+            let file = IncludedFile { modified, tokens };
+            if let Some(cache) = &cache {
+                cache.insert(path, file.clone());
+            }
+            file
+        }
+    };
+    Ok(file.tokens)
+}
+
+fn include(
+    arguments: &[String],
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
+    output: &mut String,
+) -> std::fmt::Result {
+    let Some(target) = parse_include_target(arguments) else {
+        warn!(?arguments, "invalid include target");
+        return backend.error(
+            "include requires a file path or `{:file:* heading}`",
+            output,
+        );
+    };
+    let file = target.file.as_str();
+    let Some(path) = resolve_path(file, ctx) else {
+        warn!(file, "couldn't resolve include path");
+        return backend.error(&format!("couldn't resolve `{file}`"), output);
+    };
+    let path = match path.canonicalize() {
+        Ok(p) => p,
+        Err(e) => {
+            warn!(?path, error = %e, "included file is not accessible");
+            return backend.error(&format!("couldn't include `{file}`: {e}"), output);
+        }
+    };
+    if ctx.include_stack.contains(&path) {
+        error!(?path, stack = ?ctx.include_stack, "recursive include");
+        return backend.error(&format!("recursive include of `{file}`"), output);
+    }
+    // changes to the included file should re-render the document even if including fails
+    ctx.add_dependency(&path);
+    let tokens = match parse_included(&path, ctx) {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            warn!(?path, error = %e, "couldn't parse included file");
+            return backend.error(&format!("couldn't include `{file}`: {e}"), output);
+        }
+    };
+    let tokens = match &target.heading {
+        Some((level, title)) => match find_heading(tokens, *level, title) {
+            Some(heading) => vec![heading],
+            None => {
+                warn!(?path, level, %title, "heading not found in included file");
+                return backend.error(&format!("heading `{title}` not found in `{file}`"), output);
+            }
+        },
+        None => tokens,
+    };
+
+    debug!(?path, heading = ?target.heading, "including file");
+    let mut content = String::new();
+    ctx.include_stack.push(path.clone());
+    let previous_document = ctx.document_path.replace(path.clone());
+    let result = ctx.without_todo_items(|ctx| {
+        super::render_ast(
+            &mut tokens.into_iter().peekable(),
+            ctx,
+            backend,
+            &mut content,
+        )
+    });
+    ctx.document_path = previous_document;
+    ctx.include_stack.pop();
+    result?;
+
+    let workspace_path = ctx
+        .workspace_root
+        .as_ref()
+        .and_then(|root| root.canonicalize().ok())
+        .and_then(|root| {
+            path.strip_prefix(root)
+                .ok()
+                .map(std::path::Path::to_path_buf)
+        });
+    let source = IncludeSource {
+        label: file,
+        path: &path,
+        workspace_path: workspace_path.as_deref(),
+        heading: target
+            .heading
+            .as_ref()
+            .map(|(level, title)| (*level, title.as_str())),
+    };
+    backend.include(&source, &content, output)
+}
+
+fn date(
+    arguments: &[String],
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
+    output: &mut String,
+) -> std::fmt::Result {
+    // output changes with the date, so it can't be reused like other renders
+    ctx.uses_date = true;
+    let format = if arguments.is_empty() {
+        "%Y-%m-%d".to_string()
+    } else {
+        arguments.join(" ")
+    };
+    let items: Vec<_> = chrono::format::StrftimeItems::new(&format).collect();
+    if items
+        .iter()
+        .any(|item| matches!(item, chrono::format::Item::Error))
+    {
+        warn!(%format, "invalid date format");
+        return backend.error(&format!("invalid date format `{format}`"), output);
+    }
+    let now = chrono::Local::now();
+    backend.date(
+        &now.format("%Y-%m-%d").to_string(),
+        &now.format_with_items(items.into_iter()).to_string(),
+        output,
+    )
+}
@@ -3,35 +3,42 @@ use std::iter::Peekable;
 use miette::{Context, IntoDiagnostic};
 use tracing::{debug, error, trace, warn};
 
+pub mod backend;
 mod basic;
+mod context;
 mod definition;
 mod extensions;
 mod heading;
 mod link;
 mod list;
+mod macros;
 mod paragraph;
 mod quote;
 mod table;
+pub mod todo;
 mod verbatim;
 
+use backend::{Backend, HtmlBackend};
+pub use context::RenderContext;
+pub(crate) use link::{fragment_id, raw_title};
+pub use macros::IncludeCache;
+pub use verbatim::{DocumentMeta, MetaValue};
+
 fn render_ast<Tokens>(
     tokens: &mut Peekable<Tokens>,
-    footnotes: &mut Vec<(
-        Vec<norg::ParagraphSegment>,
-        Vec<norg::DetachedModifierExtension>,
-        Vec<norg::NorgASTFlat>,
-    )>,
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
     output: &mut String,
 ) -> std::fmt::Result
 where
     Tokens: Iterator<Item = norg::NorgAST>,
 {
     trace!("rendering ast");
-    output.push_str("<div class=\"content_block\">");
+    let mut block = String::new();
     while let Some(token) = tokens.next() {
         match token {
             norg::NorgAST::Paragraph(p) => {
-                paragraph::render_paragraph(&p, output)?;
+                paragraph::render_paragraph(&p, ctx, backend, &mut block)?;
             }
             norg::NorgAST::NestableDetachedModifier {
                 modifier_type,
@@ -40,85 +47,49 @@ where
                 text,
                 content,
             } => {
-                match modifier_type {
+                let kind = match modifier_type {
                     norg::NestableDetachedModifier::Quote => {
-                        quote::render_quote(level, extensions, text, content, output)?;
-                    }
-                    norg::NestableDetachedModifier::UnorderedList => {
-                        output.push_str("<ul>");
-                        list::render_list_element(
-                            text,
-                            content,
-                            level,
-                            list::ListKind::Unordered,
-                            extensions,
-                            output,
-                        )?;
-                        // check if next tokens are also belongs to this list
-                        while let Some(norg::NorgAST::NestableDetachedModifier {
-                            level: n_level,
-                            extensions: n_extensions,
-                            text: n_text,
-                            content: n_content,
-                            ..
-                        }) = tokens.next_if(|t| {
-                            matches!(
-                                t,
-                                norg::NorgAST::NestableDetachedModifier {
-                                    modifier_type: norg::NestableDetachedModifier::UnorderedList,
-                                    ..
-                                }
+                        // TODO items of quotes aren't numbered, see `todo`
+                        ctx.without_todo_items(|ctx| {
+                            quote::render_quote(
+                                level, extensions, text, content, ctx, backend, &mut block,
                             )
-                        }) {
-                            list::render_list_element(
-                                n_text,
-                                n_content,
-                                n_level,
-                                list::ListKind::Unordered,
-                                n_extensions,
-                                output,
-                            )?;
-                        }
-                        output.push_str("</ul>");
+                        })?;
+                        continue;
                     }
-                    norg::NestableDetachedModifier::OrderedList => {
-                        output.push_str("<ol>");
-                        list::render_list_element(
-                            text,
-                            content,
-                            level,
-                            list::ListKind::Ordered,
-                            extensions,
-                            output,
-                        )?;
-                        // check if the next items are also part of list
-                        while let Some(norg::NorgAST::NestableDetachedModifier {
-                            level: n_level,
-                            extensions: n_extensions,
-                            text: n_text,
-                            content: n_content,
-                            ..
-                        }) = tokens.next_if(|t| {
-                            matches!(
-                                t,
-                                norg::NorgAST::NestableDetachedModifier {
-                                    modifier_type: norg::NestableDetachedModifier::OrderedList,
-                                    ..
-                                }
-                            )
-                        }) {
-                            list::render_list_element(
-                                n_text,
-                                n_content,
-                                n_level,
-                                list::ListKind::Ordered,
-                                n_extensions,
-                                output,
-                            )?;
-                        }
-                        output.push_str("</ul>");
-                    } // no need to check if the item is of different type, if it is then it will be flushed at the beginning of the loop
+                    norg::NestableDetachedModifier::UnorderedList => list::ListKind::Unordered,
+                    norg::NestableDetachedModifier::OrderedList => list::ListKind::Ordered,
                 };
+                let mut items = String::new();
+                list::render_list_element(
+                    text, content, level, kind, extensions, ctx, backend, &mut items,
+                )?;
+                // check if next tokens are also belongs to this list
+                while let Some(norg::NorgAST::NestableDetachedModifier {
+                    level: n_level,
+                    extensions: n_extensions,
+                    text: n_text,
+                    content: n_content,
+                    ..
+                }) = tokens.next_if(|t| {
+                    matches!(
+                        t,
+                        norg::NorgAST::NestableDetachedModifier { modifier_type: next_type, .. } if *next_type == modifier_type
+                    )
+                }) {
+                    list::render_list_element(
+                        n_text,
+                        n_content,
+                        n_level,
+                        kind,
+                        n_extensions,
+                        ctx,
+                        backend,
+                        &mut items,
+                    )?;
+                }
+                // no need to check if the item is of different type, if it is then it will be flushed at the beginning of the loop
+                backend.list(kind, &items, &mut block)?;
             }
 
             norg::NorgAST::RangeableDetachedModifier {
@@ -129,8 +100,10 @@ where
             } => {
                 match modifier_type {
                     norg::RangeableDetachedModifier::Definition => {
-                        output.push_str("<dl>");
-                        definition::render_definition(title, extensions, content, output)?;
+                        let mut items = String::new();
+                        definition::render_definition(
+                            title, extensions, content, ctx, backend, &mut items,
+                        )?;
                         // if there are more definitions then add it to the same list
                         // next_if cannot be replaced with next(), if you do that then when let fails to match that token is lost
                         while let Some(norg::NorgAST::RangeableDetachedModifier {
@@ -147,16 +120,36 @@ where
                                 }
                             )
                         }) {
-                            definition::render_definition(title, extensions, content, output)?;
+                            definition::render_definition(
+                                title, extensions, content, ctx, backend, &mut items,
+                            )?;
                         }
 
-                        output.push_str("</dl>");
+                        backend.definition_list(&items, &mut block)?;
                     }
                     norg::RangeableDetachedModifier::Footnote => {
-                        footnotes.push((title, extensions, content));
+                        ctx.footnotes.push((title, extensions, content));
                     }
                     norg::RangeableDetachedModifier::Table => {
-                        table::render_table(title, extensions, content, output);
+                        let mut cells = vec![(title, extensions, content)];
+                        // cells of the same table are consecutive
+                        while let Some(norg::NorgAST::RangeableDetachedModifier {
+                            title,
+                            extensions,
+                            content,
+                            ..
+                        }) = tokens.next_if(|tkn| {
+                            matches!(
+                                tkn,
+                                norg::NorgAST::RangeableDetachedModifier {
+                                    modifier_type: norg::RangeableDetachedModifier::Table,
+                                    ..
+                                }
+                            )
+                        }) {
+                            cells.push((title, extensions, content));
+                        }
+                        table::render_table(cells, ctx, backend, &mut block)?;
                     }
                 };
             }
@@ -166,7 +159,9 @@ where
                 extensions,
                 content,
             } => {
-                heading::render_heading(level, title, extensions, content, footnotes, output)?;
+                heading::render_heading(
+                    level, title, extensions, content, ctx, backend, &mut block,
+                )?;
             }
             //norg::NorgAST::CarryoverTag { tag_type, name, parameters, next_object } => todo!(),
             norg::NorgAST::VerbatimRangedTag {
@@ -175,77 +170,351 @@ where
                 content,
             } => {
                 // rendering code/document tag
-                verbatim::render_paragraph(name, parameters, content, output)?;
+                verbatim::render_paragraph(name, parameters, content, backend, &mut block)?;
+            }
+            norg::NorgAST::RangedTag {
+                name,
+                parameters,
+                content,
+            } => {
+                render_ranged_tag(&name, &parameters, &content, ctx, backend, &mut block)?;
+            }
+            norg::NorgAST::InfirmTag { name, parameters } => {
+                macros::invoke(&name, &parameters, ctx, backend, &mut block)?;
             }
-            //norg::NorgAST::RangedTag { name, parameters, content } => todo!(),
-            //norg::NorgAST::InfirmTag { name, parameters } => todo!(),
             _ => {
                 warn!("Rendering is not implemented for {token:?} item");
             }
         };
     }
-    Ok(())
+    backend.content_block(&block, output)
 }
 
 pub fn parse_and_render_norg(input: &str) -> miette::Result<String> {
+    parse_and_render_norg_with(input, &mut RenderContext::default())
+}
+
+/// renders norg document with given context, this can be used to pass workspace level variables,
+/// after rendering context contains the files included by the document
+pub fn parse_and_render_norg_with(input: &str, ctx: &mut RenderContext) -> miette::Result<String> {
+    render_norg(input, ctx, &mut HtmlBackend)
+}
+
+/// `@document.meta` of the document, all meta tags are merged if there are more than one
+fn collect_meta(tokens: &[norg::NorgAST]) -> Option<DocumentMeta> {
+    let meta_content: Vec<&str> = tokens
+        .iter()
+        .filter_map(|token| match token {
+            norg::NorgAST::VerbatimRangedTag { name, content, .. }
+                if name.iter().map(String::as_str).eq(["document", "meta"]) =>
+            {
+                Some(content.as_str())
+            }
+            _ => None,
+        })
+        .collect();
+    (!meta_content.is_empty()).then(|| DocumentMeta::parse(&meta_content.join("\n")))
+}
+
+/// parses only the `@document.meta` of the document without rendering it
+pub fn document_meta(input: &str) -> miette::Result<DocumentMeta> {
     let tokens = norg::parse_tree(input).map_err(|e| miette::miette!("failed to parse: {e:?}"))?;
+    Ok(collect_meta(&tokens).unwrap_or_default())
+}
+
+/// renders norg document into the output format of the given backend
+pub fn render_norg(
+    input: &str,
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
+) -> miette::Result<String> {
+    let mut tokens =
+        norg::parse_tree(input).map_err(|e| miette::miette!("failed to parse: {e:?}"))?;
     debug!("found tokens: {tokens:#?}");
 
-    let mut footnotes = Vec::new();
+    // document meta can be placed anywhere, so collect the variables before rendering
+    if let Some(meta) = collect_meta(&tokens) {
+        for (key, value) in meta.entries() {
+            ctx.set_variable(key, value);
+        }
+        ctx.meta = meta;
+    }
+
+    macros::mark_definitions(input, &mut tokens);
+
+    // document itself is part of include chain, so that including itself is detected as recursion
+    let include_depth = ctx.include_stack.len();
+    if let Some(document_path) = ctx
+        .document_path
+        .as_ref()
+        .and_then(|p| p.canonicalize().ok())
+    {
+        ctx.include_stack.push(document_path);
+    }
 
     let mut token_iterator = tokens.into_iter().peekable();
 
-    let mut output = String::with_capacity(input.len() * 2);
-    output.push_str("<div class=norg_content>");
+    let mut body = String::with_capacity(input.len() * 2);
+    let rendered = render_ast(&mut token_iterator, ctx, backend, &mut body);
+    ctx.include_stack.truncate(include_depth);
+    rendered
+        .into_diagnostic()
+        .wrap_err("Rendering ast, with ignoring fmt errors")?;
 
-    render_ast(&mut token_iterator, &mut footnotes, &mut output)
+    let mut output = String::with_capacity(body.len());
+    backend
+        .document(&body, &mut output)
         .into_diagnostic()
         .wrap_err("Rendering ast, with ignoring fmt errors")?;
-    output.push_str("</div>");
-
-    if !footnotes.is_empty() {
-        output.push_str("<footer><ol>");
-        footnotes
-            .into_iter()
-            .try_for_each(|(title, extensions, foot_note_paras)| {
-                if !extensions.is_empty() {
-                    warn!(?extensions, "extensions are not yet supported for footer");
-                }
-                let title_string = paragraph::render_segments(&title)?;
-                output.push_str(&format!("<li id=\"{}_footnote\">", title_string));
+
+    let footnotes = std::mem::take(&mut ctx.footnotes)
+        .into_iter()
+        .map(|(title, extensions, foot_note_paras)| {
+            if !extensions.is_empty() {
+                warn!(?extensions, "extensions are not yet supported for footer");
+            }
+            let title = paragraph::render_segments(&title, ctx, backend)?;
+            let mut content = String::new();
+            ctx.without_todo_items(|ctx| {
                 foot_note_paras
-                    .into_iter()
-                    .try_for_each(|fnote| render_flat_ast(&fnote, &mut output))?;
-                // TODO: create a backref for each footnote
-                let backref_tag = format!("#{}_footnote_backref", title_string);
-                output.push_str(&format!(
-                    "<a href=\"{}\" aria-label=\"{}\">↩</a>",
-                    backref_tag, backref_tag
-                ));
-                output.push_str("</li>");
-                std::fmt::Result::Ok(())
-            })
-            .into_diagnostic()
-            .wrap_err("Couldn't add foooter")?;
-        output.push_str("</ol></footer>")
-    }
+                    .iter()
+                    .try_for_each(|fnote| render_flat_ast(fnote, ctx, backend, &mut content))
+            })?;
+            Ok(backend::Footnote { title, content })
+        })
+        .collect::<Result<Vec<_>, std::fmt::Error>>()
+        .and_then(|footnotes| backend.footnotes(&footnotes, &mut output));
+    footnotes
+        .into_diagnostic()
+        .wrap_err("Couldn't add foooter")?;
     debug!(output, "rendered output");
     Ok(output)
 }
 
 /// this currently used only in html list and definitions items so some of the items may not work
-fn render_flat_ast(ast: &norg::NorgASTFlat, output: &mut String) -> std::fmt::Result {
+fn render_flat_ast(
+    ast: &norg::NorgASTFlat,
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
+    output: &mut String,
+) -> std::fmt::Result {
     trace!(?ast, "rendering flat ast");
     match ast {
         norg::NorgASTFlat::Paragraph(paras) => {
             // Create a single paragraph for all content in list items
-            paragraph::render_paragraph(paras, output)?;
+            paragraph::render_paragraph(paras, ctx, backend, output)?;
+        }
+        norg::NorgASTFlat::VerbatimRangedTag {
+            name,
+            parameters,
+            content,
+        } => {
+            // code blocks attached to slides/indent segments
+            verbatim::render_paragraph(
+                name.clone(),
+                parameters.clone(),
+                content.clone(),
+                backend,
+                output,
+            )?;
+        }
+        norg::NorgASTFlat::NestableDetachedModifier { .. } => {
+            render_flat_block(std::slice::from_ref(ast), ctx, backend, output)?;
+        }
+        norg::NorgASTFlat::RangeableDetachedModifier {
+            modifier_type: norg::RangeableDetachedModifier::Definition,
+            ..
+        } => {
+            render_flat_block(std::slice::from_ref(ast), ctx, backend, output)?;
+        }
+        norg::NorgASTFlat::Heading { level, title, .. } => {
+            let title = paragraph::render_segments(title, ctx, backend)?;
+            backend.heading((*level).clamp(1, 6), &title, "", output)?;
+        }
+        norg::NorgASTFlat::RangedTag {
+            name,
+            parameters,
+            content,
+        } => {
+            render_ranged_tag(name, parameters, content, ctx, backend, output)?;
+        }
+        norg::NorgASTFlat::InfirmTag { name, parameters } => {
+            macros::invoke(name, parameters, ctx, backend, output)?;
+        }
+        norg::NorgASTFlat::CarryoverTag { next_object, .. } => {
+            render_flat_ast(next_object, ctx, backend, output)?;
         }
         _ => {
             error!(
-                "Only paragraphs are supported in nested content, raise a issue if it is required"
+                ?ast,
+                "Unsupported item found in nested content, raise a issue if it is required"
             );
         }
     }
     Ok(())
 }
+
+/// renders content of slides and indent segments
+///
+/// consecutive list items and definitions are grouped into a single list, same as [`render_ast`]
+fn render_flat_block(
+    asts: &[norg::NorgASTFlat],
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
+    output: &mut String,
+) -> std::fmt::Result {
+    let mut asts = asts.iter().peekable();
+    while let Some(ast) = asts.next() {
+        match ast {
+            norg::NorgASTFlat::NestableDetachedModifier {
+                modifier_type:
+                    modifier_type @ (norg::NestableDetachedModifier::UnorderedList
+                    | norg::NestableDetachedModifier::OrderedList),
+                ..
+            } => {
+                let kind = match modifier_type {
+                    norg::NestableDetachedModifier::OrderedList => list::ListKind::Ordered,
+                    _ => list::ListKind::Unordered,
+                };
+                let mut items = String::new();
+                let mut item = Some(ast);
+                while let Some(norg::NorgASTFlat::NestableDetachedModifier {
+                    level,
+                    extensions,
+                    content,
+                    ..
+                }) = item
+                {
+                    list::render_flat_list_element(
+                        content, *level, kind, extensions, ctx, backend, &mut items,
+                    )?;
+                    item = asts.next_if(|next| {
+                        matches!(
+                            next,
+                            norg::NorgASTFlat::NestableDetachedModifier { modifier_type: next_type, .. } if next_type == modifier_type
+                        )
+                    });
+                }
+                backend.list(kind, &items, output)?;
+            }
+            norg::NorgASTFlat::NestableDetachedModifier {
+                modifier_type: norg::NestableDetachedModifier::Quote,
+                level,
+                content,
+                ..
+            } => {
+                let mut quote = String::new();
+                ctx.without_todo_items(|ctx| render_flat_ast(content, ctx, backend, &mut quote))?;
+                backend.quote(*level, &quote, output)?;
+            }
+            norg::NorgASTFlat::RangeableDetachedModifier {
+                modifier_type: norg::RangeableDetachedModifier::Definition,
+                ..
+            } => {
+                let mut items = String::new();
+                let mut item = Some(ast);
+                while let Some(norg::NorgASTFlat::RangeableDetachedModifier {
+                    title,
+                    extensions,
+                    content,
+                    ..
+                }) = item
+                {
+                    definition::render_definition(
+                        title.clone(),
+                        extensions.clone(),
+                        content.clone(),
+                        ctx,
+                        backend,
+                        &mut items,
+                    )?;
+                    item = asts.next_if(|next| {
+                        matches!(
+                            next,
+                            norg::NorgASTFlat::RangeableDetachedModifier {
+                                modifier_type: norg::RangeableDetachedModifier::Definition,
+                                ..
+                            }
+                        )
+                    });
+                }
+                backend.definition_list(&items, output)?;
+            }
+            _ => render_flat_ast(ast, ctx, backend, output)?,
+        }
+    }
+    Ok(())
+}
+
+/// standard ranged tags from the spec, their content is rendered in place
+const STANDARD_RANGED_TAGS: [&str; 4] = ["comment", "example", "details", "group"];
+
+fn render_ranged_tag(
+    name: &[String],
+    parameters: &[String],
+    content: &[norg::NorgASTFlat],
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
+    output: &mut String,
+) -> std::fmt::Result {
+    let (is_definition, name) = match name.split_first() {
+        Some((mark, name)) if mark == macros::DEFINITION_MARK => (true, name),
+        _ => (false, name),
+    };
+    match name.first().map(String::as_str) {
+        Some("set") => set_variable(parameters, content, ctx),
+        Some("comment") => trace!("skipping comment"),
+        // TODO items of ranged tags aren't numbered, as macro definitions aren't known from the parsed tags
+        Some(tag) if STANDARD_RANGED_TAGS.contains(&tag) => {
+            ctx.without_todo_items(|ctx| render_flat_block(content, ctx, backend, output))?;
+        }
+        Some(_) if is_definition => macros::define(name, parameters, content, ctx),
+        Some(tag) => {
+            warn!(tag, "ranged tag is not supported, rendering its content");
+            ctx.without_todo_items(|ctx| render_flat_block(content, ctx, backend, output))?;
+        }
+        None => warn!("ranged tag without name found, skipping"),
+    }
+    Ok(())
+}
+
+/// `=set name value =end` defines a variable which can be used with `&name&` in rest of the document,
+/// value can be given either as parameter or as content of the tag
+fn set_variable(parameters: &[String], content: &[norg::NorgASTFlat], ctx: &mut RenderContext) {
+    let Some((name, value_params)) = parameters.split_first() else {
+        warn!("variable name is missing in set tag, skipping");
+        return;
+    };
+    let value = if value_params.is_empty() {
+        content
+            .iter()
+            .filter_map(|ast| match ast {
+                norg::NorgASTFlat::Paragraph(segments) => {
+                    let mut text = String::new();
+                    segments
+                        .iter()
+                        .for_each(|seg| paragraph::write_raw_segment(seg, &mut text));
+                    Some(text)
+                }
+                _ => {
+                    warn!(
+                        ?ast,
+                        "only paragraphs are allowed as variable value, ignoring"
+                    );
+                    None
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        value_params.join(" ")
+    };
+    ctx.set_variable(name.as_str(), value);
+}
+
+/// variable given on the command line as `name=value`
+pub fn parse_variable(arg: &str) -> Result<(String, String), String> {
+    arg.split_once('=')
+        .map(|(name, value)| (name.trim().to_string(), value.to_string()))
+        .ok_or_else(|| format!("invalid variable `{arg}`, expected `name=value`"))
+}
@@ -1,49 +1,159 @@
-use std::fmt::Write;
 use tracing::{trace, warn};
 
-use super::{basic, link};
+use super::{backend::Backend, basic, RenderContext};
 
 pub fn render_segments(
     para_segments: &[norg::ParagraphSegment],
+    ctx: &RenderContext,
+    backend: &mut dyn Backend,
 ) -> Result<String, std::fmt::Error> {
     let mut output = String::new();
-    for segment in para_segments {
-        render_segment(segment, &mut output)?;
-    }
+    render_segment_list(para_segments, ctx, backend, &mut output)?;
     Ok(output)
 }
 
 pub fn render_paragraph(
     para_segments: &[norg::ParagraphSegment],
+    ctx: &RenderContext,
+    backend: &mut dyn Backend,
     output: &mut String,
 ) -> std::fmt::Result {
-    output.push_str("<p>");
-    for segment in para_segments {
-        render_segment(segment, output)?;
+    let content = render_segments(para_segments, ctx, backend)?;
+    backend.paragraph(&content, output)
+}
+
+/// renders consecutive segments, unlike [`render_segment`] this applies attached modifier extensions
+/// i.e. `*text*(color:red)` which are followed by attached modifier
+pub fn render_segment_list(
+    para_segments: &[norg::ParagraphSegment],
+    ctx: &RenderContext,
+    backend: &mut dyn Backend,
+    output: &mut String,
+) -> std::fmt::Result {
+    let mut index = 0;
+    while let Some(segment) = para_segments.get(index) {
+        index += 1;
+        if let norg::ParagraphSegment::AttachedModifier {
+            modifier_type,
+            content,
+        } = segment
+        {
+            if let Some((extension, consumed, remaining_text)) =
+                split_modifier_extension(&para_segments[index..])
+            {
+                trace!(extension, "found attached modifier extension");
+                basic::render_attached(
+                    *modifier_type,
+                    content,
+                    Some(&extension),
+                    ctx,
+                    backend,
+                    output,
+                )?;
+                backend.text(&remaining_text, output)?;
+                index += consumed;
+                continue;
+            }
+        }
+        render_segment(segment, ctx, backend, output)?;
     }
-    output.push_str("</p>");
     Ok(())
 }
 
-pub fn render_segment(para: &norg::ParagraphSegment, output: &mut String) -> std::fmt::Result {
+/// checks if segments start with attached modifier extension i.e `(lang:rust)`
+///
+/// returns content inside brackets, number of segments consumed and text which was part of last consumed segment
+/// but comes after the closing bracket
+fn split_modifier_extension(
+    segments: &[norg::ParagraphSegment],
+) -> Option<(String, usize, String)> {
+    let mut raw = String::new();
+    for (index, segment) in segments.iter().enumerate() {
+        let norg::ParagraphSegment::Token(token) = segment else {
+            return None;
+        };
+        if matches!(token, norg::ParagraphSegmentToken::Whitespace) {
+            return None;
+        }
+        write_raw_token(token, &mut raw);
+        if !raw.starts_with('(') {
+            return None;
+        }
+        if let Some((extension, rest)) = raw[1..].split_once(')') {
+            return Some((extension.to_string(), index + 1, rest.to_string()));
+        }
+    }
+    None
+}
+
+fn write_raw_token(token: &norg::ParagraphSegmentToken, output: &mut String) {
+    match token {
+        norg::ParagraphSegmentToken::Text(text) => output.push_str(text),
+        norg::ParagraphSegmentToken::Whitespace => output.push(' '),
+        norg::ParagraphSegmentToken::Special(c) => output.push(*c),
+        norg::ParagraphSegmentToken::Escape(c) => {
+            output.push('\\');
+            output.push(*c);
+        }
+    }
+}
+
+/// writes segment as it was in the source, used for content which should be preserved verbatim
+pub fn write_raw_segment(segment: &norg::ParagraphSegment, output: &mut String) {
+    match segment {
+        norg::ParagraphSegment::Token(token) => write_raw_token(token, output),
+        norg::ParagraphSegment::AttachedModifier {
+            modifier_type,
+            content,
+        } => {
+            output.push(*modifier_type);
+            content
+                .iter()
+                .for_each(|seg| write_raw_segment(seg, output));
+            output.push(*modifier_type);
+        }
+        norg::ParagraphSegment::InlineVerbatim(tokens) => {
+            output.push('`');
+            tokens
+                .iter()
+                .for_each(|token| write_raw_token(token, output));
+            output.push('`');
+        }
+        norg::ParagraphSegment::Link {
+            description: Some(description),
+            ..
+        } => description
+            .iter()
+            .for_each(|seg| write_raw_segment(seg, output)),
+        _ => {
+            warn!(?segment, "segment can't be written as raw text, skipping");
+        }
+    }
+}
+
+pub fn render_segment(
+    para: &norg::ParagraphSegment,
+    ctx: &RenderContext,
+    backend: &mut dyn Backend,
+    output: &mut String,
+) -> std::fmt::Result {
     trace!(para=?para,"rendering paragraph");
     match para {
         norg::ParagraphSegment::Token(norg::ParagraphSegmentToken::Text(t)) => {
-            let sanitized = crate::html::sanitize_html(t);
-            write!(output, "{}", sanitized)?;
+            backend.text(t, output)?;
         }
         norg::ParagraphSegment::Token(norg::ParagraphSegmentToken::Whitespace) => {
-            write!(output, " ")?;
+            backend.text(" ", output)?;
         }
         norg::ParagraphSegment::Token(norg::ParagraphSegmentToken::Special(chr))
         | norg::ParagraphSegment::Token(norg::ParagraphSegmentToken::Escape(chr)) => {
-            crate::html::write_char_sanitized(*chr, output)
+            backend.text(chr.encode_utf8(&mut [0; 4]), output)?;
         }
         norg::ParagraphSegment::AttachedModifier {
             modifier_type,
             content,
         } => {
-            basic::render_attached(*modifier_type, content, output)?;
+            basic::render_attached(*modifier_type, content, None, ctx, backend, output)?;
         }
         //ParagraphSegment::AttachedModifierOpener(_) => todo!(),
         //ParagraphSegment::AttachedModifierOpenerFail(_) => todo!(),
@@ -58,9 +168,12 @@ pub fn render_segment(para: &norg::ParagraphSegment, output: &mut String) -> std
             targets,
             description,
         } => {
-            write!(output, "<span>")?;
-            link::render_link(filepath.as_deref(), targets, description.as_deref(), output)?;
-            write!(output, "</span>")?;
+            trace!("rendering link");
+            let description = description
+                .as_deref()
+                .map(|description| render_segments(description, ctx, backend))
+                .transpose()?;
+            backend.link(filepath.as_deref(), targets, description.as_deref(), output)?;
         }
         norg::ParagraphSegment::InlineVerbatim(tokens) => {
             let mut rendered_code = String::with_capacity(tokens.len());
@@ -72,8 +185,7 @@ pub fn render_segment(para: &norg::ParagraphSegment, output: &mut String) -> std
                     norg::ParagraphSegmentToken::Escape(c) => rendered_code.push(*c),
                 }
             }
-            let sanitized = crate::html::sanitize_html(&rendered_code);
-            write!(output, "<code>{}</code>", sanitized)?;
+            backend.inline_code(&rendered_code, output)?;
         }
         _ => {
             warn!("rendering para segment {para:?} is not yet implemented");
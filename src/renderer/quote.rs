@@ -1,13 +1,15 @@
 //! module which does rendering of quotes
-use std::fmt::Write;
+use super::{backend::Backend, RenderContext};
 use tracing::{error, instrument, trace, warn};
 
-#[instrument(skip(inner_quotes, output))]
+#[instrument(skip(inner_quotes, ctx, backend, output))]
 pub fn render_quote(
     level: u16,
     extensions: Vec<norg::DetachedModifierExtension>,
     text: Box<norg::NorgASTFlat>,
     inner_quotes: Vec<norg::NorgAST>,
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
     output: &mut String,
 ) -> std::fmt::Result {
     trace!("rendering quote");
@@ -15,8 +17,8 @@ pub fn render_quote(
         warn!("Quote has extensions which is not supposed be, if things have changed, then raise issue to fix this");
     }
 
-    write!(output, "<blockquote>").unwrap();
-    super::render_flat_ast(&text, output)?;
+    let mut content = String::new();
+    super::render_flat_ast(&text, ctx, backend, &mut content)?;
 
     for inner_quote in inner_quotes {
         // only quotes are allowed in quotes,
@@ -34,12 +36,14 @@ pub fn render_quote(
                 inner_extensions,
                 inner_text,
                 inner_content,
-                output,
+                ctx,
+                backend,
+                &mut content,
             )?;
         } else {
             error!(tokens=?inner_quote, "Unexpected tokens found in quotes, only quotes are allowed")
         }
     }
 
-    write!(output, "</blockquote>")
+    backend.quote(level, &content, output)
 }
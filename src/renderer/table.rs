@@ -1,12 +1,116 @@
+//! tables made of `: A1` cells, cells are placed by their position and missing cells are left empty
+use std::collections::BTreeMap;
+
 use tracing::{debug, instrument, warn};
 
-#[instrument(skip(content, extensions, _output))]
+use super::{backend::Backend, link, paragraph, RenderContext};
+
+/// position, extensions and content of a cell
+pub type Cell = (
+    Vec<norg::ParagraphSegment>,
+    Vec<norg::DetachedModifierExtension>,
+    Vec<norg::NorgASTFlat>,
+);
+
+/// number of columns of the largest table, same as spreadsheets where the last column is `XFD`
+const MAX_COLUMNS: usize = 16_384;
+/// number of rows of the largest table, same as spreadsheets
+const MAX_ROWS: usize = 1_048_576;
+/// number of cells of the rendered table including the empty ones, columns of a table with only a
+/// few cells far to the right are skipped rather than filling every row up to them
+const MAX_CELLS: usize = 65_536;
+
+/// parses spreadsheet like position `B3` into zero based column and one based row,
+/// positions outside of [`MAX_COLUMNS`] and [`MAX_ROWS`] are invalid
+fn cell_position(position: &str) -> Option<(usize, usize)> {
+    let position = position.trim();
+    let split = position.find(|c: char| !c.is_ascii_alphabetic())?;
+    let (column, row) = position.split_at(split);
+    if column.is_empty() {
+        return None;
+    }
+    let column = column
+        .chars()
+        .try_fold(0usize, |column, c| {
+            column
+                .checked_mul(26)?
+                .checked_add(c.to_ascii_uppercase() as usize - 'A' as usize + 1)
+        })
+        .filter(|column| *column <= MAX_COLUMNS)?;
+    let row = row
+        .parse()
+        .ok()
+        .filter(|row| (1..=MAX_ROWS).contains(row))?;
+    Some((column - 1, row))
+}
+
+#[instrument(skip_all)]
 pub fn render_table(
-    title: Vec<norg::ParagraphSegment>,
-    extensions: Vec<norg::DetachedModifierExtension>,
-    content: Vec<norg::NorgASTFlat>,
-    _output: &mut String,
-) {
-    warn!("rendering table is not implemented");
-    debug!("content of table: {content:?} and extension: {extensions:?}");
+    cells: Vec<Cell>,
+    ctx: &mut RenderContext,
+    backend: &mut dyn Backend,
+    output: &mut String,
+) -> std::fmt::Result {
+    let mut rows: BTreeMap<usize, BTreeMap<usize, String>> = BTreeMap::new();
+    let mut previous = None;
+    for (title, extensions, content) in cells {
+        if !extensions.is_empty() {
+            warn!(?extensions, "extensions are not supported for table cells");
+        }
+        let position = link::raw_title(&title);
+        let Some((column, row)) = cell_position(&position).or_else(|| {
+            // TODO: support relative motions like `>` and `_`
+            warn!(
+                position,
+                "unsupported cell position, placing it after previous cell"
+            );
+            match previous {
+                Some((column, row)) => (column + 1 < MAX_COLUMNS).then_some((column + 1, row)),
+                None => Some((0, 1)),
+            }
+        }) else {
+            warn!(position, "table is too wide, skipping the cell");
+            continue;
+        };
+        debug!(position, column, row, "rendering table cell");
+        previous = Some((column, row));
+
+        let mut rendered = String::new();
+        // TODO items of cells aren't numbered, as skipped cells aren't rendered
+        ctx.without_todo_items(|ctx| match content.as_slice() {
+            // single paragraph cells are kept inline
+            [norg::NorgASTFlat::Paragraph(paras)] => {
+                paragraph::render_segment_list(paras, ctx, backend, &mut rendered)
+            }
+            blocks => super::render_flat_block(blocks, ctx, backend, &mut rendered),
+        })?;
+
+        rows.entry(row).or_default().insert(column, rendered);
+    }
+
+    let columns = rows
+        .values()
+        .filter_map(|row| row.last_key_value())
+        .map(|(column, _)| column + 1)
+        .max()
+        .unwrap_or_default();
+    // every row has at least one cell, so at least one column is always rendered
+    let columns = columns.min((MAX_CELLS / rows.len().max(1)).max(1));
+    let rows: Vec<Vec<String>> = rows
+        .into_values()
+        .map(|mut row| {
+            let skipped = row.split_off(&columns);
+            if !skipped.is_empty() {
+                warn!(
+                    columns,
+                    skipped = skipped.len(),
+                    "table has too many cells, skipping the cells of later columns"
+                );
+            }
+            (0..columns)
+                .map(|column| row.remove(&column).unwrap_or_default())
+                .collect()
+        })
+        .collect();
+    backend.table(&rows, output)
 }
@@ -0,0 +1,228 @@
+//! changing the TODO status of list items in the source of a document
+//!
+//! the parser doesn't keep positions, so the renderer numbers the TODO list items of the document in
+//! the order they are rendered, and the status of an item is searched in the `( )` extensions of the
+//! list lines of the source. the source is only changed when it parses to the same document with only
+//! the status of that item changed.
+//!
+//! items of footnotes, tables, quotes and ranged tags aren't numbered, as they aren't rendered in
+//! document order, neither are the items of included files and expanded macros
+use std::ops::Range;
+
+use tracing::{debug, trace};
+
+/// result of changing the status of a TODO item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TodoChange {
+    /// item already has the status, the source doesn't need to be written
+    Unchanged,
+    /// source with the status of the item changed
+    Changed(String),
+}
+
+/// character of the TODO status written in the source, statuses are named as the `data-status` of the html output
+pub fn todo_marker(status: &str) -> Option<char> {
+    match status {
+        "undone" => Some(' '),
+        "done" => Some('x'),
+        "needs-clarification" => Some('?'),
+        "paused" => Some('='),
+        "urgent" => Some('!'),
+        "recurring" => Some('+'),
+        "pending" => Some('-'),
+        "canceled" => Some('_'),
+        _ => None,
+    }
+}
+
+/// character of the parsed TODO status, same as [`todo_marker`]
+fn status_marker(status: &norg::TodoStatus) -> char {
+    match status {
+        norg::TodoStatus::Undone => ' ',
+        norg::TodoStatus::Done => 'x',
+        norg::TodoStatus::NeedsClarification => '?',
+        norg::TodoStatus::Paused => '=',
+        norg::TodoStatus::Urgent => '!',
+        norg::TodoStatus::Recurring(_) => '+',
+        norg::TodoStatus::Pending => '-',
+        norg::TodoStatus::Canceled => '_',
+    }
+}
+
+/// whether the item is numbered by the renderer, the status of an item is its first TODO extension
+pub(super) fn has_todo(extensions: &[norg::DetachedModifierExtension]) -> bool {
+    extensions
+        .iter()
+        .any(|extension| matches!(extension, norg::DetachedModifierExtension::Todo(_)))
+}
+
+fn first_todo(extensions: &mut [norg::DetachedModifierExtension]) -> Option<&mut norg::TodoStatus> {
+    extensions.iter_mut().find_map(|extension| match extension {
+        norg::DetachedModifierExtension::Todo(status) => Some(status),
+        _ => None,
+    })
+}
+
+fn is_list(modifier_type: &norg::NestableDetachedModifier) -> bool {
+    matches!(
+        modifier_type,
+        norg::NestableDetachedModifier::UnorderedList | norg::NestableDetachedModifier::OrderedList
+    )
+}
+
+/// statuses of the numbered items in the order they are rendered
+fn collect_todos<'a>(token: &'a mut norg::NorgAST, todos: &mut Vec<&'a mut norg::TodoStatus>) {
+    match token {
+        norg::NorgAST::Heading { content, .. } => content
+            .iter_mut()
+            .for_each(|token| collect_todos(token, todos)),
+        norg::NorgAST::NestableDetachedModifier {
+            modifier_type,
+            extensions,
+            text,
+            content,
+            ..
+        } if is_list(modifier_type) => {
+            todos.extend(first_todo(extensions));
+            collect_flat_todos(text, todos);
+            content
+                .iter_mut()
+                .for_each(|token| collect_todos(token, todos));
+        }
+        norg::NorgAST::RangeableDetachedModifier {
+            modifier_type: norg::RangeableDetachedModifier::Definition,
+            content,
+            ..
+        } => content
+            .iter_mut()
+            .for_each(|token| collect_flat_todos(token, todos)),
+        _ => {}
+    }
+}
+
+fn collect_flat_todos<'a>(
+    token: &'a mut norg::NorgASTFlat,
+    todos: &mut Vec<&'a mut norg::TodoStatus>,
+) {
+    match token {
+        norg::NorgASTFlat::NestableDetachedModifier {
+            modifier_type,
+            extensions,
+            content,
+            ..
+        } if is_list(modifier_type) => {
+            todos.extend(first_todo(extensions));
+            collect_flat_todos(content, todos);
+        }
+        norg::NorgASTFlat::RangeableDetachedModifier {
+            modifier_type: norg::RangeableDetachedModifier::Definition,
+            content,
+            ..
+        } => content
+            .iter_mut()
+            .for_each(|token| collect_flat_todos(token, todos)),
+        norg::NorgASTFlat::CarryoverTag { next_object, .. } => {
+            collect_flat_todos(next_object, todos)
+        }
+        _ => {}
+    }
+}
+
+fn todos(tokens: &mut [norg::NorgAST]) -> Vec<&mut norg::TodoStatus> {
+    let mut todos = Vec::new();
+    tokens
+        .iter_mut()
+        .for_each(|token| collect_todos(token, &mut todos));
+    todos
+}
+
+/// bytes of the TODO status in the extensions `( |# A)` of a list line, like `x` of `- (x) text`
+fn todo_range(line: &str) -> Option<Range<usize>> {
+    let text = line.trim_start();
+    let modifier = text.chars().next().filter(|c| matches!(c, '-' | '~'))?;
+    let rest = text.trim_start_matches(modifier);
+    let extensions = rest.trim_start_matches([' ', '\t']);
+    if extensions.len() == rest.len() {
+        return None;
+    }
+    let mut start = line.len() - extensions.len() + 1;
+    let extensions = extensions.strip_prefix('(')?;
+    for extension in extensions[..extensions.find(')')?].split('|') {
+        let range = start..start + extension.len();
+        start = range.end + 1;
+        let trimmed = extension.trim();
+        let offset = range.start + extension.len() - extension.trim_start().len();
+        match trimmed.chars().next() {
+            // undone is written as a space, `( )`
+            None if !extension.is_empty() => return Some(range),
+            Some('+') => return Some(offset..offset + trimmed.len()),
+            Some('x' | '?' | '!' | '-' | '=' | '_') if trimmed.len() == 1 => {
+                return Some(offset..offset + 1)
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// bytes of the TODO statuses written in the list lines of the source
+fn todo_ranges(input: &str) -> Vec<Range<usize>> {
+    let mut offset = 0;
+    input
+        .split_inclusive('\n')
+        .filter_map(|line| {
+            let start = offset;
+            offset += line.len();
+            todo_range(line.trim_end_matches(['\n', '\r']))
+                .map(|todo| start + todo.start..start + todo.end)
+        })
+        .collect()
+}
+
+/// source with the status of the numbered TODO item changed to `status`
+///
+/// `None` if there isn't such item or its status couldn't be found in the source, an item like
+/// `(+ Thursday)` is unchanged when it is set to recurring again. candidate statuses of the source are tried from
+/// the one with the same number, and a candidate is only changed if the parsed document stays the same
+/// apart from the status of the item.
+pub fn set_todo_status(input: &str, index: usize, status: &str) -> Option<TodoChange> {
+    let marker = todo_marker(status)?;
+    let mut tokens = norg::parse_tree(input).ok()?;
+    let original = (**todos(&mut tokens).get(index)?).clone();
+    if status_marker(&original) == marker {
+        return Some(TodoChange::Unchanged);
+    }
+    let document = crate::json::tree_to_json(&tokens);
+    let ranges = todo_ranges(input);
+    let candidates = ranges.get(index).into_iter().chain(
+        ranges
+            .iter()
+            .enumerate()
+            .filter(|(candidate, _)| *candidate != index)
+            .map(|(_, range)| range),
+    );
+    for range in candidates {
+        let mut output = String::with_capacity(input.len());
+        output.push_str(&input[..range.start]);
+        output.push(marker);
+        output.push_str(&input[range.end..]);
+        let Ok(mut changed) = norg::parse_tree(&output) else {
+            continue;
+        };
+        // the changed item should have the status, and be the same document with the previous one back
+        let matches = todos(&mut changed)
+            .into_iter()
+            .nth(index)
+            .is_some_and(|todo| {
+                let changed_marker = status_marker(todo);
+                *todo = original.clone();
+                changed_marker == marker
+            });
+        if matches && crate::json::tree_to_json(&changed) == document {
+            trace!(index, ?range, "found TODO status in the source");
+            return Some(TodoChange::Changed(output));
+        }
+    }
+    debug!(index, "TODO status isn't found in the source");
+    None
+}
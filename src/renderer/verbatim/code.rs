@@ -1,14 +1,16 @@
-use std::fmt::Write;
-use tracing::{debug, instrument, warn};
+use tracing::{debug, instrument};
 
-#[instrument(skip(content, target))]
+use crate::renderer::backend::Backend;
+
+#[instrument(skip(content, backend, target))]
 pub fn render_code<'n, 'd>(
     _name: &'n [String],
     params: Vec<String>,
     content: String,
+    backend: &mut dyn Backend,
     target: &mut String,
 ) -> std::fmt::Result {
-    let language = params.first().map_or("text", |l| l.as_str());
-    debug!("found language: {language}");
-    write!(target, "<pre class={language}>{content}</pre>")
+    let language = params.first().map(String::as_str);
+    debug!(?language, "found language");
+    backend.code_block(language, &content, target)
 }
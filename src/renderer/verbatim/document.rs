@@ -1,56 +1,207 @@
 //! show page document in the top of the page
 
-use std::fmt::Write;
 use tracing::{instrument, trace, warn};
 
-fn parse_document(s: &str, output: &mut String) -> std::fmt::Result {
-    for line in s.lines() {
-        if let Some((key, value)) = line.trim().split_once(':') {
-            match key.trim() {
-                "title" => {
-                    trace!(value, "title of the document");
-                    write!(output, "<div>Title: {}</div>", value.trim())?;
+use crate::renderer::backend::Backend;
+
+/// value of a document meta key, `[ ]` are lists and `{ }` are nested keys
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetaValue {
+    Text(String),
+    List(Vec<String>),
+    Object(Vec<(String, MetaValue)>),
+}
+
+impl std::fmt::Display for MetaValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text(text) => f.write_str(text),
+            Self::List(items) => f.write_str(&items.join(", ")),
+            Self::Object(entries) => {
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str("; ")?;
+                    }
+                    write!(f, "{key}: {value}")?;
                 }
-                "description" => {
-                    trace!(value, "description of the document");
-                    write!(output, "<div>Description: {}</div>", value.trim())?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// parsed `@document.meta`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentMeta {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub authors: Vec<String>,
+    pub categories: Vec<String>,
+    pub created: Option<String>,
+    pub updated: Option<String>,
+    pub version: Option<String>,
+    /// keys without a field of their own, in the order they are written
+    pub extra: Vec<(String, MetaValue)>,
+}
+
+/// items of a list written in single line, separated by commas or by whitespace if there are no commas
+fn split_items(items: &str) -> Vec<String> {
+    let items: Vec<&str> = if items.contains(',') {
+        items.split(',').collect()
+    } else {
+        items.split_whitespace().collect()
+    };
+    items
+        .into_iter()
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn parse_value(value: &str, lines: &mut std::str::Lines) -> MetaValue {
+    let value = value.trim();
+    if let Some(rest) = value.strip_prefix('[') {
+        if let Some(items) = rest.trim_end().strip_suffix(']') {
+            return MetaValue::List(split_items(items));
+        }
+        // items of multi line lists are written one per line, so they can contain spaces
+        let mut items = split_items(rest);
+        for line in lines.by_ref() {
+            let line = line.trim();
+            if let Some(last) = line.strip_suffix(']') {
+                items.extend(split_items(last));
+                break;
+            }
+            if !line.is_empty() {
+                items.push(line.to_string());
+            }
+        }
+        MetaValue::List(items)
+    } else if value == "{" {
+        MetaValue::Object(parse_entries(lines, true))
+    } else {
+        MetaValue::Text(value.to_string())
+    }
+}
+
+/// key and value of a `key: value` line, keys are identifiers followed by `:` and a whitespace
+/// or the end of the line, so that text like `https://example.com` isn't taken as a key
+fn entry(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once(':')?;
+    let mut chars = key.chars();
+    let is_key = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+    (is_key && (value.is_empty() || value.starts_with(char::is_whitespace))).then_some((key, value))
+}
+
+/// `key: value` pairs, lines without a key continue the value of the previous key
+///
+/// lines indented more than the first key are always continuation lines
+fn parse_entries(lines: &mut std::str::Lines, nested: bool) -> Vec<(String, MetaValue)> {
+    let mut entries: Vec<(String, MetaValue)> = Vec::new();
+    let mut indent = None;
+    while let Some(raw_line) = lines.next() {
+        let line = raw_line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if nested && line == "}" {
+            break;
+        }
+        let line_indent = raw_line.len() - raw_line.trim_start().len();
+        match entry(line).filter(|_| indent.is_none_or(|indent| line_indent <= indent)) {
+            Some((key, value)) => {
+                indent.get_or_insert(line_indent);
+                let value = parse_value(value, lines);
+                entries.push((key.to_string(), value));
+            }
+            _ => match entries.last_mut() {
+                Some((_, MetaValue::Text(text))) => {
+                    if !text.is_empty() {
+                        text.push('\n');
+                    }
+                    text.push_str(line);
                 }
+                _ => warn!(line, "document meta line without a key, ignoring"),
+            },
+        }
+    }
+    entries
+}
+
+/// text of the value, `None` if it is empty
+fn text(value: MetaValue) -> Option<String> {
+    Some(value.to_string()).filter(|text| !text.is_empty())
+}
+
+impl DocumentMeta {
+    pub fn parse(content: &str) -> Self {
+        let mut meta = Self::default();
+        for (key, value) in parse_entries(&mut content.lines(), false) {
+            match key.as_str() {
+                "title" => meta.title = text(value),
+                "description" => meta.description = text(value),
+                // single line authors are separated with commas, as names contain spaces
                 "authors" => {
-                    trace!(value, "authors of the document");
-                    write!(output, "<div>👤 {}</div>", value.trim())?;
+                    meta.authors = match value {
+                        MetaValue::List(authors) => authors,
+                        other => other
+                            .to_string()
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|author| !author.is_empty())
+                            .map(String::from)
+                            .collect(),
+                    }
                 }
                 "categories" => {
-                    trace!(value, "categories of the document");
-                    write!(output, "<div>📂 {}</div>", value.trim())?;
-                }
-                "created" => {
-                    trace!(value, "created of the document");
-                    write!(output, "<div>🕒 {}</div>", value.trim())?;
-                }
-                "updated" => {
-                    trace!(value, "updated of the document");
-                    write!(output, "<div>Last Updated {}</div>", value.trim())?;
-                }
-                "version" => {
-                    trace!(value, "version of the document");
-                    write!(output, "<div>📌 {}</div>", value.trim())?;
-                }
-                _ => {
-                    warn!(key, "Unknown key while parsing document meta, ignoring");
+                    meta.categories = match value {
+                        MetaValue::List(categories) => categories,
+                        other => split_items(&other.to_string()),
+                    }
                 }
+                "created" => meta.created = text(value),
+                "updated" => meta.updated = text(value),
+                "version" => meta.version = text(value),
+                _ => meta.extra.push((key, value)),
             }
-        } else {
-            warn!(line, "Ignoring the line, since there is no `:`");
         }
+        meta
+    }
+
+    /// every non empty key with its value as text, lists are joined with `, `
+    pub fn entries(&self) -> Vec<(&str, String)> {
+        let list = |items: &[String]| Some(items.join(", ")).filter(|items| !items.is_empty());
+        let mut entries: Vec<(&str, String)> = [
+            ("title", self.title.clone()),
+            ("description", self.description.clone()),
+            ("authors", list(&self.authors)),
+            ("categories", list(&self.categories)),
+            ("created", self.created.clone()),
+            ("updated", self.updated.clone()),
+            ("version", self.version.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value?)))
+        .collect();
+        entries.extend(
+            self.extra
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.to_string())),
+        );
+        entries
     }
-    Ok(())
 }
 
-#[instrument(skip(content, output))]
+#[instrument(skip(content, backend, output))]
 pub fn render_document(
     name: &[String],
     params: Vec<String>,
     content: String,
+    backend: &mut dyn Backend,
     output: &mut String,
 ) -> std::fmt::Result {
     trace!("rendering document");
@@ -59,9 +210,9 @@ pub fn render_document(
             if !params.is_empty() {
                 warn!("not sure what to do with params")
             };
-            write!(output, "<div class=\"metadata\">")?;
-            parse_document(&content, output)?;
-            write!(output, "</div>")?;
+            let meta = DocumentMeta::parse(&content);
+            trace!(?meta, "document meta");
+            backend.document_meta(&meta, output)?;
         }
         None => {
             warn!(content, "Missing document type, skipping");
@@ -1,24 +1,33 @@
 use tracing::{instrument, trace, warn};
 
+use super::backend::Backend;
+
 mod code;
 mod document;
 
-#[instrument(skip(params, content, target))]
+pub use document::{DocumentMeta, MetaValue};
+
+#[instrument(skip(params, content, backend, target))]
 pub fn render_paragraph(
     name: Vec<String>,
     params: Vec<String>,
     content: String,
+    backend: &mut dyn Backend,
     target: &mut String,
 ) -> std::fmt::Result {
     if let Some((first_name, name_etc)) = name.split_first() {
         match first_name.as_str() {
             "code" => {
                 trace!("rendering code block");
-                code::render_code(name_etc, params, content, target)?;
+                code::render_code(name_etc, params, content, backend, target)?;
+            }
+            "math" => {
+                trace!("rendering math block");
+                backend.math_block(&content, target)?;
             }
             "document" => {
                 trace!("rendering document");
-                document::render_document(name_etc, params, content, target)?;
+                document::render_document(name_etc, params, content, backend, target)?;
             }
             _ => {
                 warn!("unknown tag: {first_name}, so just pushing the content as it is");
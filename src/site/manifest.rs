@@ -0,0 +1,240 @@
+//! record of the previous static build, so that only changed pages are rendered again
+//!
+//! manifest is stored as JSON in the output directory and is ignored if it can't be read,
+//! which results in a full build
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+use miette::{Context, IntoDiagnostic};
+use serde_json::{json, Value};
+use tracing::{debug, warn};
+
+/// name of the manifest file in the output directory
+pub const MANIFEST_FILE: &str = ".norgmill-manifest.json";
+
+/// manifests written with another version are ignored
+const MANIFEST_VERSION: u64 = 3;
+
+/// FNV-1a hash of the content, unlike `DefaultHasher` it is same across builds of norgmill
+pub fn content_hash(content: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in content {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{hash:016x}")
+}
+
+/// current date as it's recorded for pages showing it
+pub fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+/// hashes of the files read in the current build, missing files have an empty hash
+#[derive(Debug, Default)]
+pub struct Hashes(HashMap<PathBuf, String>);
+
+impl Hashes {
+    pub fn get(&mut self, path: &Path) -> String {
+        self.0
+            .entry(path.to_path_buf())
+            .or_insert_with(|| {
+                std::fs::read(path)
+                    .map(|content| content_hash(&content))
+                    .unwrap_or_default()
+            })
+            .clone()
+    }
+}
+
+/// source file of the workspace and everything that was generated from it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Entry {
+    /// hash of the source content
+    pub hash: String,
+    /// hash of the navigation links and breadcrumbs of the page, they change when other documents
+    /// are added or removed or when titles of the parent indexes change
+    pub navigation: String,
+    /// generated files relative to the output directory
+    pub outputs: Vec<PathBuf>,
+    /// included files with their hash at the time of rendering
+    pub dependencies: BTreeMap<PathBuf, String>,
+    /// linked workspace files and whether they existed at the time of rendering,
+    /// links are rewritten differently for missing files
+    pub links: BTreeMap<PathBuf, bool>,
+    /// meta title of norg files, kept so that category pages don't need to read unchanged files
+    pub title: Option<String>,
+    /// meta categories of norg files
+    pub categories: Vec<String>,
+    /// date the page was rendered on if it shows the current date with `.date`,
+    /// such pages are rendered again on other days
+    pub date: Option<String>,
+}
+
+impl Entry {
+    /// outputs are up to date if neither the source nor anything it depends on has changed
+    pub fn is_fresh(
+        &self,
+        hash: &str,
+        navigation: &str,
+        output_dir: &Path,
+        hashes: &mut Hashes,
+    ) -> bool {
+        self.hash == hash
+            && self.navigation == navigation
+            && self
+                .outputs
+                .iter()
+                .all(|output| output_dir.join(output).is_file())
+            && self
+                .dependencies
+                .iter()
+                .all(|(dependency, hash)| hashes.get(dependency) == *hash)
+            && self
+                .links
+                .iter()
+                .all(|(link, exists)| link.is_file() == *exists)
+            && self.date.as_ref().is_none_or(|date| *date == today())
+    }
+
+    fn to_json(&self) -> Value {
+        let dependencies: serde_json::Map<String, Value> = self
+            .dependencies
+            .iter()
+            .map(|(path, hash)| (path_string(path), json!(hash)))
+            .collect();
+        let links: serde_json::Map<String, Value> = self
+            .links
+            .iter()
+            .map(|(path, exists)| (path_string(path), json!(exists)))
+            .collect();
+        json!({
+            "hash": self.hash,
+            "navigation": self.navigation,
+            "outputs": self.outputs.iter().map(|path| path_string(path)).collect::<Vec<_>>(),
+            "dependencies": dependencies,
+            "links": links,
+            "title": self.title,
+            "categories": self.categories,
+            "date": self.date,
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        let paths = |key: &str| -> Option<Vec<PathBuf>> {
+            value
+                .get(key)?
+                .as_array()?
+                .iter()
+                .map(|path| path.as_str().map(PathBuf::from))
+                .collect()
+        };
+        Some(Self {
+            hash: value.get("hash")?.as_str()?.to_string(),
+            navigation: value.get("navigation")?.as_str()?.to_string(),
+            outputs: paths("outputs")?,
+            dependencies: value
+                .get("dependencies")?
+                .as_object()?
+                .iter()
+                .map(|(path, hash)| Some((PathBuf::from(path), hash.as_str()?.to_string())))
+                .collect::<Option<_>>()?,
+            links: value
+                .get("links")?
+                .as_object()?
+                .iter()
+                .map(|(path, exists)| Some((PathBuf::from(path), exists.as_bool()?)))
+                .collect::<Option<_>>()?,
+            title: match value.get("title")? {
+                Value::Null => None,
+                title => Some(title.as_str()?.to_string()),
+            },
+            categories: value
+                .get("categories")?
+                .as_array()?
+                .iter()
+                .map(|category| category.as_str().map(String::from))
+                .collect::<Option<_>>()?,
+            date: match value.get("date")? {
+                Value::Null => None,
+                date => Some(date.as_str()?.to_string()),
+            },
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+    /// hash of the build configuration, every page is rendered again when it changes
+    pub config: String,
+    /// entries by source path relative to the workspace root
+    pub files: BTreeMap<PathBuf, Entry>,
+    /// pages generated without a source like directory indexes and feeds, relative to the output directory
+    pub generated: Vec<PathBuf>,
+}
+
+impl Manifest {
+    /// manifest of the previous build, or an empty one if there is none
+    pub fn load(output_dir: &Path) -> Self {
+        let path = output_dir.join(MANIFEST_FILE);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            debug!(?path, "no manifest found, building everything");
+            return Self::default();
+        };
+        match serde_json::from_str(&content)
+            .ok()
+            .and_then(|value| Self::from_json(&value))
+        {
+            Some(manifest) => manifest,
+            None => {
+                warn!(?path, "invalid manifest, building everything");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, output_dir: &Path) -> miette::Result<()> {
+        let path = output_dir.join(MANIFEST_FILE);
+        let files: serde_json::Map<String, Value> = self
+            .files
+            .iter()
+            .map(|(path, entry)| (path_string(path), entry.to_json()))
+            .collect();
+        let manifest = json!({
+            "version": MANIFEST_VERSION,
+            "config": self.config,
+            "files": files,
+            "generated": self.generated.iter().map(|path| path_string(path)).collect::<Vec<_>>(),
+        });
+        std::fs::write(&path, format!("{manifest:#}"))
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Couldn't write manifest {}", path.display()))
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        if value.get("version")?.as_u64()? != MANIFEST_VERSION {
+            return None;
+        }
+        Some(Self {
+            config: value.get("config")?.as_str()?.to_string(),
+            files: value
+                .get("files")?
+                .as_object()?
+                .iter()
+                .map(|(path, entry)| Some((PathBuf::from(path), Entry::from_json(entry)?)))
+                .collect::<Option<_>>()?,
+            generated: value
+                .get("generated")?
+                .as_array()?
+                .iter()
+                .map(|path| path.as_str().map(PathBuf::from))
+                .collect::<Option<_>>()?,
+        })
+    }
+}
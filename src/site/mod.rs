@@ -0,0 +1,715 @@
+//! full html pages and static site export of a workspace
+//!
+//! server and static site share the same page template, only links to the assets and workspace differ
+
+mod manifest;
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use miette::{Context, IntoDiagnostic};
+use tracing::{debug, info, instrument, trace};
+
+use crate::{
+    categories::{self, CategoryIndex},
+    constants,
+    export::{is_norg_file, relative_output_dir, workspace_files},
+    feed,
+    html::{percent_decode, percent_encode, sanitize_html, unescape_html},
+    navigation::{self, Navigation},
+    renderer::{self, backend::HtmlBackend, RenderContext},
+};
+pub use manifest::{content_hash, MANIFEST_FILE};
+use manifest::{Entry, Hashes, Manifest};
+
+pub const STYLE: &str = include_str!("../../assets/style.css");
+pub const SCRIPTS: &str = include_str!("../../assets/scripts.js");
+pub const FAVICON: &str = include_str!("../../assets/neorg.svg");
+
+/// directory in the static site where styles and scripts are written
+const STATIC_DIR: &str = "static";
+
+/// directory in the static site where category pages are written
+const CATEGORIES_DIR: &str = "categories";
+
+/// directory in the static site where atom feeds are written
+const FEEDS_DIR: &str = "feeds";
+
+/// links which differ between the server and the static site
+#[derive(Debug, Clone)]
+pub struct PageLinks<'a> {
+    /// index of the workspace
+    pub home: &'a str,
+    /// styles and scripts added in head, either inline or linked
+    pub styles_and_scripts: &'a str,
+    pub favicon: &'a str,
+    /// norg source of the page
+    pub raw: &'a str,
+    /// index of the parent directory
+    pub up: Option<&'a str>,
+    pub next: Option<&'a str>,
+    pub prev: Option<&'a str>,
+    /// link and title of the directories above the page, starting from the workspace root
+    pub breadcrumbs: &'a [(String, String)],
+    /// hash of the norg source the page is rendered from, the server checks it before editing the source
+    pub source_hash: Option<&'a str>,
+}
+
+/// link of the header navigation, shown disabled without an `href` if there is nothing to link to
+fn nav_link(label: &str, href: Option<&str>) -> String {
+    match href {
+        Some(href) => format!(r#"<a href="{}">{label}</a>"#, sanitize_html(href)),
+        None => format!(r#"<a aria-disabled="true">{label}</a>"#),
+    }
+}
+
+/// trail of directory links shown above the title, empty if there are no directories above the page
+fn breadcrumb_trail(breadcrumbs: &[(String, String)]) -> String {
+    if breadcrumbs.is_empty() {
+        return String::new();
+    }
+    let items: String = breadcrumbs
+        .iter()
+        .map(|(href, title)| {
+            format!(
+                r#"<li><a href="{}">{}</a></li>"#,
+                sanitize_html(href),
+                sanitize_html(title)
+            )
+        })
+        .collect();
+    format!(r#"<ol class="breadcrumbs" aria-label="Breadcrumb">{items}</ol>"#)
+}
+
+/// full html page with the header and navigation around the rendered content
+pub fn html_page(title: &str, content: &str, links: &PageLinks) -> String {
+    format!(
+        r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <title>{title}</title>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <link rel="preconnect" href="https://fonts.googleapis.com">
+    <link rel="preconnect" href="https://fonts.gstatic.com">
+    <link rel="icon" href="{favicon}" type="image/svg+xml">
+    <link rel="stylesheet" href="https://fonts.googleapis.com/css2?family=Source+Sans+Pro:wght@600;700&family=Source+Serif+Pro:wght@400;700&display=swap">
+    {styles_and_scripts}
+</head>
+<body>
+    <header class="site-header">
+        <div class="header-content">
+            <div class="page-title">
+                {breadcrumbs}
+                <h1 class="site-title">{title}</h1>
+            </div>
+            <nav>
+                <a href="{home}">Home</a>
+                {up}
+                {next}
+                {prev}
+                <a href="{raw}">Raw</a>
+                <button class="theme-toggle" aria-label="Toggle dark/light mode">
+                    <span class="icon">☀️</span>
+                </button>
+            </nav>
+        </div>
+    </header>
+    <main class="norg_content">
+        <article{source_hash}>
+            {content}
+        </article>
+    </main>
+</body>
+</html>"##,
+        title = sanitize_html(title),
+        favicon = links.favicon,
+        styles_and_scripts = links.styles_and_scripts,
+        home = links.home,
+        raw = links.raw,
+        breadcrumbs = breadcrumb_trail(links.breadcrumbs),
+        up = nav_link("Up", links.up),
+        next = nav_link("Next", links.next),
+        prev = nav_link("Prev", links.prev),
+        source_hash = links
+            .source_hash
+            .map(|hash| format!(r#" data-source-hash="{}""#, sanitize_html(hash)))
+            .unwrap_or_default(),
+    )
+}
+
+/// relative prefix from a file to the root of the site, `""` for files in the root
+fn root_prefix(file: &Path) -> String {
+    "../".repeat(file.components().count().saturating_sub(1))
+}
+
+/// norg file of the link, the extension is optional in links
+fn norg_path(path: &Path) -> PathBuf {
+    if is_norg_file(path) {
+        path.to_path_buf()
+    } else {
+        PathBuf::from(format!("{}.norg", path.display()))
+    }
+}
+
+/// file name of the category page, categories can contain characters which are not allowed in file names
+fn category_file_name(name: &str) -> String {
+    name.replace(['/', '\\'], "-")
+}
+
+/// `.html` page of the norg file, the extension is optional in links
+fn html_path(path: &str) -> String {
+    format!("{}.html", path.strip_suffix(".norg").unwrap_or(path))
+}
+
+/// converts a server link into a link inside the static site along with the linked workspace file,
+/// `None` if the link doesn't point into the workspace
+///
+/// `/view/current/...` links and relative links to norg files of the workspace point to the generated `.html`
+fn site_href(href: &str, root_dir: &Path, document: &Path) -> Option<(String, Option<PathBuf>)> {
+    let (path, fragment) = match href.split_once('#') {
+        Some((path, fragment)) => (path, Some(fragment)),
+        None => (href, None),
+    };
+    let path = path.split_once('?').map_or(path, |(path, _)| path);
+
+    if let Some(category) = path
+        .strip_prefix(constants::CATEGORIES_PATH)
+        .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+    {
+        let page = match category.trim_start_matches('/') {
+            "" => "index.html".to_string(),
+            name => format!(
+                "{}.html",
+                percent_encode(&category_file_name(&percent_decode(name)))
+            ),
+        };
+        let target = format!("{}{CATEGORIES_DIR}/{page}", root_prefix(document));
+        return Some((target, None));
+    }
+
+    let (mut target, linked) = if let Some(workspace_path) = path
+        .strip_prefix(constants::CURRENT_WORKSPACE_PATH)
+        .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+    {
+        let workspace_path = workspace_path.trim_start_matches('/');
+        let (page, linked) = if workspace_path.is_empty() {
+            ("index.html".to_string(), PathBuf::from("index.norg"))
+        } else if root_dir.join(workspace_path).is_file()
+            && !is_norg_file(Path::new(workspace_path))
+        {
+            (workspace_path.to_string(), PathBuf::from(workspace_path))
+        } else {
+            (
+                html_path(workspace_path),
+                norg_path(Path::new(workspace_path)),
+            )
+        };
+        (root_prefix(document) + &page, linked)
+    } else if !path.is_empty() && !path.starts_with('/') && !path.contains(':') {
+        // relative links are kept relative to the document, but only if they point to a norg file
+        let document_dir = document.parent().unwrap_or(Path::new(""));
+        let linked = norg_path(&document_dir.join(path));
+        if !root_dir.join(&linked).is_file() {
+            return None;
+        }
+        (html_path(path), linked)
+    } else {
+        return None;
+    };
+    if let Some(fragment) = fragment {
+        target.push('#');
+        target.push_str(fragment);
+    }
+    Some((target, Some(linked)))
+}
+
+/// rewrites links of the rendered document so that they work without the server
+pub fn rewrite_links(content: &str, root_dir: &Path, document: &Path) -> String {
+    rewrite_links_collecting(content, root_dir, document, &mut Vec::new())
+}
+
+/// same as [`rewrite_links`], linked workspace files are added to `links`
+fn rewrite_links_collecting(
+    content: &str,
+    root_dir: &Path,
+    document: &Path,
+    links: &mut Vec<PathBuf>,
+) -> String {
+    const HREF: &str = "href=\"";
+    let mut output = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find(HREF) {
+        let (before, after) = rest.split_at(start + HREF.len());
+        output.push_str(before);
+        let Some(end) = after.find('"') else {
+            rest = after;
+            break;
+        };
+        // attribute values of the rendered html are escaped
+        let href = unescape_html(&after[..end]);
+        match site_href(&href, root_dir, document) {
+            Some((rewritten, linked)) => {
+                trace!(href, rewritten, "rewriting link");
+                output.push_str(&sanitize_html(&rewritten));
+                links.extend(linked);
+            }
+            None => output.push_str(&after[..end]),
+        }
+        rest = &after[end..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// title of the index of a directory without an `index.norg`
+fn directory_title(directory: &Path) -> String {
+    directory
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Index".to_string())
+}
+
+/// page listing the pages and subdirectories of a directory which doesn't have an `index.norg`,
+/// `subdirectories` and `pages` are names inside the directory, pages without the `.norg` extension
+/// and `extension` is the extension of the linked pages
+fn directory_index(
+    directory: &Path,
+    subdirectories: &[String],
+    pages: &[String],
+    extension: &str,
+) -> Result<String, std::fmt::Error> {
+    let mut content = String::new();
+    writeln!(
+        content,
+        "<h1>{}</h1>",
+        sanitize_html(&directory_title(directory))
+    )?;
+    content.push_str("<ul class=\"directory_index\">\n");
+    for name in subdirectories {
+        writeln!(
+            content,
+            "<li><a href=\"{}/index.{extension}\">{}/</a></li>",
+            percent_encode(name),
+            sanitize_html(name)
+        )?;
+    }
+    for name in pages {
+        writeln!(
+            content,
+            "<li><a href=\"{}.{extension}\">{}</a></li>",
+            percent_encode(name),
+            sanitize_html(name)
+        )?;
+    }
+    content.push_str("</ul>");
+    Ok(content)
+}
+
+/// title and content of the index of `directory` in `root_dir`, for the server where directories
+/// without an `index.norg` don't have a generated page
+pub fn read_directory_index(root_dir: &Path, directory: &Path) -> miette::Result<(String, String)> {
+    let path = root_dir.join(directory);
+    let mut subdirectories = Vec::new();
+    let mut pages = Vec::new();
+    for entry in std::fs::read_dir(&path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Couldn't read directory {}", path.display()))?
+    {
+        let entry = entry.into_diagnostic()?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') {
+            continue;
+        }
+        if entry.file_type().into_diagnostic()?.is_dir() {
+            subdirectories.push(name);
+        } else if let Some(stem) = name.strip_suffix(".norg") {
+            pages.push(stem.to_string());
+        }
+    }
+    subdirectories.sort();
+    pages.sort();
+    let content = directory_index(directory, &subdirectories, &pages, "norg")
+        .into_diagnostic()
+        .wrap_err("Couldn't generate directory index")?;
+    Ok((directory_title(directory), content))
+}
+
+fn write_file(path: &Path, content: impl AsRef<[u8]>) -> miette::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Couldn't create directory {}", parent.display()))?;
+    }
+    std::fs::write(path, content)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Couldn't write {}", path.display()))
+}
+
+/// page in the static site, links are relative so that the site can be hosted from any path
+fn site_page(
+    file: &Path,
+    title: &str,
+    content: &str,
+    raw: &str,
+    navigation: &Navigation,
+) -> String {
+    let prefix = root_prefix(file);
+    let page_link = |path: &Option<PathBuf>| {
+        path.as_ref()
+            .map(|path| format!("{prefix}{}", html_path(&path.to_string_lossy())))
+    };
+    let (up, next, prev) = (
+        page_link(&navigation.up),
+        page_link(&navigation.next),
+        page_link(&navigation.prev),
+    );
+    let breadcrumbs: Vec<(String, String)> = navigation
+        .breadcrumbs
+        .iter()
+        .map(|breadcrumb| {
+            (
+                format!("{prefix}{}", html_path(&breadcrumb.index.to_string_lossy())),
+                breadcrumb.title.clone(),
+            )
+        })
+        .collect();
+    let styles_and_scripts = format!(
+        r#"<link rel="stylesheet" href="{prefix}{STATIC_DIR}/style.css">
+<script src="{prefix}{STATIC_DIR}/scripts.js"></script>"#
+    );
+    html_page(
+        title,
+        content,
+        &PageLinks {
+            home: &format!("{prefix}index.html"),
+            styles_and_scripts: &styles_and_scripts,
+            favicon: &format!("{prefix}favicon.svg"),
+            raw,
+            up: up.as_deref(),
+            next: next.as_deref(),
+            prev: prev.as_deref(),
+            breadcrumbs: &breadcrumbs,
+            source_hash: None,
+        },
+    )
+}
+
+/// result of a static build
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BuildSummary {
+    /// norg files rendered into pages
+    pub rendered: usize,
+    /// other files copied as it is
+    pub copied: usize,
+    /// files which didn't change since the previous build
+    pub unchanged: usize,
+    /// outputs removed because their source was deleted
+    pub removed: usize,
+}
+
+/// hash of everything other than the workspace which changes the output
+fn config_hash(variables: &HashMap<String, String>) -> String {
+    let mut variables: Vec<_> = variables.iter().collect();
+    variables.sort();
+    let config = format!("{}{variables:?}", env!("CARGO_PKG_VERSION"));
+    manifest::content_hash(config.as_bytes())
+}
+
+/// removes a generated file and the directories which became empty because of it
+fn remove_output(output_dir: &Path, output: &Path) -> miette::Result<()> {
+    let path = output_dir.join(output);
+    match std::fs::remove_file(&path) {
+        Ok(()) => debug!(?path, "removed stale output"),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => {
+            return Err(e)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Couldn't remove {}", path.display()))
+        }
+    }
+    for directory in output.ancestors().skip(1) {
+        if directory.as_os_str().is_empty()
+            || std::fs::remove_dir(output_dir.join(directory)).is_err()
+        {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// location of the atom feed of a workspace directory in the static site
+pub fn feed_path(directory: &Path) -> PathBuf {
+    PathBuf::from(format!("{FEEDS_DIR}/{}.xml", directory.display()))
+}
+
+/// renders the whole workspace into a self contained static site in `output_dir`
+///
+/// norg sources and other files are copied next to the pages, so that raw links and images keep working.
+/// atom feeds of `feeds` directories are written to `feeds/<directory>.xml` and pages of `@document.meta`
+/// categories to `categories/<name>.html`.
+/// a manifest of the build is kept in the output directory, so that only files which changed or whose
+/// includes or links changed are rendered again, `force` ignores it and builds everything
+#[instrument(skip(variables))]
+pub fn build_site(
+    root_dir: &Path,
+    output_dir: &Path,
+    variables: &HashMap<String, String>,
+    feeds: &[PathBuf],
+    force: bool,
+) -> miette::Result<BuildSummary> {
+    let mut files = workspace_files(root_dir)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Couldn't read workspace {}", root_dir.display()))?;
+    if let Some(output) = relative_output_dir(root_dir, output_dir) {
+        debug!(?output, "skipping output directory inside the workspace");
+        files.retain(|file| !file.starts_with(&output));
+    }
+    let config = config_hash(variables);
+    let previous = if force {
+        Manifest::default()
+    } else {
+        Manifest::load(output_dir)
+    };
+    let previous_files = if previous.config == config {
+        previous.files
+    } else {
+        info!("build configuration changed, building everything");
+        BTreeMap::new()
+    };
+    let mut manifest = Manifest {
+        config,
+        ..Default::default()
+    };
+    let mut hashes = Hashes::default();
+    let mut summary = BuildSummary::default();
+    let includes = std::sync::Arc::new(renderer::IncludeCache::default());
+
+    let mut directories = BTreeSet::from([PathBuf::new()]);
+    for file in &files {
+        directories.extend(file.ancestors().skip(1).map(Path::to_path_buf));
+        let source = root_dir.join(file);
+        let hash = hashes.get(&source);
+        let navigation = if is_norg_file(file) {
+            navigation::navigation(root_dir, file)
+        } else {
+            Navigation::default()
+        };
+        // breadcrumbs are part of the navigation, so titles of the parent indexes are included
+        let navigation_hash = manifest::content_hash(format!("{navigation:?}").as_bytes());
+        if let Some(entry) = previous_files
+            .get(file)
+            .filter(|entry| entry.is_fresh(&hash, &navigation_hash, output_dir, &mut hashes))
+        {
+            trace!(?file, "file is unchanged");
+            manifest.files.insert(file.clone(), entry.clone());
+            summary.unchanged += 1;
+            continue;
+        }
+
+        let target = output_dir.join(file);
+        write_file(
+            &target,
+            std::fs::read(&source)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Couldn't read {}", source.display()))?,
+        )?;
+        let mut entry = Entry {
+            hash,
+            navigation: navigation_hash,
+            outputs: vec![file.clone()],
+            ..Default::default()
+        };
+        if !is_norg_file(file) {
+            debug!(?file, "copied file");
+            manifest.files.insert(file.clone(), entry);
+            summary.copied += 1;
+            continue;
+        }
+
+        debug!(?file, "rendering page");
+        let content = std::fs::read_to_string(&source)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Couldn't read {}", source.display()))?;
+        let mut ctx = RenderContext::with_variables(variables.clone())
+            .with_document_path(&source)
+            .with_workspace_root(root_dir)
+            .with_include_cache(includes.clone());
+        let body = renderer::render_norg(&content, &mut ctx, &mut HtmlBackend)
+            .wrap_err_with(|| format!("Couldn't render {}", source.display()))?;
+        let mut links = Vec::new();
+        let body = rewrite_links_collecting(&body, root_dir, file, &mut links);
+        let meta = ctx.document_meta();
+        let title = meta.title.clone().unwrap_or_else(|| {
+            file.file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned()
+        });
+        entry.title = meta.title.clone();
+        entry.categories = meta.categories.clone();
+        entry.date = ctx.uses_date().then(manifest::today);
+        let raw = file.file_name().unwrap_or_default().to_string_lossy();
+        let page = file.with_extension("html");
+        write_file(
+            &output_dir.join(&page),
+            site_page(file, &title, &body, &raw, &navigation),
+        )?;
+
+        entry.outputs.push(page);
+        for dependency in ctx.dependencies() {
+            entry
+                .dependencies
+                .insert(dependency.clone(), hashes.get(dependency));
+        }
+        for link in links {
+            let link = root_dir.join(link);
+            let exists = link.is_file();
+            entry.links.insert(link, exists);
+        }
+        manifest.files.insert(file.clone(), entry);
+        summary.rendered += 1;
+    }
+
+    for directory in &directories {
+        let index = directory.join("index.norg");
+        if files.contains(&index) {
+            continue;
+        }
+        debug!(?directory, "generating directory index");
+        let name = |path: &Path| {
+            path.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned()
+        };
+        let subdirectories: Vec<String> = directories
+            .iter()
+            .filter(|subdirectory| subdirectory.parent() == Some(directory))
+            .map(|subdirectory| name(subdirectory))
+            .collect();
+        let pages: Vec<String> = files
+            .iter()
+            .filter(|file| is_norg_file(file) && file.parent() == Some(directory))
+            .map(|page| name(&page.with_extension("")))
+            .collect();
+        let content = directory_index(directory, &subdirectories, &pages, "html")
+            .into_diagnostic()
+            .wrap_err("Couldn't generate directory index")?;
+        let output = directory.join("index.html");
+        let navigation = navigation::navigation(root_dir, &index);
+        write_file(
+            &output_dir.join(&output),
+            site_page(
+                &index,
+                &directory_title(directory),
+                &content,
+                "#",
+                &navigation,
+            ),
+        )?;
+        manifest.generated.push(output);
+    }
+
+    let mut category_index = CategoryIndex::default();
+    for (file, entry) in manifest.files.iter().filter(|(file, _)| is_norg_file(file)) {
+        category_index.insert(file.clone(), entry.title.clone(), entry.categories.clone());
+    }
+    let overview = Path::new(CATEGORIES_DIR).join("index.html");
+    let content = categories::categories_overview(&category_index, |name| {
+        format!("{}.html", percent_encode(&category_file_name(name)))
+    })
+    .into_diagnostic()
+    .wrap_err("Couldn't generate categories overview")?;
+    write_file(
+        &output_dir.join(&overview),
+        site_page(
+            &overview,
+            "Categories",
+            &content,
+            "#",
+            &Navigation::default(),
+        ),
+    )?;
+    manifest.generated.push(overview);
+    for (name, documents) in category_index.categories() {
+        let page = Path::new(CATEGORIES_DIR).join(format!("{}.html", category_file_name(name)));
+        debug!(name, ?page, "generating category page");
+        let prefix = root_prefix(&page);
+        let content = categories::category_page(name, &documents, |path| {
+            format!("{prefix}{}", html_path(&path.to_string_lossy()))
+        })
+        .into_diagnostic()
+        .wrap_err("Couldn't generate category page")?;
+        write_file(
+            &output_dir.join(&page),
+            site_page(
+                &page,
+                &format!("Category: {name}"),
+                &content,
+                "#",
+                &Navigation::default(),
+            ),
+        )?;
+        manifest.generated.push(page);
+    }
+
+    for directory in feeds {
+        let output = feed_path(directory);
+        debug!(?directory, ?output, "generating feed");
+        let prefix = root_prefix(&output);
+        let mut entries = feed::feed_entries(root_dir, directory, variables)?;
+        for entry in &mut entries {
+            entry.content = rewrite_links(&entry.content, root_dir, &entry.path);
+        }
+        let feed = feed::atom_feed(
+            directory,
+            &output.file_name().unwrap_or_default().to_string_lossy(),
+            &feed::feed_author(root_dir, variables),
+            &entries,
+            |path| format!("{prefix}{}", html_path(&path.to_string_lossy())),
+        )
+        .into_diagnostic()
+        .wrap_err("Couldn't generate feed")?;
+        write_file(&output_dir.join(&output), feed)?;
+        manifest.generated.push(output);
+    }
+
+    let outputs: BTreeSet<&PathBuf> = manifest
+        .files
+        .values()
+        .flat_map(|entry| &entry.outputs)
+        .chain(&manifest.generated)
+        .collect();
+    for (file, entry) in &previous_files {
+        if manifest.files.contains_key(file) {
+            continue;
+        }
+        debug!(?file, "source was deleted");
+        for output in entry
+            .outputs
+            .iter()
+            .filter(|output| !outputs.contains(output))
+        {
+            remove_output(output_dir, output)?;
+        }
+        summary.removed += 1;
+    }
+    for generated in previous
+        .generated
+        .iter()
+        .filter(|generated| !outputs.contains(generated))
+    {
+        remove_output(output_dir, generated)?;
+    }
+
+    write_file(&output_dir.join(STATIC_DIR).join("style.css"), STYLE)?;
+    write_file(&output_dir.join(STATIC_DIR).join("scripts.js"), SCRIPTS)?;
+    write_file(&output_dir.join("favicon.svg"), FAVICON)?;
+    manifest.save(output_dir)?;
+    info!(?summary, "built static site");
+    Ok(summary)
+}
@@ -0,0 +1,293 @@
+//! tasks of the workspace, headings and list items with TODO status or date extensions
+//!
+//! dates are written as `2024-01-31`, optionally followed by a time, or as neorg timestamps like
+//! `Wed, 31 Jan 2024 10:00`. recurring tasks `(+ Thursday)` recur on a weekday, `(+ 5th)` on a day
+//! of every month and `(+ 5 Jan)` on a day of every year
+
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, Months, NaiveDate, NaiveTime, Weekday};
+use miette::{Context, IntoDiagnostic};
+use tracing::{debug, instrument, trace, warn};
+
+use crate::{
+    export::{is_norg_file, workspace_files},
+    html::sanitize_html,
+    renderer,
+};
+
+/// date of a task extension with the time if it was given
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TaskDate {
+    pub date: NaiveDate,
+    pub time: Option<NaiveTime>,
+}
+
+/// how often a recurring task repeats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recurrence {
+    Weekly(Weekday),
+    /// day of the month
+    Monthly(u32),
+    /// month and day
+    Yearly(u32, u32),
+}
+
+#[derive(Debug)]
+pub struct Task {
+    /// document of the task relative to the workspace root
+    pub path: PathBuf,
+    /// level and title of the heading containing the task, the task itself if it is a heading
+    pub heading: Option<(u16, String)>,
+    /// text of the list item or title of the heading
+    pub text: String,
+    pub status: Option<norg::TodoStatus>,
+    pub priority: Option<String>,
+    pub due: Option<TaskDate>,
+    pub start: Option<TaskDate>,
+    pub timestamp: Option<TaskDate>,
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    // neorg separates seconds with a dot, `10:00.30`
+    let value = value.replace('.', ":");
+    NaiveTime::parse_from_str(&value, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(&value, "%H:%M"))
+        .ok()
+}
+
+/// day of the month without the ordinal suffix, `5th` is `5`
+fn parse_day(value: &str) -> Option<u32> {
+    value
+        .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+        .parse()
+        .ok()
+        .filter(|day| (1..=31).contains(day))
+}
+
+fn parse_month(value: &str) -> Option<u32> {
+    NaiveDate::parse_from_str(&format!("1 {value} 2000"), "%d %b %Y")
+        .or_else(|_| NaiveDate::parse_from_str(&format!("1 {value} 2000"), "%d %B %Y"))
+        .ok()
+        .map(|date| date.month())
+}
+
+/// date of a task extension, `None` if it isn't a date
+pub fn parse_task_date(value: &str) -> Option<TaskDate> {
+    let tokens: Vec<&str> = value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .collect();
+    let time = tokens.iter().find_map(|token| {
+        let (_, time) = token.split_once('T').unwrap_or(("", token));
+        time.contains(':').then(|| parse_time(time)).flatten()
+    });
+    // iso dates, time can be a separate token or joined with `T`
+    if let Some(date) = tokens.iter().find_map(|token| {
+        let date = token.split_once('T').map_or(*token, |(date, _)| date);
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+    }) {
+        return Some(TaskDate { date, time });
+    }
+    let mut tokens = tokens
+        .into_iter()
+        .skip_while(|token| token.parse::<Weekday>().is_ok());
+    let (day, month, year) = (tokens.next()?, tokens.next()?, tokens.next()?);
+    let date = NaiveDate::from_ymd_opt(year.parse().ok()?, parse_month(month)?, parse_day(day)?)?;
+    Some(TaskDate { date, time })
+}
+
+/// recurrence of a recurring TODO status, `None` if it isn't understood
+pub fn parse_recurrence(value: &str) -> Option<Recurrence> {
+    let tokens: Vec<&str> = value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .collect();
+    match tokens.as_slice() {
+        [weekday] if weekday.parse::<Weekday>().is_ok() => {
+            weekday.parse().ok().map(Recurrence::Weekly)
+        }
+        [day] => parse_day(day).map(Recurrence::Monthly),
+        [first, second] => match (parse_day(first), parse_month(second)) {
+            (Some(day), Some(month)) => Some(Recurrence::Yearly(month, day)),
+            _ => Some(Recurrence::Yearly(parse_month(first)?, parse_day(second)?)),
+        },
+        _ => None,
+    }
+}
+
+impl Recurrence {
+    /// days from `first` to `last` on which the task recurs
+    pub fn occurrences(&self, first: NaiveDate, last: NaiveDate) -> Vec<NaiveDate> {
+        let mut occurrences = Vec::new();
+        match *self {
+            Self::Weekly(weekday) => {
+                occurrences.extend(
+                    first
+                        .iter_days()
+                        .take_while(|date| *date <= last)
+                        .filter(|date| date.weekday() == weekday),
+                );
+            }
+            Self::Monthly(day) => {
+                let mut month = first.with_day(1).unwrap_or(first);
+                while month <= last {
+                    // months without the day are skipped, like the 31st in april
+                    occurrences.extend(month.with_day(day));
+                    let Some(next) = month.checked_add_months(Months::new(1)) else {
+                        break;
+                    };
+                    month = next;
+                }
+            }
+            Self::Yearly(month, day) => {
+                for year in first.year()..=last.year() {
+                    occurrences.extend(NaiveDate::from_ymd_opt(year, month, day));
+                }
+            }
+        }
+        occurrences.retain(|date| (first..=last).contains(date));
+        occurrences
+    }
+}
+
+impl Task {
+    /// recurrence of the TODO status, `None` for tasks which don't recur
+    pub fn recurrence(&self) -> Option<Recurrence> {
+        match &self.status {
+            Some(norg::TodoStatus::Recurring(Some(recurrence))) => parse_recurrence(recurrence),
+            _ => None,
+        }
+    }
+
+    /// done and canceled tasks don't need any attention
+    pub fn is_open(&self) -> bool {
+        !matches!(
+            self.status,
+            Some(norg::TodoStatus::Done | norg::TodoStatus::Canceled)
+        )
+    }
+
+    /// link to the heading of the task in the document
+    pub fn link(&self, view: &str) -> String {
+        let mut link = format!("{view}/{}", self.path.display());
+        if let Some((level, title)) = &self.heading {
+            link.push('#');
+            link.push_str(&renderer::fragment_id(
+                &sanitize_html(title),
+                &format!("h{level}"),
+            ));
+        }
+        link
+    }
+}
+
+/// task with its extensions, `None` if none of the extensions make it a task
+fn task(
+    path: &Path,
+    heading: Option<(u16, String)>,
+    text: String,
+    extensions: Vec<norg::DetachedModifierExtension>,
+) -> Option<Task> {
+    let mut task = Task {
+        path: path.to_path_buf(),
+        heading,
+        text,
+        status: None,
+        priority: None,
+        due: None,
+        start: None,
+        timestamp: None,
+    };
+    let is_task = !extensions.is_empty();
+    let parse = |value: String| {
+        let date = parse_task_date(&value);
+        if date.is_none() {
+            warn!(?path, value, "task date is not understood");
+        }
+        date
+    };
+    for extension in extensions {
+        match extension {
+            norg::DetachedModifierExtension::Todo(status) => task.status = Some(status),
+            norg::DetachedModifierExtension::Priority(priority) => task.priority = Some(priority),
+            norg::DetachedModifierExtension::DueDate(due) => task.due = parse(due),
+            norg::DetachedModifierExtension::StartDate(start) => task.start = parse(start),
+            norg::DetachedModifierExtension::Timestamp(timestamp) => {
+                task.timestamp = parse(timestamp)
+            }
+        }
+    }
+    is_task.then_some(task)
+}
+
+/// text of a list item, paragraphs are kept and any other block is skipped
+fn item_text(text: &norg::NorgASTFlat) -> String {
+    match text {
+        norg::NorgASTFlat::Paragraph(segments) => renderer::raw_title(segments).trim().to_string(),
+        _ => String::new(),
+    }
+}
+
+fn collect_tasks(
+    path: &Path,
+    nodes: Vec<norg::NorgAST>,
+    heading: &Option<(u16, String)>,
+    tasks: &mut Vec<Task>,
+) {
+    for node in nodes {
+        match node {
+            norg::NorgAST::Heading {
+                level,
+                title,
+                extensions,
+                content,
+            } => {
+                let title = renderer::raw_title(&title).trim().to_string();
+                let this_heading = Some((level, title.clone()));
+                tasks.extend(task(path, this_heading.clone(), title, extensions));
+                collect_tasks(path, content, &this_heading, tasks);
+            }
+            norg::NorgAST::NestableDetachedModifier {
+                extensions,
+                text,
+                content,
+                ..
+            } => {
+                tasks.extend(task(path, heading.clone(), item_text(&text), extensions));
+                collect_tasks(path, content, heading, tasks);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// tasks of a norg document in the order they are written, `path` is the document relative to the workspace root
+pub fn document_tasks(path: &Path, input: &str) -> miette::Result<Vec<Task>> {
+    let nodes = norg::parse_tree(input).map_err(|e| miette::miette!("failed to parse: {e:?}"))?;
+    let mut tasks = Vec::new();
+    collect_tasks(path, nodes, &None, &mut tasks);
+    trace!(?path, count = tasks.len(), "collected tasks");
+    Ok(tasks)
+}
+
+/// tasks of every norg document in the workspace, documents which can't be parsed are skipped
+#[instrument]
+pub fn workspace_tasks(root_dir: &Path) -> miette::Result<Vec<Task>> {
+    let files = workspace_files(root_dir)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Couldn't read workspace {}", root_dir.display()))?;
+    let mut tasks = Vec::new();
+    for file in files.into_iter().filter(|file| is_norg_file(file)) {
+        let source = root_dir.join(&file);
+        debug!(?file, "collecting tasks");
+        match std::fs::read_to_string(&source)
+            .into_diagnostic()
+            .and_then(|input| document_tasks(&file, &input))
+        {
+            Ok(document_tasks) => tasks.extend(document_tasks),
+            Err(e) => warn!(?file, "Couldn't collect tasks: {e}"),
+        }
+    }
+    Ok(tasks)
+}
@@ -0,0 +1,91 @@
+use std::path::Path;
+
+use chrono::{NaiveDate, NaiveTime, Weekday};
+use norgmill::{
+    agenda::{agenda, agenda_page},
+    tasks::{document_tasks, parse_recurrence, parse_task_date, Recurrence},
+};
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("invalid test date")
+}
+
+// Task Date Tests
+#[test]
+fn test_task_dates() {
+    let result = parse_task_date("2024-01-31").expect("Failed to parse iso date");
+    assert_eq!(result.date, date(2024, 1, 31));
+    assert_eq!(result.time, None);
+
+    let result = parse_task_date("2024-01-31 14:30").expect("Failed to parse date with time");
+    assert_eq!(result.time, NaiveTime::from_hms_opt(14, 30, 0));
+
+    let result = parse_task_date("Wed, 31 Jan 2024 10:00.30").expect("Failed to parse timestamp");
+    assert_eq!(result.date, date(2024, 1, 31));
+    assert_eq!(result.time, NaiveTime::from_hms_opt(10, 0, 30));
+
+    assert!(parse_task_date("someday").is_none());
+}
+
+#[test]
+fn test_task_recurrence() {
+    assert_eq!(
+        parse_recurrence("Thursday"),
+        Some(Recurrence::Weekly(Weekday::Thu))
+    );
+    assert_eq!(parse_recurrence("5th"), Some(Recurrence::Monthly(5)));
+    assert_eq!(parse_recurrence("5 Jan"), Some(Recurrence::Yearly(1, 5)));
+    assert_eq!(parse_recurrence("Jan 5th"), Some(Recurrence::Yearly(1, 5)));
+
+    let result = Recurrence::Monthly(31).occurrences(date(2024, 1, 15), date(2024, 5, 1));
+    assert_eq!(result, [date(2024, 1, 31), date(2024, 3, 31)]);
+}
+
+#[test]
+fn test_document_tasks() {
+    let norg = "* Garden\n- ( |< 2024-01-31) Buy seeds\n- Plain item\n** ( ) Harvest";
+    let tasks = document_tasks(Path::new("garden.norg"), norg).expect("Failed to collect tasks");
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[0].text, "Buy seeds");
+    assert_eq!(tasks[0].heading, Some((1, "Garden".to_string())));
+    assert_eq!(tasks[0].due.map(|due| due.date), Some(date(2024, 1, 31)));
+    assert_eq!(
+        tasks[0].link("/view/current"),
+        "/view/current/garden.norg#Garden_h1"
+    );
+    assert_eq!(tasks[1].heading, Some((2, "Harvest".to_string())));
+}
+
+// Agenda Tests
+#[test]
+fn test_agenda_groups() {
+    let norg = "- ( |< 2024-01-10) Overdue bill
+- ( |@ 2024-01-15) Meeting today
+- ( |< 2024-01-19) Due this week
+- ( |> 2024-02-10) Starts later
+- (x|< 2024-01-12) Finished
+- (+ Monday) Weekly review";
+    let tasks = document_tasks(Path::new("tasks.norg"), norg).expect("Failed to collect tasks");
+    // 2024-01-15 is a monday
+    let result = agenda(&tasks, date(2024, 1, 15));
+    let texts = |items: &[norgmill::agenda::AgendaItem]| -> Vec<String> {
+        items.iter().map(|item| item.task.text.clone()).collect()
+    };
+    assert_eq!(texts(&result.overdue), ["Overdue bill"]);
+    assert_eq!(texts(&result.today), ["Meeting today", "Weekly review"]);
+    assert_eq!(texts(&result.this_week), ["Due this week"]);
+    assert!(texts(&result.upcoming).contains(&"Starts later".to_string()));
+    assert_eq!(
+        texts(&result.upcoming)
+            .iter()
+            .filter(|text| *text == "Weekly review")
+            .count(),
+        4
+    );
+
+    let page = agenda_page(&result, |task| task.link("/view/current")).unwrap();
+    assert!(page.contains("<h2>Overdue</h2>"));
+    assert!(page.contains(r#"<time datetime="2024-01-10">Wed, 10 Jan 2024</time>"#));
+    assert!(page.contains(r#"<a href="/view/current/tasks.norg">Overdue bill</a>"#));
+    assert!(!page.contains("Finished"));
+}
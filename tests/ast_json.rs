@@ -0,0 +1,67 @@
+use norgmill::json::{parse_to_json, SCHEMA_VERSION};
+
+fn ast_json(norg: &str, flat: bool) -> String {
+    parse_to_json(norg, flat)
+        .expect("Failed to convert AST into JSON")
+        .to_string()
+}
+
+// JSON AST Tests
+#[test]
+fn test_json_schema_version() {
+    let result = ast_json("Hello", false);
+    assert!(result.contains(&format!("\"schema_version\":{SCHEMA_VERSION}")));
+    assert!(result.contains("\"kind\":\"tree\""));
+    let result = ast_json("Hello", true);
+    assert!(result.contains("\"kind\":\"flat\""));
+}
+
+#[test]
+fn test_json_paragraph_segments() {
+    let result = ast_json("Hello *bold* world", false);
+    assert!(result.contains("\"type\":\"paragraph\""));
+    assert!(result.contains("\"type\":\"text\",\"text\":\"Hello\""));
+    assert!(result.contains("\"type\":\"attached_modifier\""));
+    assert!(result.contains("\"modifier\":\"*\""));
+}
+
+#[test]
+fn test_json_heading_content() {
+    let tree = ast_json("* Heading\n  Content", false);
+    assert!(tree.contains("\"type\":\"heading\""));
+    assert!(tree.contains("\"level\":1"));
+    assert!(tree.contains("\"content\":[{"));
+    let flat = ast_json("* Heading\n  Content", true);
+    assert!(flat.contains("\"type\":\"heading\""));
+}
+
+#[test]
+fn test_json_link_targets() {
+    let result = ast_json("{https://example.com}[Example]", false);
+    assert!(result.contains("\"type\":\"link\""));
+    assert!(result.contains("\"type\":\"url\",\"value\":\"https://example.com\""));
+}
+
+#[test]
+fn test_json_todo_extension() {
+    let result = ast_json("- (x) Done task", false);
+    assert!(result.contains("\"type\":\"nestable_detached_modifier\""));
+    assert!(result.contains("\"modifier\":\"unordered_list\""));
+    assert!(result.contains("\"type\":\"todo\",\"status\":\"done\""));
+}
+
+#[test]
+fn test_json_verbatim_tag() {
+    let result = ast_json("@code rust\nfn main() {}\n@end", false);
+    assert!(result.contains("\"type\":\"verbatim_ranged_tag\""));
+    assert!(result.contains("\"parameters\":[\"rust\"]"));
+}
+
+#[test]
+fn test_json_explicit_type_names() {
+    let result = ast_json("- ( ) Open task\n- (?) Unclear task\n> Quote", false);
+    assert!(result.contains("\"type\":\"todo\",\"status\":\"undone\""));
+    assert!(result.contains("\"status\":\"needs_clarification\""));
+    assert!(result.contains("\"modifier\":\"quote\""));
+    assert!(!result.contains("\"debug\""));
+}
@@ -0,0 +1,36 @@
+use norgmill::renderer::{backend::HtmlBackend, render_norg, RenderContext};
+
+// Html Backend Tests
+#[test]
+fn test_html_backend_matches_renderer_before_backends() {
+    let norg = r#"* Heading
+Some *bold* text with a {https://example.com}[link].
+
+- first item
+- ( ) todo item
+
+@code rust
+fn main() {}
+@end"#;
+    // output of the renderer before it was split into backends, the code block may keep its last newline
+    let expected = concat!(
+        r#"<div class=norg_content><div class="content_block"><div class="heading_block">"#,
+        r#"<h1 class="heading_1">Heading</h1><div class="content_block">"#,
+        r#"<p>Some <strong>bold</strong> text with a <span><a href="https://example.com">link</a></span>.</p>"#,
+        r#"<ul><li class=unordered_l1>first item</li>"#,
+        r#"<li class=unordered_l1 data-status="undone"><span class="status-indicator"></span>todo item</li></ul>"#,
+        r#"<pre class=rust>fn main() {}</pre></div></div></div></div>"#,
+    );
+    let result = render_norg(norg, &mut RenderContext::default(), &mut HtmlBackend)
+        .expect("Failed to render with html backend");
+    assert_eq!(result.replace('\n', ""), expected);
+}
+
+#[test]
+fn test_html_backend_escapes_heading_title_once() {
+    let norg = "* Fish & Chips";
+    let result = render_norg(norg, &mut RenderContext::default(), &mut HtmlBackend)
+        .expect("Failed to render heading");
+    assert!(result.contains("Fish &amp; Chips"));
+    assert!(!result.contains("&amp;amp;"));
+}
@@ -468,3 +468,11 @@ fn test_paragraph_with_weather_symbols() {
     let result = parse_and_render_norg(norg).expect("Failed to parse paragraph with weather symbols");
     assert!(result.contains("<p>Weather: ☀️ ⛅ ☁️ 🌧️ ⛈️ 🌩️ ❄️ 🌨️ 🌪️ 🌈</p>"));
 }
+
+#[test]
+fn test_content_block_closed() {
+    let norg = "* Heading\n  Paragraph under the heading.";
+    let result = parse_and_render_norg(norg).expect("Failed to parse heading with content");
+    assert!(result.contains("<div class=\"content_block\">"));
+    assert_eq!(result.matches("<div").count(), result.matches("</div>").count());
+}
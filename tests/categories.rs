@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use norgmill::categories::{categories_overview, category_page, CategoryIndex};
+
+mod common;
+use common::Workspace;
+
+/// workspace with two documents sharing a category
+fn categorized_workspace(name: &str) -> Workspace {
+    let root = Workspace::new(name);
+    std::fs::write(
+        root.join("rust.norg"),
+        "@document.meta\ntitle: Learning Rust\ncategories: [ programming rust ]\n@end",
+    )
+    .expect("Failed to write rust.norg");
+    std::fs::write(
+        root.join("python.norg"),
+        "@document.meta\ncategories: [\n  programming\n  machine learning\n]\n@end",
+    )
+    .expect("Failed to write python.norg");
+    std::fs::write(root.join("plain.norg"), "No categories here.")
+        .expect("Failed to write plain.norg");
+    root
+}
+
+// Category Index Tests
+#[test]
+fn test_category_index_update() {
+    let root = categorized_workspace("update");
+    let mut index = CategoryIndex::default();
+    index.update(&root).expect("Failed to update index");
+    let categories = index.categories();
+    assert_eq!(
+        categories.keys().copied().collect::<Vec<_>>(),
+        vec!["machine learning", "programming", "rust"]
+    );
+    assert_eq!(categories["programming"].len(), 2);
+    assert_eq!(
+        index.documents("rust"),
+        vec![(Path::new("rust.norg"), "Learning Rust")]
+    );
+
+    std::fs::remove_file(root.join("rust.norg")).expect("Failed to delete rust.norg");
+    index.update(&root).expect("Failed to update index");
+    assert!(index.documents("rust").is_empty());
+    assert_eq!(
+        index.documents("programming"),
+        vec![(Path::new("python.norg"), "python")]
+    );
+}
+
+#[test]
+fn test_category_pages() {
+    let root = categorized_workspace("pages");
+    let mut index = CategoryIndex::default();
+    index.update(&root).expect("Failed to update index");
+
+    let overview = categories_overview(&index, |name| format!("/categories/{name}"))
+        .expect("Failed to render overview");
+    assert!(overview.contains(r#"<a href="/categories/programming">programming</a> (2)"#));
+
+    let page = category_page("rust", &index.documents("rust"), |path| {
+        format!("/view/current/{}", path.display())
+    })
+    .expect("Failed to render category page");
+    assert!(page.contains(r#"<a href="/view/current/rust.norg">Learning Rust</a>"#));
+}
+
+#[test]
+fn test_category_pages_in_static_site() {
+    let root = categorized_workspace("site_root");
+    let output = Workspace::new("site_output");
+    norgmill::site::build_site(&root, &output, &HashMap::new(), &[], false)
+        .expect("Failed to build site");
+
+    let overview =
+        std::fs::read_to_string(output.join("categories/index.html")).expect("overview missing");
+    assert!(overview.contains(r#"href="machine%20learning.html""#));
+    let page = std::fs::read_to_string(output.join("categories/machine learning.html"))
+        .expect("category page missing");
+    assert!(page.contains(r#"href="../python.html""#));
+    let document = std::fs::read_to_string(output.join("rust.html")).expect("rust.html missing");
+    assert!(document.contains(r#"href="categories/rust.html""#));
+}
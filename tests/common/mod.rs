@@ -0,0 +1,35 @@
+//! fixtures shared between the integration tests
+use std::path::{Path, PathBuf};
+
+/// fresh temporary directory for a test, so that tests don't share files, it is removed when dropped
+pub struct Workspace(PathBuf);
+
+impl Workspace {
+    /// `name` has to be unique among the tests of a file
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("norgmill_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("Failed to create test workspace");
+        Self(dir)
+    }
+}
+
+impl std::ops::Deref for Workspace {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for Workspace {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
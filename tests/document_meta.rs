@@ -0,0 +1,89 @@
+use norgmill::renderer::{parse_and_render_norg_with, DocumentMeta, MetaValue, RenderContext};
+
+// Document Meta Model Tests
+#[test]
+fn test_meta_known_fields() {
+    let meta = DocumentMeta::parse(
+        "title: My Notes\ndescription: Things I learned\nauthors: Jane Doe, John\ncreated: 2024-01-01\nupdated: 2024-02-01\nversion: 1.1.1",
+    );
+    assert_eq!(meta.title.as_deref(), Some("My Notes"));
+    assert_eq!(meta.description.as_deref(), Some("Things I learned"));
+    assert_eq!(meta.authors, vec!["Jane Doe", "John"]);
+    assert_eq!(meta.created.as_deref(), Some("2024-01-01"));
+    assert_eq!(meta.updated.as_deref(), Some("2024-02-01"));
+    assert_eq!(meta.version.as_deref(), Some("1.1.1"));
+}
+
+#[test]
+fn test_meta_inline_array() {
+    let meta = DocumentMeta::parse("categories: [ rust notes ]");
+    assert_eq!(meta.categories, vec!["rust", "notes"]);
+}
+
+#[test]
+fn test_meta_multiline_array() {
+    let meta = DocumentMeta::parse("categories: [\n  machine learning\n  rust\n]\ntitle: After");
+    assert_eq!(meta.categories, vec!["machine learning", "rust"]);
+    assert_eq!(meta.title.as_deref(), Some("After"));
+}
+
+#[test]
+fn test_meta_multiline_value() {
+    let meta = DocumentMeta::parse("description: first line\n  second line\ntitle: T");
+    assert_eq!(meta.description.as_deref(), Some("first line\nsecond line"));
+}
+
+#[test]
+fn test_meta_extra_keys() {
+    let meta = DocumentMeta::parse("status: draft\ntags: [a b]\nlinks: {\n  home: example.com\n}");
+    assert_eq!(
+        meta.extra,
+        vec![
+            ("status".to_string(), MetaValue::Text("draft".to_string())),
+            (
+                "tags".to_string(),
+                MetaValue::List(vec!["a".to_string(), "b".to_string()])
+            ),
+            (
+                "links".to_string(),
+                MetaValue::Object(vec![(
+                    "home".to_string(),
+                    MetaValue::Text("example.com".to_string())
+                )])
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_meta_available_after_render() {
+    let mut ctx = RenderContext::default();
+    let result = parse_and_render_norg_with(
+        "@document.meta\ntitle: Rendered\ncategories: [ a b ]\n@end\n\nIn &categories&.",
+        &mut ctx,
+    )
+    .expect("Failed to render document with meta");
+    assert_eq!(ctx.document_meta().title.as_deref(), Some("Rendered"));
+    assert_eq!(ctx.document_meta().categories, vec!["a", "b"]);
+    assert!(result.contains(r#"<a class="category" href="/categories/a">a</a>, "#));
+    assert!(result.contains(">a, b</span>"));
+}
+
+#[test]
+fn test_meta_multiline_value_with_colons() {
+    let meta = DocumentMeta::parse(
+        "description: links to\n  https://example.com/page\n  and note: not a key\nhomepage: https://example.com\ntitle: T",
+    );
+    assert_eq!(
+        meta.description.as_deref(),
+        Some("links to\nhttps://example.com/page\nand note: not a key")
+    );
+    assert_eq!(
+        meta.extra,
+        vec![(
+            "homepage".to_string(),
+            MetaValue::Text("https://example.com".to_string())
+        )]
+    );
+    assert_eq!(meta.title.as_deref(), Some("T"));
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use norgmill::feed::{atom_feed, feed_author, feed_entries, parse_date};
+
+mod common;
+use common::Workspace;
+
+/// workspace with two blog posts and a page outside of the blog
+fn blog_workspace(name: &str) -> Workspace {
+    let root = Workspace::new(name);
+    std::fs::create_dir_all(root.join("blog")).expect("Failed to create blog dir");
+    std::fs::write(
+        root.join("blog/first.norg"),
+        "@document.meta\ntitle: First post\ndescription: The beginning\nauthors: Jane\ncreated: 2024-01-01\n@end\n\nHello *world*.",
+    )
+    .expect("Failed to write first post");
+    std::fs::write(
+        root.join("blog/second.norg"),
+        "@document.meta\ntitle: Second post\ncreated: 2024-02-01T10:00:00+0530\nupdated: 2024-02-03T10:00:00+0530\n@end\n\nMore & more, after the {:first:}[first post].",
+    )
+    .expect("Failed to write second post");
+    std::fs::write(root.join("index.norg"), "Not in the blog.").expect("Failed to write index");
+    root
+}
+
+// Feed Tests
+#[test]
+fn test_feed_dates() {
+    assert!(parse_date("2024-01-31T20:15:00+0530").is_some());
+    assert!(parse_date("2024-01-31T20:15:00Z").is_some());
+    assert_eq!(
+        parse_date("2024-01-31").map(|date| date.to_rfc3339()),
+        Some("2024-01-31T00:00:00+00:00".to_string())
+    );
+    assert!(parse_date("yesterday").is_none());
+}
+
+#[test]
+fn test_feed_entries_from_meta() {
+    let root = blog_workspace("entries");
+    let entries =
+        feed_entries(&root, Path::new("blog"), &HashMap::new()).expect("Failed to read entries");
+    assert_eq!(entries.len(), 2);
+    // newest entry is first
+    assert_eq!(entries[0].title, "Second post");
+    assert_eq!(entries[1].title, "First post");
+    assert_eq!(entries[1].description.as_deref(), Some("The beginning"));
+    assert_eq!(entries[1].authors, vec!["Jane".to_string()]);
+    assert!(entries[1].content.contains("<strong>world</strong>"));
+}
+
+#[test]
+fn test_atom_feed() {
+    let root = blog_workspace("atom");
+    let entries =
+        feed_entries(&root, Path::new("blog"), &HashMap::new()).expect("Failed to read entries");
+    let result = atom_feed(
+        Path::new("blog"),
+        "/feeds/blog.xml",
+        "Blog Team",
+        &entries,
+        |path| format!("/view/current/{}", path.display()),
+    )
+    .expect("Failed to generate feed");
+    assert!(result.contains("  <author><name>Blog Team</name></author>\n  <link"));
+    assert!(result.contains(r#"<feed xmlns="http://www.w3.org/2005/Atom">"#));
+    assert!(result.contains("<title>First post</title>"));
+    assert!(result.contains("<updated>2024-02-03T10:00:00+05:30</updated>"));
+    assert!(result.contains(r#"href="/view/current/blog/first.norg""#));
+    assert!(result.contains("<author><name>Jane</name></author>"));
+    assert!(result.contains("<summary>The beginning</summary>"));
+    assert!(result.contains("&lt;strong&gt;world&lt;/strong&gt;"));
+    assert!(!result.contains("Not in the blog"));
+}
+
+#[test]
+fn test_feed_in_static_site() {
+    let root = blog_workspace("site_root");
+    let output = Workspace::new("site_output");
+    norgmill::site::build_site(
+        &root,
+        &output,
+        &HashMap::new(),
+        &[PathBuf::from("blog")],
+        false,
+    )
+    .expect("Failed to build site");
+    let feed = std::fs::read_to_string(output.join("feeds/blog.xml")).expect("feed missing");
+    assert!(feed.contains(r#"href="../blog/first.html""#));
+    assert!(feed.contains("href=&quot;first.html&quot;"));
+    assert!(!feed.contains("/view/current"));
+}
+
+#[test]
+fn test_feed_author() {
+    let root = blog_workspace("author");
+    let variables = HashMap::from([("author".to_string(), "Jane".to_string())]);
+    assert_eq!(feed_author(&root, &variables), "Jane");
+    let name = root
+        .canonicalize()
+        .expect("Failed to canonicalize workspace")
+        .file_name()
+        .expect("Workspace without a name")
+        .to_string_lossy()
+        .into_owned();
+    assert_eq!(feed_author(&root, &HashMap::new()), name);
+}
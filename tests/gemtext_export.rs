@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use norgmill::renderer::{backend::GemtextBackend, render_norg, RenderContext};
+
+mod common;
+use common::Workspace;
+
+fn render_gemtext(norg: &str) -> String {
+    render_norg(
+        norg,
+        &mut RenderContext::default(),
+        &mut GemtextBackend::default(),
+    )
+    .expect("Failed to render gemtext")
+}
+
+// Gemtext Element Tests
+#[test]
+fn test_gemtext_headings_capped() {
+    let result = render_gemtext("* One\n** Two\n*** Three\n**** Four");
+    assert!(result.contains("# One\n"));
+    assert!(result.contains("## Two\n"));
+    assert!(result.contains("### Three\n"));
+    assert!(result.contains("### Four\n"));
+    assert!(!result.contains("####"));
+}
+
+#[test]
+fn test_gemtext_list_items() {
+    let result = render_gemtext("- first\n- (x) done");
+    assert!(result.contains("* first\n"));
+    assert!(result.contains("* [x] done\n"));
+}
+
+#[test]
+fn test_gemtext_links_hoisted_after_paragraph() {
+    let result = render_gemtext("Read the {https://example.com}[docs] before starting.");
+    assert!(result.contains("Read the docs before starting.\n=> https://example.com docs\n"));
+}
+
+#[test]
+fn test_gemtext_code_block() {
+    let result = render_gemtext("@code rust\nfn main() {}\n@end");
+    assert!(result.contains("```rust\nfn main() {}\n```"));
+}
+
+#[test]
+fn test_gemtext_norg_links_rewritten() {
+    let result = render_gemtext("See {:notes/todo:}[todo].");
+    assert!(result.contains("=> notes/todo.gmi todo"));
+}
+
+// Gemtext Workspace Export Tests
+#[test]
+fn test_gemtext_workspace_export() {
+    let root = Workspace::new("root");
+    let output = Workspace::new("output");
+    std::fs::create_dir_all(root.join("notes")).expect("Failed to create notes dir");
+    std::fs::write(root.join("index.norg"), "Go to {:$/notes/page:}[page].")
+        .expect("Failed to write index");
+    std::fs::write(root.join("notes/page.norg"), "Back to {:$/index:}[index].")
+        .expect("Failed to write page");
+    std::fs::write(root.join("notes/image.png"), "not really an image")
+        .expect("Failed to write image");
+
+    let rendered =
+        norgmill::export::export_workspace(&root, &output, "gmi", &HashMap::new(), |path| {
+            Box::new(GemtextBackend::for_document(path))
+        })
+        .expect("Failed to export workspace");
+    assert_eq!(rendered, 2);
+
+    let index = std::fs::read_to_string(output.join("index.gmi")).expect("index.gmi missing");
+    assert!(index.contains("=> notes/page.gmi page"));
+    let page = std::fs::read_to_string(output.join("notes/page.gmi")).expect("page.gmi missing");
+    assert!(page.contains("=> ../index.gmi index"));
+    assert!(output.join("notes/image.png").exists());
+}
+
+#[test]
+fn test_gemtext_code_block_fence_lines_escaped() {
+    let result = render_gemtext("@code markdown\n```rust\nfn main() {}\n```\n@end");
+    assert!(result.contains("```markdown\n\u{200B}```rust\nfn main() {}\n\u{200B}```\n```"));
+}
+
+#[test]
+fn test_gemtext_workspace_export_skips_output_inside_root() {
+    let root = Workspace::new("root_with_output");
+    std::fs::write(root.join("index.norg"), "* Index").expect("Failed to write index");
+    let output = root.join("out");
+
+    for _ in 0..2 {
+        let rendered =
+            norgmill::export::export_workspace(&root, &output, "gmi", &HashMap::new(), |path| {
+                Box::new(GemtextBackend::for_document(path))
+            })
+            .expect("Failed to export workspace");
+        assert_eq!(rendered, 1);
+    }
+    assert!(output.join("index.gmi").exists());
+    assert!(!output.join("out").exists());
+}
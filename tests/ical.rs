@@ -0,0 +1,61 @@
+use std::path::Path;
+
+use chrono::{TimeZone, Utc};
+use norgmill::{ical::tasks_calendar, tasks::document_tasks};
+
+fn calendar(norg: &str) -> String {
+    let tasks = document_tasks(Path::new("tasks.norg"), norg).expect("Failed to collect tasks");
+    let stamp = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+    tasks_calendar(&tasks, stamp).expect("Failed to generate calendar")
+}
+
+// iCalendar Tests
+#[test]
+fn test_ical_todo() {
+    let result = calendar("- ( |# A|< 2024-01-31 14:30) Pay rent, on time");
+    assert!(result.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(result.ends_with("END:VCALENDAR\r\n"));
+    assert!(result.contains("BEGIN:VTODO\r\n"));
+    assert!(result.contains("SUMMARY:Pay rent\\, on time\r\n"));
+    assert!(result.contains("DUE:20240131T143000\r\n"));
+    assert!(result.contains("STATUS:NEEDS-ACTION\r\n"));
+    assert!(result.contains("PRIORITY:1\r\n"));
+    assert!(result.contains("DTSTAMP:20240115T090000Z\r\n"));
+    assert!(!result.contains("VEVENT"));
+}
+
+#[test]
+fn test_ical_event_and_status() {
+    let result = calendar("- (_|@ 2024-02-01) Cancelled meeting\n- (x|> 2024-01-02) Finished");
+    assert!(result.contains("BEGIN:VEVENT\r\n"));
+    assert!(result.contains("DTSTART;VALUE=DATE:20240201\r\n"));
+    assert!(result.contains("STATUS:CANCELLED\r\n"));
+    assert!(result.contains("DTSTART;VALUE=DATE:20240102\r\n"));
+    assert!(result.contains("STATUS:COMPLETED\r\n"));
+}
+
+#[test]
+fn test_ical_recurring() {
+    let result = calendar("- (+ Thursday) Weekly review");
+    assert!(result.contains("RRULE:FREQ=WEEKLY;BYDAY=TH\r\n"));
+    // next thursday after the export
+    assert!(result.contains("DTSTART;VALUE=DATE:20240118\r\n"));
+
+    let result = calendar("- (+ 5th|< 2024-02-05) Monthly report");
+    assert!(result.contains("RRULE:FREQ=MONTHLY;BYMONTHDAY=5\r\n"));
+    assert!(result.contains("DTSTART;VALUE=DATE:20240205\r\n"));
+}
+
+#[test]
+fn test_ical_undated_tasks_skipped() {
+    let result = calendar("- ( ) Someday");
+    assert!(!result.contains("VTODO"));
+    assert!(!result.contains("VEVENT"));
+}
+
+#[test]
+fn test_ical_long_lines_folded() {
+    let result = calendar(&format!("- ( |< 2024-01-31) {}", "word ".repeat(30)));
+    assert!(result.lines().all(|line| line.len() <= 76));
+    assert!(result.contains("\r\n "));
+}
@@ -0,0 +1,118 @@
+use std::{collections::HashMap, path::Path};
+
+use chrono::NaiveDate;
+use norgmill::journal::{calendar, period_view, Journal, Period};
+
+mod common;
+use common::Workspace;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("invalid test date")
+}
+
+/// workspace with journal entries in january and february of 2024
+fn journal_workspace(name: &str) -> Workspace {
+    let root = Workspace::new(name);
+    for (path, content) in [
+        ("journal/2024/01/02.norg", "Started the garden."),
+        ("journal/2024/01/05.norg", "Planted tomatoes."),
+        ("journal/2024/02/01.norg", "First harvest."),
+        ("journal/template.norg", "Template"),
+        ("notes/index.norg", "Notes"),
+    ] {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).expect("Failed to create directory");
+        std::fs::write(path, content).expect("Failed to write entry");
+    }
+    root
+}
+
+// Journal Tests
+#[test]
+fn test_journal_found() {
+    let root = journal_workspace("found");
+    let journal = Journal::find(&root)
+        .expect("Failed to read journal")
+        .expect("journal not found");
+    assert_eq!(journal.directory, Path::new("journal"));
+    assert_eq!(journal.entries.len(), 3);
+
+    let root = Workspace::new("missing");
+    assert!(Journal::find(&root)
+        .expect("Failed to read journal")
+        .is_none());
+}
+
+#[test]
+fn test_journal_periods() {
+    let week = Period::week(date(2024, 1, 5)).expect("Failed to find week");
+    assert_eq!(week.first(), date(2024, 1, 1));
+    assert_eq!(week.last(), Some(date(2024, 1, 7)));
+    assert_eq!(week.link(), "/journal/week/2024-01-01");
+
+    let month = Period::parse_month("2024-02").expect("Failed to parse month");
+    assert_eq!(month.last(), Some(date(2024, 2, 29)));
+    assert_eq!(month.prev(), Some(Period::Month(date(2024, 1, 1))));
+    assert_eq!(
+        Period::Day(date(2024, 1, 31)).next(),
+        Some(Period::Day(date(2024, 2, 1)))
+    );
+}
+
+#[test]
+fn test_journal_periods_at_supported_date_range_ends() {
+    let last_month = Period::month(NaiveDate::MAX);
+    assert_eq!(last_month.next(), None);
+    assert_eq!(last_month.checked(), None);
+    assert_eq!(Period::Day(NaiveDate::MAX).next(), None);
+    assert_eq!(Period::Day(NaiveDate::MIN).prev(), None);
+    assert_eq!(Period::Day(NaiveDate::MIN).checked(), None);
+    assert_eq!(Period::Week(NaiveDate::MAX).last(), None);
+    assert!(Period::Day(date(2024, 1, 3)).checked().is_some());
+
+    let root = journal_workspace("range_ends");
+    let journal = Journal::find(&root).unwrap().unwrap();
+    let result = calendar(&journal, NaiveDate::MAX, date(2024, 1, 5)).unwrap();
+    assert!(!result.contains(" →</a>"));
+}
+
+#[test]
+fn test_journal_calendar() {
+    let root = journal_workspace("calendar");
+    let journal = Journal::find(&root).unwrap().unwrap();
+    let result = calendar(&journal, date(2024, 1, 1), date(2024, 1, 5)).unwrap();
+    assert!(result.contains("<caption>January 2024</caption>"));
+    assert!(
+        result.contains(r#"<td class="has_entry"><a href="/journal/day/2024-01-02">2</a></td>"#)
+    );
+    assert!(result
+        .contains(r#"<td class="today has_entry"><a href="/journal/day/2024-01-05">5</a></td>"#));
+    assert!(result.contains("<td>3</td>"));
+    assert!(result.contains(r#"href="/journal?month=2024-02""#));
+    assert!(result.contains(r#"href="/journal/month/2024-01""#));
+}
+
+#[test]
+fn test_journal_period_view() {
+    let root = journal_workspace("view");
+    let journal = Journal::find(&root).unwrap().unwrap();
+    let variables = HashMap::new();
+
+    let result = period_view(
+        &root,
+        &journal,
+        Period::month(date(2024, 1, 10)),
+        &variables,
+    )
+    .expect("Failed to render month");
+    assert!(result.contains("Started the garden."));
+    assert!(result.contains("Planted tomatoes."));
+    assert!(!result.contains("First harvest."));
+    assert!(result.contains(r#"href="/view/current/journal/2024/01/02.norg""#));
+
+    let result = period_view(&root, &journal, Period::Day(date(2024, 1, 3)), &variables)
+        .expect("Failed to render day");
+    assert!(result.contains("No journal entries."));
+    assert!(result.contains(r#"href="/journal/day/2024-01-02""#));
+    assert!(result.contains(r#"href="/journal/day/2024-01-04""#));
+}
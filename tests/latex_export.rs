@@ -0,0 +1,87 @@
+use norgmill::renderer::{backend::LatexBackend, render_norg, RenderContext};
+
+fn render_latex(norg: &str) -> String {
+    render_norg(norg, &mut RenderContext::default(), &mut LatexBackend)
+        .expect("Failed to render latex")
+}
+
+// Latex Document Tests
+#[test]
+fn test_latex_complete_document() {
+    let result = render_latex("Some text.");
+    assert!(result.starts_with("\\documentclass{article}"));
+    assert!(result.contains("\\begin{document}"));
+    assert!(result.contains("Some text."));
+    assert!(result.trim_end().ends_with("\\end{document}"));
+}
+
+#[test]
+fn test_latex_document_meta() {
+    let result = render_latex(
+        "@document.meta\ntitle: My Paper\nauthors: Alice, Bob\ncreated: 2024-01-01\n@end",
+    );
+    assert!(result.contains("\\title{My Paper}"));
+    assert!(result.contains("\\author{Alice \\and Bob}"));
+    assert!(result.contains("\\date{2024-01-01}"));
+    assert!(result.contains("\\maketitle"));
+}
+
+#[test]
+fn test_latex_escapes_special_characters() {
+    let result = render_latex("Costs 5$ & 10% of #1_a");
+    assert!(result.contains("5\\$ \\& 10\\% of \\#1\\_a"));
+}
+
+// Latex Structure Tests
+#[test]
+fn test_latex_headings_to_sections() {
+    let result = render_latex("* Intro\n** Details\n*** More");
+    assert!(result.contains("\\section{Intro}\\label{sec:intro}"));
+    assert!(result.contains("\\subsection{Details}"));
+    assert!(result.contains("\\subsubsection{More}"));
+}
+
+#[test]
+fn test_latex_lists() {
+    let result = render_latex("- one\n- (x) done\n\n~ first");
+    assert!(result.contains("\\begin{itemize}"));
+    assert!(result.contains("\\item one"));
+    assert!(result.contains("\\item[$\\boxtimes$] done"));
+    assert!(result.contains("\\begin{enumerate}"));
+    assert!(result.contains("\\item first"));
+}
+
+#[test]
+fn test_latex_brackets_in_item_titles() {
+    let result = render_latex("$ Term ]x\nDefinition text.\n\n- [not] an option");
+    assert!(result.contains("\\item[{Term ]x}] Definition text."));
+    assert!(result.contains("\\item{} [not] an option"));
+}
+
+#[test]
+fn test_latex_code_blocks() {
+    let result = render_latex("@code python\nprint('hi')\n@end\n\n@code rust\nfn main() {}\n@end");
+    assert!(result.contains("\\begin{lstlisting}[language=Python]\nprint('hi')\n\\end{lstlisting}"));
+    assert!(result.contains("\\begin{verbatim}\nfn main() {}\n\\end{verbatim}"));
+}
+
+// Latex Math Tests
+#[test]
+fn test_latex_inline_math_is_native() {
+    let result = render_latex("Energy is $E = mc^2$.");
+    assert!(result.contains("$E = mc^2$"));
+}
+
+#[test]
+fn test_latex_math_block() {
+    let result = render_latex("@math\n\\int_0^1 x\\,dx\n@end");
+    assert!(result.contains("\\[\n\\int_0^1 x\\,dx\n\\]"));
+}
+
+// Latex Footnote Tests
+#[test]
+fn test_latex_footnote_inline() {
+    let result = render_latex("Claim {^ Source}.\n\n^ Source\nThe reference.");
+    assert!(result.contains("Claim \\footnote{The reference.}"));
+    assert!(!result.contains("\\NorgFootnote{source}"));
+}
@@ -290,4 +290,21 @@ fn test_list_with_special_characters() {
     assert!(result.contains("<ul>"));
     assert!(result.contains("<li>Item with &amp; &lt; &gt; &quot; &#x27; characters</li>"));
     assert!(result.contains("</ul>"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_ordered_list_closed_with_ol() {
+    let norg = "~ First step\n~ Second step";
+    let result = parse_and_render_norg(norg).expect("Failed to parse ordered list");
+    assert!(result.contains("<ol>"));
+    assert!(result.contains("</ol>"));
+    assert!(!result.contains("</ul>"));
+}
+
+#[test]
+fn test_list_item_text_not_wrapped_in_paragraph() {
+    let norg = "- Plain item";
+    let result = parse_and_render_norg(norg).expect("Failed to parse list item");
+    assert!(result.contains(">Plain item</li>"));
+    assert!(!result.contains("<p>Plain item</p>"));
+}
@@ -0,0 +1,187 @@
+use norgmill::renderer::{parse_and_render_norg, parse_and_render_norg_with, RenderContext};
+
+mod common;
+use common::Workspace;
+
+// Macro Definition Tests
+#[test]
+fn test_macro_definition_not_rendered() {
+    let norg = r#"=greeting
+Hello from macro
+=end
+
+Some text."#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse macro definition");
+    assert!(!result.contains("Hello from macro"));
+    assert!(result.contains("Some text."));
+}
+
+#[test]
+fn test_macro_invocation_without_parameters() {
+    let norg = r#"=greeting
+Hello from macro
+=end
+
+.greeting"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse macro invocation");
+    assert!(result.contains("Hello from macro"));
+}
+
+#[test]
+fn test_macro_invocation_with_parameters() {
+    let norg = r#"=see url
+(see &url&)
+=end
+
+.see https://wikipedia.com/some-cool-cake-recipe"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse macro with parameters");
+    assert!(result.contains("(see"));
+    assert!(result.contains(">https://wikipedia.com/some-cool-cake-recipe</span>"));
+}
+
+#[test]
+fn test_macro_parameters_are_scoped() {
+    let norg = r#"=show value
+&value&
+=end
+
+.show inside
+
+&value&"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse scoped macro parameters");
+    assert!(result.contains(">inside</span>"));
+    assert!(result.contains("class=\"variable undefined\""));
+}
+
+#[test]
+fn test_macro_undefined() {
+    let norg = ".missing";
+    let result = parse_and_render_norg(norg).expect("Failed to parse undefined macro");
+    assert!(result.contains("<div class=\"macro-error\">undefined macro `missing`</div>"));
+}
+
+#[test]
+fn test_macro_recursive() {
+    let norg = r#"=loop
+.loop
+=end
+
+.loop"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse recursive macro");
+    assert!(result.contains("recursive invocation of macro `loop`"));
+}
+
+// Builtin Macro Tests
+#[test]
+fn test_macro_date_builtin() {
+    let norg = ".date";
+    let result = parse_and_render_norg(norg).expect("Failed to parse date macro");
+    assert!(result.contains("<time datetime=\""));
+}
+
+#[test]
+fn test_macro_date_invalid_format() {
+    let norg = ".date %Q";
+    let result = parse_and_render_norg(norg).expect("Failed to parse date macro with bad format");
+    assert!(result.contains("invalid date format"));
+}
+
+#[test]
+fn test_macro_include_file() {
+    let dir = Workspace::new("include");
+    std::fs::write(dir.join("other.norg"), "Content from the other file.")
+        .expect("Failed to write included file");
+    let norg = ".include other";
+    let mut ctx = RenderContext::default().with_document_path(dir.join("index.norg"));
+    let result = parse_and_render_norg_with(norg, &mut ctx).expect("Failed to parse include macro");
+    assert!(result.contains("<div class=\"include\">"));
+    assert!(result.contains("Content from the other file."));
+}
+
+#[test]
+fn test_macro_include_workspace_root() {
+    let dir = Workspace::new("include_root");
+    std::fs::create_dir_all(dir.join("notes")).expect("Failed to create notes dir");
+    std::fs::write(dir.join("shared.norg"), "Shared content.").expect("Failed to write file");
+    let norg = ".include $/shared";
+    let mut ctx = RenderContext::default()
+        .with_document_path(dir.join("notes/index.norg"))
+        .with_workspace_root(dir.to_path_buf());
+    let result =
+        parse_and_render_norg_with(norg, &mut ctx).expect("Failed to parse workspace include");
+    assert!(result.contains("Shared content."));
+}
+
+#[test]
+fn test_macro_include_cycle() {
+    let dir = Workspace::new("include_cycle");
+    std::fs::write(dir.join("a.norg"), ".include b").expect("Failed to write a.norg");
+    std::fs::write(dir.join("b.norg"), ".include a").expect("Failed to write b.norg");
+    let mut ctx = RenderContext::default().with_document_path(dir.join("a.norg"));
+    let result = parse_and_render_norg_with(".include b", &mut ctx)
+        .expect("Failed to parse recursive include");
+    assert!(result.contains("recursive include of `a`"));
+}
+
+#[test]
+fn test_macro_include_missing_file() {
+    let dir = Workspace::new("include_missing");
+    let mut ctx = RenderContext::default().with_document_path(dir.join("index.norg"));
+    let result = parse_and_render_norg_with(".include nothing", &mut ctx)
+        .expect("Failed to parse missing include");
+    assert!(result.contains("couldn&#x27;t include `nothing`"));
+}
+
+#[test]
+fn test_unknown_ranged_tag_is_not_a_macro() {
+    let norg = r#"|unknown
+Content of the tag
+|end"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse unknown ranged tag");
+    assert!(result.contains("Content of the tag"));
+}
+
+#[test]
+fn test_standard_infirm_tag_is_not_a_macro() {
+    let norg = ".toc\n\nSome text.";
+    let result = parse_and_render_norg(norg).expect("Failed to parse standard infirm tag");
+    assert!(!result.contains("undefined macro"));
+    assert!(result.contains("Some text."));
+}
+
+#[test]
+fn test_ranged_tag_sharing_macro_name_is_not_a_macro() {
+    let norg = r#"=note
+From the macro
+=end
+
+|note
+Content of the tag
+|end"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse tags sharing a name");
+    assert!(result.contains("Content of the tag"));
+    assert!(!result.contains("From the macro"));
+}
+
+#[test]
+fn test_macro_tag_in_code_block_is_not_a_definition() {
+    let norg = r#"@code norg
+=note
+@end
+
+|note
+Content of the tag
+|end"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse macro tag in code block");
+    assert!(result.contains("Content of the tag"));
+}
+
+#[test]
+fn test_macro_date_marks_context() {
+    let mut ctx = RenderContext::default();
+    parse_and_render_norg_with("Today is\n.date", &mut ctx).expect("Failed to parse date macro");
+    assert!(ctx.uses_date());
+    let mut ctx = RenderContext::default();
+    parse_and_render_norg_with("No date here.", &mut ctx).expect("Failed to parse document");
+    assert!(!ctx.uses_date());
+}
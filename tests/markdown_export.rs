@@ -0,0 +1,120 @@
+use norgmill::renderer::{backend::MarkdownBackend, render_norg, RenderContext};
+
+fn render_markdown(norg: &str) -> String {
+    render_norg(
+        norg,
+        &mut RenderContext::default(),
+        &mut MarkdownBackend::default(),
+    )
+    .expect("Failed to render markdown")
+}
+
+// Markdown Heading Tests
+#[test]
+fn test_markdown_headings() {
+    let result = render_markdown("* Heading\n** Sub Heading\nText under it.");
+    assert!(result.contains("# Heading\n"));
+    assert!(result.contains("## Sub Heading\n"));
+    assert!(result.contains("Text under it."));
+    assert!(!result.contains("<div"));
+}
+
+// Markdown Inline Markup Tests
+#[test]
+fn test_markdown_attached_modifiers() {
+    let result = render_markdown("*bold* /italic/ -strike- `code`");
+    assert!(result.contains("**bold**"));
+    assert!(result.contains("*italic*"));
+    assert!(result.contains("~~strike~~"));
+    assert!(result.contains("`code`"));
+}
+
+#[test]
+fn test_markdown_escapes_special_characters() {
+    let result = render_markdown("Text with [brackets] and <tags>.");
+    assert!(result.contains("\\[brackets\\]"));
+    assert!(result.contains("\\<tags\\>"));
+}
+
+// Markdown List Tests
+#[test]
+fn test_markdown_todo_checkboxes() {
+    let result = render_markdown("- (x) done task\n- ( ) open task");
+    assert!(result.contains("- [x] done task"));
+    assert!(result.contains("- [ ] open task"));
+}
+
+#[test]
+fn test_markdown_ordered_list() {
+    let result = render_markdown("~ first\n~ second");
+    assert!(result.contains("1. first"));
+    assert!(result.contains("1. second"));
+}
+
+// Markdown Block Tests
+#[test]
+fn test_markdown_code_block() {
+    let result = render_markdown("@code rust\nfn main() {}\n@end");
+    assert!(result.contains("```rust\nfn main() {}\n```"));
+}
+
+#[test]
+fn test_markdown_quote() {
+    let result = render_markdown("> quoted text");
+    assert!(result.contains("> quoted text"));
+}
+
+#[test]
+fn test_markdown_definition() {
+    let result = render_markdown("$ Term\nDefinition of the term.");
+    assert!(result.contains("**Term**"));
+    assert!(result.contains("Definition of the term."));
+}
+
+#[test]
+fn test_markdown_table() {
+    let result = render_markdown(": A1\nName\n: B1\nValue\n: A2\nfoo\n: B2\nbar");
+    assert!(result.contains("| Name | Value |"));
+    assert!(result.contains("| --- | --- |"));
+    assert!(result.contains("| foo | bar |"));
+}
+
+#[test]
+fn test_markdown_footnote() {
+    let result = render_markdown("Text with note {^ Note}.\n\n^ Note\nFootnote content.");
+    assert!(result.contains("[^note]"));
+    assert!(result.contains("[^note]: Footnote content."));
+}
+
+// Markdown Link Tests
+#[test]
+fn test_markdown_url_link() {
+    let result = render_markdown("{https://example.com}[Example]");
+    assert!(result.contains("[Example](https://example.com)"));
+}
+
+#[test]
+fn test_markdown_norg_file_link_rewritten() {
+    let result = render_markdown("{:notes/todo:}[Todo list]");
+    assert!(result.contains("[Todo list](notes/todo.md)"));
+}
+
+#[test]
+fn test_markdown_norg_file_heading_link() {
+    let result = render_markdown("{:notes:* Some Heading}[heading]");
+    assert!(result.contains("[heading](notes.md#some-heading)"));
+}
+
+#[test]
+fn test_markdown_code_span_not_escaped() {
+    let result = render_markdown("Run `cp a_b [c]`(lang:sh) now.");
+    assert!(result.contains("`cp a_b [c]`"));
+    assert!(!result.contains("\\_"));
+}
+
+#[test]
+fn test_markdown_front_matter_at_start() {
+    let result = render_markdown("Some text.\n\n@document.meta\ntitle: a: b\n@end");
+    assert!(result.starts_with("---\ntitle: \"a: b\"\n---\n"));
+    assert!(result.contains("Some text."));
+}
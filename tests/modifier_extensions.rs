@@ -592,4 +592,46 @@ fn test_extension_with_urls() {
     assert!(result.contains("URL style:"));
     assert!(result.contains("background image"));
     assert!(result.contains("background-image:url(https://example.com/image.jpg)"));
-}
\ No newline at end of file
+}
+// Attached Modifier Extension Suffix Tests
+#[test]
+fn test_attached_extension_suffix_color() {
+    let norg = r#"*some green and bold text!*(color:green) and more"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse color extension suffix");
+    assert!(result.contains("<strong style=\"color:green\">some green and bold text!</strong>"));
+    assert!(result.contains("and more"));
+    assert!(!result.contains("(color:green)"));
+}
+
+#[test]
+fn test_attached_extension_suffix_code_lang() {
+    let norg = r#"`print("This is some python")`(lang:python)"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse lang extension suffix");
+    assert!(result.contains("<code class=\"language-python\">"));
+}
+
+#[test]
+fn test_attached_extension_suffix_multiple_attributes() {
+    let norg = r#"!hidden text!(important|color:red|note:draft)"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse multiple extension attributes");
+    assert!(result.contains("style=\"color:red\""));
+    assert!(result.contains("data-note=\"draft\""));
+    assert!(result.contains("class=\"spoiler important\""));
+}
+
+#[test]
+fn test_attached_extension_suffix_invalid_key_dropped() {
+    let norg = r#"*text*(x/onmouseover:alert|ok:yes)"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse invalid extension key");
+    assert!(result.contains("data-ok=\"yes\""));
+    assert!(!result.contains("onmouseover"));
+    assert!(!result.contains("alert"));
+}
+
+#[test]
+fn test_attached_extension_suffix_invalid_color_dropped() {
+    let norg = r#"*text*(color:red;background:blue) and *more*(color:#12zz34)"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse invalid color extension");
+    assert!(!result.contains("style="));
+    assert!(!result.contains("background"));
+}
@@ -0,0 +1,120 @@
+use std::path::{Path, PathBuf};
+
+use norgmill::navigation::{journal_date, navigation};
+
+mod common;
+use common::Workspace;
+
+fn write(root: &Path, path: &str) {
+    let path = root.join(path);
+    std::fs::create_dir_all(path.parent().unwrap()).expect("Failed to create directory");
+    std::fs::write(path, "content").expect("Failed to write document");
+}
+
+// Navigation Tests
+#[test]
+fn test_navigation_siblings() {
+    let root = Workspace::new("siblings");
+    for path in [
+        "index.norg",
+        "notes/index.norg",
+        "notes/a.norg",
+        "notes/b.norg",
+        "notes/c.norg",
+    ] {
+        write(&root, path);
+    }
+    write(&root, "notes/image.png");
+    write(&root, "notes/.hidden.norg");
+
+    let result = navigation(&root, Path::new("notes/b.norg"));
+    assert_eq!(result.up, Some(PathBuf::from("notes/index.norg")));
+    assert_eq!(result.prev, Some(PathBuf::from("notes/a.norg")));
+    assert_eq!(result.next, Some(PathBuf::from("notes/c.norg")));
+
+    let result = navigation(&root, Path::new("notes/a.norg"));
+    assert_eq!(result.prev, None);
+    let result = navigation(&root, Path::new("notes/c.norg"));
+    assert_eq!(result.next, None);
+}
+
+#[test]
+fn test_navigation_index() {
+    let root = Workspace::new("index");
+    for path in ["index.norg", "notes/index.norg", "notes/a.norg"] {
+        write(&root, path);
+    }
+
+    let result = navigation(&root, Path::new("notes/index.norg"));
+    assert_eq!(result.up, Some(PathBuf::from("index.norg")));
+    assert_eq!(result.prev, None);
+    assert_eq!(result.next, None);
+
+    let result = navigation(&root, Path::new("index.norg"));
+    assert_eq!(result.up, None);
+}
+
+#[test]
+fn test_navigation_journal_dates() {
+    let root = Workspace::new("journal");
+    for path in [
+        "journal/2023/12/31.norg",
+        "journal/2024/01/02.norg",
+        "journal/2024/01/10.norg",
+        "journal/2024/02/01.norg",
+        "journal/template.norg",
+    ] {
+        write(&root, path);
+    }
+
+    let result = navigation(&root, Path::new("journal/2024/01/02.norg"));
+    assert_eq!(result.up, Some(PathBuf::from("journal/2024/01/index.norg")));
+    assert_eq!(result.prev, Some(PathBuf::from("journal/2023/12/31.norg")));
+    assert_eq!(result.next, Some(PathBuf::from("journal/2024/01/10.norg")));
+
+    let result = navigation(&root, Path::new("journal/2024/01/10.norg"));
+    assert_eq!(result.next, Some(PathBuf::from("journal/2024/02/01.norg")));
+
+    // documents without a date follow the directory order
+    let result = navigation(&root, Path::new("journal/template.norg"));
+    assert_eq!(result.prev, None);
+    assert_eq!(result.next, None);
+}
+
+#[test]
+fn test_journal_date() {
+    assert!(journal_date(Path::new("2024/01/31.norg")).is_some());
+    assert!(journal_date(Path::new("2024-01-31.norg")).is_some());
+    assert!(journal_date(Path::new("2024/13/31.norg")).is_none());
+    assert!(journal_date(Path::new("notes.norg")).is_none());
+}
+
+#[test]
+fn test_navigation_breadcrumbs() {
+    let root = Workspace::new("breadcrumbs");
+    write(&root, "projects/garden/plan.norg");
+    std::fs::write(
+        root.join("projects/index.norg"),
+        "@document.meta\ntitle: All Projects\n@end",
+    )
+    .expect("Failed to write index");
+
+    let result = navigation(&root, Path::new("projects/garden/plan.norg"));
+    let titles: Vec<&str> = result
+        .breadcrumbs
+        .iter()
+        .map(|breadcrumb| breadcrumb.title.as_str())
+        .collect();
+    let root_name = root.file_name().unwrap().to_string_lossy();
+    assert_eq!(titles, [root_name.as_ref(), "All Projects", "garden"]);
+    assert_eq!(
+        result.breadcrumbs[2].index,
+        PathBuf::from("projects/garden/index.norg")
+    );
+
+    // the directory of an index is the page itself
+    let result = navigation(&root, Path::new("projects/index.norg"));
+    assert_eq!(result.breadcrumbs.len(), 1);
+    let result = navigation(&root, Path::new("index.norg"));
+    assert!(result.breadcrumbs.is_empty());
+}
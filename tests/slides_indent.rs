@@ -698,4 +698,34 @@ Final paragraph.
     assert!(result.contains("println!(\"Hello, world!\");"));
     assert!(result.contains("</code></pre>"));
     assert!(result.contains("Final paragraph."));
-}
\ No newline at end of file
+}
+// Detached Modifier Suffix Tests
+#[test]
+fn test_list_item_slide_with_code() {
+    let norg = r#"- :
+  This is part of the list item.
+  @code lua
+  print("This is also a part of the list item")
+  @end
+- Second item"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse list item slide");
+    let item_start = result.find("<li").expect("list item should be rendered");
+    let item_end = result[item_start..].find("</li>").expect("list item should be closed") + item_start;
+    let item = &result[item_start..item_end];
+    assert!(item.contains("This is part of the list item."));
+    assert!(item.contains("print(\"This is also a part of the list item\")"));
+    assert!(result.contains("Second item"));
+}
+
+#[test]
+fn test_definition_indent_segment_paragraphs() {
+    let norg = r#"$$ Term
+First paragraph of the definition.
+
+Second paragraph of the definition.
+$$"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse ranged definition");
+    assert!(result.contains("<dt>Term</dt>"));
+    assert!(result.contains("<dd><p>First paragraph of the definition.</p>"));
+    assert!(result.contains("<p>Second paragraph of the definition.</p></dd>"));
+}
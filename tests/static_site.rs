@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use norgmill::{
+    renderer::parse_and_render_norg,
+    site::{build_site, rewrite_links, MANIFEST_FILE},
+};
+
+mod common;
+use common::Workspace;
+
+/// small workspace with an index, a page in a subdirectory and an image
+fn sample_workspace(name: &str) -> Workspace {
+    let root = Workspace::new(name);
+    std::fs::create_dir_all(root.join("notes")).expect("Failed to create notes dir");
+    std::fs::write(root.join("index.norg"), "Go to {:$/notes/page:}[page].")
+        .expect("Failed to write index");
+    std::fs::write(
+        root.join("notes/page.norg"),
+        "* Title\n  Back to {:$/index:}[index] or {:$/notes/page:* Title}[here].",
+    )
+    .expect("Failed to write page");
+    std::fs::write(root.join("notes/image.png"), "not really an image")
+        .expect("Failed to write image");
+    root
+}
+
+// Static Site Tests
+#[test]
+fn test_site_links_rewritten() {
+    let root = sample_workspace("links");
+    let document = std::path::Path::new("notes/page.norg");
+    let body = parse_and_render_norg(
+        "{:$/index:}[index], {:page:* Title}[here] and {https://example.com/?a=1&b=2}[query].",
+    )
+    .expect("Failed to render links");
+    let result = rewrite_links(&body, &root, document);
+    assert!(result.contains(r#"href="../index.html""#));
+    assert!(result.contains(r#"href="page.html#Title_h1""#));
+    assert!(result.contains(r#"href="https://example.com/?a=1&amp;b=2""#));
+    assert!(!result.contains("/view/current"));
+}
+
+#[test]
+fn test_site_build() {
+    let root = sample_workspace("build_root");
+    let output = Workspace::new("build_output");
+
+    let summary =
+        build_site(&root, &output, &HashMap::new(), &[], false).expect("Failed to build site");
+    assert_eq!(summary.rendered, 2);
+
+    let index = std::fs::read_to_string(output.join("index.html")).expect("index.html missing");
+    assert!(index.contains("<!DOCTYPE html>"));
+    assert!(index.contains(r#"href="static/style.css""#));
+    assert!(index.contains(r#"href="notes/page.html""#));
+    let page = std::fs::read_to_string(output.join("notes/page.html")).expect("page.html missing");
+    assert!(page.contains(r#"href="../static/style.css""#));
+    assert!(page.contains(r#"href="../index.html""#));
+    assert!(!page.contains("/view/current"));
+
+    assert!(output.join("notes/image.png").exists());
+    assert!(output.join("notes/page.norg").exists());
+    assert!(output.join("static/scripts.js").exists());
+    assert!(output.join("favicon.svg").exists());
+}
+
+#[test]
+fn test_site_directory_index() {
+    let root = sample_workspace("index_root");
+    let output = Workspace::new("index_output");
+    build_site(&root, &output, &HashMap::new(), &[], false).expect("Failed to build site");
+
+    let index =
+        std::fs::read_to_string(output.join("notes/index.html")).expect("directory index missing");
+    assert!(index.contains(r#"<a href="page.html">page</a>"#));
+    assert!(index.contains(r#"href="../index.html""#));
+}
+
+#[test]
+fn test_site_directory_index_encodes_hrefs() {
+    let root = sample_workspace("index_encoded_root");
+    let output = Workspace::new("index_encoded_output");
+    std::fs::write(root.join("notes/a page & more.norg"), "Content.")
+        .expect("Failed to write page");
+    build_site(&root, &output, &HashMap::new(), &[], false).expect("Failed to build site");
+
+    let index =
+        std::fs::read_to_string(output.join("notes/index.html")).expect("directory index missing");
+    assert!(index.contains(r#"<a href="a%20page%20%26%20more.html">a page &amp; more</a>"#));
+}
+
+#[test]
+fn test_site_output_inside_root_skipped() {
+    let root = sample_workspace("inside_root");
+    let output = root.join("public");
+    let variables = HashMap::new();
+    let summary = build_site(&root, &output, &variables, &[], false).expect("Failed to build site");
+    assert_eq!(summary.rendered, 2);
+
+    let summary =
+        build_site(&root, &output, &variables, &[], false).expect("Failed to rebuild site");
+    assert_eq!(summary.rendered, 0);
+    assert_eq!(summary.copied, 0);
+    assert!(!output.join("public").exists());
+}
+
+#[test]
+fn test_site_incremental_build() {
+    let root = sample_workspace("incremental_root");
+    let output = Workspace::new("incremental_output");
+    let variables = HashMap::new();
+    build_site(&root, &output, &variables, &[], false).expect("Failed to build site");
+    assert!(output.join(MANIFEST_FILE).exists());
+
+    let summary =
+        build_site(&root, &output, &variables, &[], false).expect("Failed to rebuild site");
+    assert_eq!(summary.rendered, 0);
+    assert_eq!(summary.copied, 0);
+    assert_eq!(summary.unchanged, 3);
+
+    std::fs::write(root.join("index.norg"), "Changed index.").expect("Failed to update index");
+    let summary =
+        build_site(&root, &output, &variables, &[], false).expect("Failed to rebuild site");
+    assert_eq!(summary.rendered, 1);
+    let index = std::fs::read_to_string(output.join("index.html")).expect("index.html missing");
+    assert!(index.contains("Changed index."));
+
+    let summary = build_site(&root, &output, &variables, &[], true).expect("Failed to force build");
+    assert_eq!(summary.rendered, 2);
+}
+
+#[test]
+fn test_site_deleted_source_removed() {
+    let root = sample_workspace("deleted_root");
+    let output = Workspace::new("deleted_output");
+    let variables = HashMap::new();
+    build_site(&root, &output, &variables, &[], false).expect("Failed to build site");
+    assert!(output.join("notes/page.html").exists());
+
+    std::fs::remove_file(root.join("notes/page.norg")).expect("Failed to delete page");
+    let summary =
+        build_site(&root, &output, &variables, &[], false).expect("Failed to rebuild site");
+    assert_eq!(summary.removed, 1);
+    // index links to the deleted page, so it is rendered again
+    assert_eq!(summary.rendered, 1);
+    assert!(!output.join("notes/page.html").exists());
+    assert!(!output.join("notes/page.norg").exists());
+    assert!(output.join("notes/image.png").exists());
+}
+
+#[test]
+fn test_site_navigation_links() {
+    let root = sample_workspace("navigation_root");
+    std::fs::write(root.join("notes/other.norg"), "Other page.").expect("Failed to write page");
+    let output = Workspace::new("navigation_output");
+    let variables = HashMap::new();
+    build_site(&root, &output, &variables, &[], false).expect("Failed to build site");
+
+    let page =
+        std::fs::read_to_string(output.join("notes/other.html")).expect("other.html missing");
+    assert!(page.contains(r#"<a href="../notes/index.html">Up</a>"#));
+    assert!(page.contains(r#"<a href="../notes/page.html">Prev</a>"#));
+    assert!(page.contains(r#"<a aria-disabled="true">Next</a>"#));
+    assert!(page.contains(r#"<ol class="breadcrumbs" aria-label="Breadcrumb">"#));
+    assert!(page.contains(r#"<li><a href="../notes/index.html">notes</a></li>"#));
+
+    // adding a sibling changes the links of its neighbours
+    std::fs::write(root.join("notes/zebra.norg"), "Last page.").expect("Failed to write page");
+    build_site(&root, &output, &variables, &[], false).expect("Failed to rebuild site");
+    let page =
+        std::fs::read_to_string(output.join("notes/other.html")).expect("other.html missing");
+    assert!(page.contains(r#"<a href="../notes/zebra.html">Next</a>"#));
+}
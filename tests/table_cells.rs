@@ -545,4 +545,25 @@ This cell contains a nested table:
     // Should have two tables
     assert!(result.matches("<table>").count() >= 2);
     assert!(result.matches("</table>").count() >= 2);
-}
\ No newline at end of file
+}
+#[test]
+fn test_table_cell_position_out_of_range() {
+    let norg = r#": ZZZZZZZZZZZZZZ1
+Very wide cell.
+: A99999999999
+Very long cell."#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse table cells out of range");
+    // positions out of range are placed after the previous cell
+    assert!(result.contains("<td>Very wide cell.</td><td>Very long cell.</td>"));
+}
+
+#[test]
+fn test_table_sparse_far_column_is_bounded() {
+    let rows: String = (1..=8).map(|row| format!(": A{row}\nRow {row}.\n")).collect();
+    let norg = format!("{rows}: XFD8\nFar away cell.");
+    let result = parse_and_render_norg(&norg).expect("Failed to parse table with far away cell");
+    assert!(result.contains("<td>Row 1.</td>"));
+    assert!(result.contains("<td>Row 8.</td>"));
+    assert!(!result.contains("Far away cell."));
+    assert!(result.matches("<td>").count() <= 65_536);
+}
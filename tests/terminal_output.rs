@@ -0,0 +1,85 @@
+use norgmill::renderer::{backend::TerminalBackend, render_norg, RenderContext};
+
+fn render_plain(norg: &str, width: usize) -> String {
+    render_norg(
+        norg,
+        &mut RenderContext::default(),
+        &mut TerminalBackend::plain(width),
+    )
+    .expect("Failed to render for terminal")
+}
+
+fn render_ansi(norg: &str) -> String {
+    render_norg(
+        norg,
+        &mut RenderContext::default(),
+        &mut TerminalBackend::ansi(80),
+    )
+    .expect("Failed to render for terminal")
+}
+
+// Plain Text Tests
+#[test]
+fn test_terminal_plain_has_no_escape_codes() {
+    let result = render_plain("* Heading\nSome *bold* and /italic/ text.", 80);
+    assert!(result.contains("# Heading"));
+    assert!(result.contains("Some bold and italic text."));
+    assert!(!result.contains('\x1b'));
+}
+
+#[test]
+fn test_terminal_wraps_paragraphs() {
+    let result = render_plain(
+        "one two three four five six seven eight nine ten eleven twelve thirteen",
+        20,
+    );
+    assert!(result.lines().count() > 1);
+    assert!(result.lines().all(|line| line.chars().count() <= 20));
+}
+
+#[test]
+fn test_terminal_lists() {
+    let result = render_plain("- first\n- ( ) todo\n\n~ one\n~ two", 80);
+    assert!(result.contains("• first"));
+    assert!(result.contains("• [ ] todo"));
+    assert!(result.contains("1. one"));
+    assert!(result.contains("2. two"));
+}
+
+#[test]
+fn test_terminal_boxed_code_block() {
+    let result = render_plain("@code rust\nfn main() {}\n@end", 80);
+    assert!(result.contains("┌─ rust"));
+    assert!(result.contains("│ fn main() {} │"));
+    assert!(result.contains("└"));
+}
+
+#[test]
+fn test_terminal_code_block_keeps_tab_indentation() {
+    let result = render_plain("@code make\nall:\n\tcargo build\n@end", 80);
+    assert!(result.contains("│ all:            │"));
+    assert!(result.contains("│     cargo build │"));
+}
+
+#[test]
+fn test_terminal_footnotes_at_end() {
+    let result = render_plain("Text {^ Note}.\n\n^ Note\nFootnote content.", 80);
+    let footnote = result
+        .find("[Note] Footnote content.")
+        .expect("footnote missing");
+    assert!(footnote > result.find("Text").expect("text missing"));
+}
+
+#[test]
+fn test_terminal_strips_control_characters() {
+    let result = render_plain("bad \u{1b}[31m text", 80);
+    assert!(!result.contains('\x1b'));
+}
+
+// Ansi Tests
+#[test]
+fn test_terminal_ansi_bold_and_italic() {
+    let result = render_ansi("*bold* /italic/");
+    assert!(result.contains("\x1b[1mbold\x1b[22m"));
+    assert!(result.contains("\x1b[3mitalic\x1b[23m"));
+}
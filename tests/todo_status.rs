@@ -0,0 +1,95 @@
+use norgmill::renderer::{
+    parse_and_render_norg, parse_and_render_norg_with,
+    todo::{set_todo_status, TodoChange},
+    RenderContext,
+};
+
+fn render_with_todo_items(norg: &str) -> String {
+    let mut ctx = RenderContext::default().with_todo_items();
+    parse_and_render_norg_with(norg, &mut ctx).expect("Failed to render with TODO items")
+}
+
+fn changed(change: Option<TodoChange>) -> String {
+    match change {
+        Some(TodoChange::Changed(output)) => output,
+        other => panic!("Expected a changed source, got {other:?}"),
+    }
+}
+
+// TODO Item Number Tests
+#[test]
+fn test_todo_items_are_numbered() {
+    let norg = "- ( ) First task\n- Not a task\n  -- (x) Nested task\n~ (?) Ordered task\n";
+    let result = render_with_todo_items(norg);
+    assert!(result.contains(r#"data-todo="0""#));
+    assert!(result.contains(r#"data-todo="1""#));
+    assert!(result.contains(r#"data-todo="2""#));
+    assert!(!result.contains(r#"data-todo="3""#));
+}
+
+#[test]
+fn test_todo_items_are_optional() {
+    let result = parse_and_render_norg("- ( ) Task").expect("Failed to render");
+    assert!(!result.contains("data-todo"));
+}
+
+#[test]
+fn test_todo_items_of_ranged_tags_are_not_numbered() {
+    let norg = "|details\n- ( ) Hidden task\n|end\n- ( ) Document task\n";
+    let result = render_with_todo_items(norg);
+    assert!(result.contains(r#"data-todo="0""#));
+    assert!(!result.contains(r#"data-todo="1""#));
+}
+
+// TODO Write Back Tests
+#[test]
+fn test_set_todo_status() {
+    let norg = "- ( ) Undone task\n- (x) Done task\n";
+    let result = changed(set_todo_status(norg, 0, "done"));
+    assert_eq!(result, "- (x) Undone task\n- (x) Done task\n");
+
+    let result = changed(set_todo_status(norg, 1, "undone"));
+    assert_eq!(result, "- ( ) Undone task\n- ( ) Done task\n");
+
+    let norg = "- (+ Thursday) Recurring task\n";
+    let result = changed(set_todo_status(norg, 0, "done"));
+    assert_eq!(result, "- (x) Recurring task\n");
+}
+
+#[test]
+fn test_set_todo_status_with_other_extensions() {
+    let norg = "- (x|# A) Prioritized task\n";
+    let result = changed(set_todo_status(norg, 0, "undone"));
+    assert_eq!(result, "- ( |# A) Prioritized task\n");
+}
+
+#[test]
+fn test_set_todo_status_unchanged() {
+    let norg = "- (+ Thursday) Recurring task\n- (x) Done task\n";
+    assert_eq!(
+        set_todo_status(norg, 0, "recurring"),
+        Some(TodoChange::Unchanged)
+    );
+    assert_eq!(
+        set_todo_status(norg, 1, "done"),
+        Some(TodoChange::Unchanged)
+    );
+}
+
+#[test]
+fn test_set_todo_status_refuses_unknown_items() {
+    let norg = "- ( ) Undone task\n";
+    assert!(set_todo_status(norg, 1, "done").is_none());
+    assert!(set_todo_status(norg, 0, "finished").is_none());
+}
+
+#[test]
+fn test_set_todo_status_skips_unparsed_statuses() {
+    // looks like a TODO item in the source, but the parser takes it as the content of the example
+    let norg = "|example\n- ( ) Example task\n|end\n- ( ) Real task\n";
+    let result = changed(set_todo_status(norg, 0, "done"));
+    assert_eq!(
+        result,
+        "|example\n- ( ) Example task\n|end\n- (x) Real task\n"
+    );
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use norgmill::renderer::{parse_and_render_norg_with, IncludeCache, RenderContext};
+
+mod common;
+use common::Workspace;
+
+const RECIPES: &str = r#"* Breakfast
+Pancakes with syrup.
+
+* Dinner
+Pasta with sauce.
+** Dessert
+Ice cream.
+"#;
+
+// Heading Transclusion Tests
+#[test]
+fn test_include_heading_from_file() {
+    let dir = Workspace::new("heading");
+    std::fs::write(dir.join("recipes.norg"), RECIPES).expect("Failed to write recipes");
+    let mut ctx = RenderContext::default()
+        .with_document_path(dir.join("index.norg"))
+        .with_workspace_root(dir.to_path_buf());
+    let result = parse_and_render_norg_with(".include {:recipes:* Dinner}", &mut ctx)
+        .expect("Failed to parse heading include");
+    assert!(result.contains("Pasta with sauce."));
+    assert!(result.contains("Ice cream."));
+    assert!(!result.contains("Pancakes with syrup."));
+}
+
+#[test]
+fn test_include_nested_heading() {
+    let dir = Workspace::new("nested_heading");
+    std::fs::write(dir.join("recipes.norg"), RECIPES).expect("Failed to write recipes");
+    let mut ctx = RenderContext::default().with_document_path(dir.join("index.norg"));
+    let result = parse_and_render_norg_with(".include {:recipes:** dessert}", &mut ctx)
+        .expect("Failed to parse nested heading include");
+    assert!(result.contains("Ice cream."));
+    assert!(!result.contains("Pasta with sauce."));
+}
+
+#[test]
+fn test_include_heading_source_link() {
+    let dir = Workspace::new("source_link");
+    std::fs::create_dir_all(dir.join("food")).expect("Failed to create food dir");
+    std::fs::write(dir.join("food/recipes.norg"), RECIPES).expect("Failed to write recipes");
+    let mut ctx = RenderContext::default()
+        .with_document_path(dir.join("index.norg"))
+        .with_workspace_root(dir.to_path_buf());
+    let result = parse_and_render_norg_with(".include {:food/recipes:* Breakfast}", &mut ctx)
+        .expect("Failed to parse include with source link");
+    assert!(result.contains(
+        "<a class=\"include-source\" href=\"/view/current/food/recipes.norg#Breakfast_h1\">"
+    ));
+}
+
+#[test]
+fn test_include_missing_heading() {
+    let dir = Workspace::new("missing_heading");
+    std::fs::write(dir.join("recipes.norg"), RECIPES).expect("Failed to write recipes");
+    let mut ctx = RenderContext::default().with_document_path(dir.join("index.norg"));
+    let result = parse_and_render_norg_with(".include {:recipes:* Lunch}", &mut ctx)
+        .expect("Failed to parse include of missing heading");
+    assert!(result.contains("heading `Lunch` not found in `recipes`"));
+}
+
+#[test]
+fn test_include_records_dependencies() {
+    let dir = Workspace::new("dependencies");
+    std::fs::write(dir.join("recipes.norg"), RECIPES).expect("Failed to write recipes");
+    std::fs::write(dir.join("other.norg"), ".include {:recipes:* Breakfast}")
+        .expect("Failed to write other");
+    let mut ctx = RenderContext::default().with_document_path(dir.join("index.norg"));
+    parse_and_render_norg_with(".include other", &mut ctx).expect("Failed to parse nested include");
+    let dependencies: Vec<_> = ctx
+        .dependencies()
+        .iter()
+        .filter_map(|dep| dep.file_name())
+        .collect();
+    assert_eq!(dependencies, ["other.norg", "recipes.norg"]);
+}
+
+#[test]
+fn test_include_heading_recursive() {
+    let dir = Workspace::new("recursive");
+    std::fs::write(
+        dir.join("notes.norg"),
+        "* Section\n.include {:index:* Start}\n",
+    )
+    .expect("Failed to write notes");
+    let mut ctx = RenderContext::default().with_document_path(dir.join("index.norg"));
+    std::fs::write(
+        dir.join("index.norg"),
+        "* Start\n.include {:notes:* Section}\n",
+    )
+    .expect("Failed to write index");
+    let result = parse_and_render_norg_with("* Start\n.include {:notes:* Section}\n", &mut ctx)
+        .expect("Failed to parse recursive heading include");
+    assert!(result.contains("recursive include of `index`"));
+}
+
+#[test]
+fn test_include_cache_follows_modifications() {
+    let dir = Workspace::new("cache");
+    std::fs::write(dir.join("recipes.norg"), RECIPES).expect("Failed to write recipes");
+    let cache = Arc::new(IncludeCache::default());
+    let render = || {
+        let mut ctx = RenderContext::default()
+            .with_document_path(dir.join("index.norg"))
+            .with_include_cache(cache.clone());
+        parse_and_render_norg_with(".include {:recipes:* Breakfast}", &mut ctx)
+            .expect("Failed to parse cached include")
+    };
+    assert!(render().contains("Pancakes with syrup."));
+    assert!(render().contains("Pancakes with syrup."));
+
+    std::fs::write(dir.join("recipes.norg"), "* Breakfast\nWaffles.")
+        .expect("Failed to update recipes");
+    // modification time has to change for the cached copy to be replaced, even on coarse file systems
+    std::fs::File::options()
+        .write(true)
+        .open(dir.join("recipes.norg"))
+        .and_then(|file| {
+            file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(10))
+        })
+        .expect("Failed to update modification time");
+    let result = render();
+    assert!(result.contains("Waffles."));
+    assert!(!result.contains("Pancakes with syrup."));
+}
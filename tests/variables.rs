@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use norgmill::renderer::{parse_and_render_norg, parse_and_render_norg_with, RenderContext};
+
+// Document Meta Variables Tests
+#[test]
+fn test_variable_from_document_meta() {
+    let norg = r#"@document.meta
+title: My Notes
+authors: someone
+@end
+
+This page is &title& by &authors&."#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse meta variables");
+    assert!(result.contains("<span class=\"variable\" title=\"title\">My Notes</span>"));
+    assert!(result.contains("<span class=\"variable\" title=\"authors\">someone</span>"));
+}
+
+#[test]
+fn test_variable_meta_defined_after_usage() {
+    let norg = r#"Version &version& is released.
+
+@document.meta
+version: 1.2.0
+@end"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse meta variable defined later");
+    assert!(result.contains(">1.2.0</span>"));
+}
+
+// Set Tag Tests
+#[test]
+fn test_variable_from_set_tag_parameters() {
+    let norg = r#"=set project norgmill
+=end
+
+Welcome to &project&."#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse set tag with parameters");
+    assert!(result.contains("<span class=\"variable\" title=\"project\">norgmill</span>"));
+}
+
+#[test]
+fn test_variable_from_set_tag_content() {
+    let norg = r#"=set greeting
+Hello there
+=end
+
+&greeting&, friend."#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse set tag with content");
+    assert!(result.contains(">Hello there</span>"));
+}
+
+#[test]
+fn test_variable_set_tag_overrides_meta() {
+    let norg = r#"@document.meta
+title: Meta Title
+@end
+
+=set title Overridden
+=end
+
+&title&"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse overridden variable");
+    assert!(result.contains(">Overridden</span>"));
+}
+
+// Workspace Variables Tests
+#[test]
+fn test_variable_from_workspace() {
+    let norg = "Maintained by &owner&.";
+    let variables = HashMap::from([("owner".to_string(), "the team".to_string())]);
+    let result = parse_and_render_norg_with(norg, &mut RenderContext::with_variables(variables))
+        .expect("Failed to parse workspace variable");
+    assert!(result.contains(">the team</span>"));
+}
+
+#[test]
+fn test_variable_document_overrides_workspace() {
+    let norg = r#"@document.meta
+owner: document owner
+@end
+
+&owner&"#;
+    let variables = HashMap::from([("owner".to_string(), "workspace owner".to_string())]);
+    let result = parse_and_render_norg_with(norg, &mut RenderContext::with_variables(variables))
+        .expect("Failed to parse overridden workspace variable");
+    assert!(result.contains(">document owner</span>"));
+    assert!(!result.contains("workspace owner"));
+}
+
+// Undefined Variables Tests
+#[test]
+fn test_variable_undefined() {
+    let norg = "Value is &missing&.";
+    let result = parse_and_render_norg(norg).expect("Failed to parse undefined variable");
+    assert!(result.contains("class=\"variable undefined\""));
+    assert!(result.contains("&amp;missing&amp;"));
+}
+
+#[test]
+fn test_variable_value_is_escaped() {
+    let norg = r#"=set html <b>bold</b>
+=end
+
+&html&"#;
+    let result = parse_and_render_norg(norg).expect("Failed to parse variable with html");
+    assert!(result.contains("&lt;b&gt;bold&lt;/b&gt;"));
+    assert!(!result.contains("<b>bold</b>"));
+}